@@ -7,15 +7,27 @@ use std::{
 use bytes::Bytes;
 use futures::{Stream, StreamExt, TryStreamExt};
 use http_body::SizeHint;
+use thiserror::Error;
 
 use crate::header::HeaderMap;
 
+#[cfg(feature = "io")]
+const READER_CHUNK_SIZE: usize = 64 * 1024;
+
 #[derive(Debug)]
 pub enum BodyComponent {
     Data(Bytes),
     Trailers(HeaderMap),
 }
 
+#[derive(Error, Debug)]
+pub enum CollectError {
+    #[error("body exceeded the limit of {0} bytes")]
+    TooLarge(usize),
+    #[error(transparent)]
+    Stream(#[from] anyhow::Error),
+}
+
 //TODO: docs
 pub enum Body {
     Bytes(Vec<u8>),
@@ -83,27 +95,155 @@ impl Body {
         }
     }
 
+    /// Appends `trailers` after the end of this body's data.
+    ///
+    /// Trailers are only transmitted over HTTP/2, or over HTTP/1.1 with `transfer-encoding: chunked` to clients that sent `te: trailers`.
+    /// If the body already has trailers, only the first set is sent.
+    pub fn with_trailers(self, trailers: HeaderMap) -> Self {
+        match self {
+            Body::Bytes(bytes) => Self::bytes_and_trailers(bytes, trailers),
+            Body::Stream { size_hint, stream } => Body::Stream {
+                size_hint,
+                stream: Box::pin(stream.chain(futures::stream::once(async move {
+                    Ok(BodyComponent::Trailers(trailers))
+                }))),
+            },
+        }
+    }
+
     pub async fn collect(self) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.collect_with_trailers().await?.0)
+    }
+
+    /// Buffers the body, also returning the trailers if any were received.
+    pub async fn collect_with_trailers(
+        self,
+    ) -> Result<(Vec<u8>, Option<HeaderMap>), anyhow::Error> {
         match self {
-            Body::Bytes(x) => Ok(x),
+            Body::Bytes(x) => Ok((x, None)),
             Body::Stream {
                 size_hint,
                 mut stream,
             } => {
                 let mut out = Vec::with_capacity(size_hint.unwrap_or_default());
+                let mut trailers = None;
                 while let Some(component) = stream.next().await.transpose()? {
                     match component {
                         BodyComponent::Data(data) => {
                             out.extend_from_slice(&data[..]);
                         }
-                        BodyComponent::Trailers(_) => (),
+                        BodyComponent::Trailers(x) => trailers = Some(x),
                     }
                 }
-                Ok(out)
+                Ok((out, trailers))
             }
         }
     }
 
+    /// Buffers the body like `collect`, but fails with `CollectError::TooLarge` once more than `max` bytes are received.
+    /// The size hint is checked up front, so bodies known to be too large are rejected without reading them.
+    pub async fn collect_limited(self, max: usize) -> Result<Vec<u8>, CollectError> {
+        Ok(self.collect_limited_with_trailers(max).await?.0)
+    }
+
+    /// Same as `collect_limited`, also returning the trailers if any were received.
+    pub async fn collect_limited_with_trailers(
+        self,
+        max: usize,
+    ) -> Result<(Vec<u8>, Option<HeaderMap>), CollectError> {
+        match self {
+            Body::Bytes(bytes) => {
+                if bytes.len() > max {
+                    return Err(CollectError::TooLarge(max));
+                }
+                Ok((bytes, None))
+            }
+            Body::Stream {
+                size_hint,
+                mut stream,
+            } => {
+                let size_hint = size_hint.unwrap_or_default();
+                if size_hint > max {
+                    return Err(CollectError::TooLarge(max));
+                }
+                let mut out = Vec::with_capacity(size_hint);
+                let mut trailers = None;
+                while let Some(component) = stream.next().await.transpose()? {
+                    match component {
+                        BodyComponent::Data(data) => {
+                            if out.len() + data.len() > max {
+                                return Err(CollectError::TooLarge(max));
+                            }
+                            out.extend_from_slice(&data[..]);
+                        }
+                        BodyComponent::Trailers(x) => trailers = Some(x),
+                    }
+                }
+                Ok((out, trailers))
+            }
+        }
+    }
+
+    /// Streams the contents of `reader` until EOF. `size_hint` should be the exact number of bytes that will be read, if known.
+    #[cfg(feature = "io")]
+    pub fn from_reader(
+        reader: impl tokio::io::AsyncRead + Send + Sync + 'static,
+        size_hint: Option<usize>,
+    ) -> Self {
+        let stream = tokio_util::io::ReaderStream::with_capacity(reader, READER_CHUNK_SIZE);
+        Body::Stream {
+            size_hint,
+            stream: Box::pin(
+                stream
+                    .map_ok(BodyComponent::Data)
+                    .map_err(anyhow::Error::from),
+            ),
+        }
+    }
+
+    /// The exact length of the body's data, if known.
+    pub fn size_hint(&self) -> Option<usize> {
+        match self {
+            Body::Bytes(bytes) => Some(bytes.len()),
+            Body::Stream { size_hint, .. } => *size_hint,
+        }
+    }
+
+    /// Calls `on_chunk` with each chunk of data as it is streamed, and `on_end` with the total data length and the trailers, if any,
+    /// once the body ends. The body itself is passed through unchanged, i.e. to checksum or audit it.
+    /// `on_end` is not called if the body fails or is dropped before it ends.
+    pub fn inspect(
+        self,
+        on_chunk: impl FnMut(&Bytes) + Send + Sync + 'static,
+        on_end: impl FnOnce(usize, Option<&HeaderMap>) + Send + Sync + 'static,
+    ) -> Self {
+        Body::Stream {
+            size_hint: self.size_hint(),
+            stream: Box::pin(InspectStream {
+                inner: self.into_stream(),
+                on_chunk,
+                on_end: Some(on_end),
+                length: 0,
+                trailers: None,
+            }),
+        }
+    }
+
+    /// Streams the body with `f` applied to each chunk of data, passing trailers through unchanged.
+    /// If `f` fails, the error is yielded and the body ends. The size hint is dropped, as `f` may change the length.
+    pub fn map_data(
+        self,
+        f: impl FnMut(Bytes) -> Result<Bytes, anyhow::Error> + Send + Sync + 'static,
+    ) -> Self {
+        Body::Stream {
+            size_hint: None,
+            stream: Box::pin(MapDataStream {
+                inner: Some(self.into_stream()),
+                f,
+            }),
+        }
+    }
+
     pub fn into_stream(
         self,
     ) -> Pin<Box<dyn Stream<Item = Result<BodyComponent, anyhow::Error>> + Send + Sync + 'static>>
@@ -120,6 +260,88 @@ impl Body {
     }
 }
 
+struct InspectStream<C, E> {
+    inner: BodyStream,
+    on_chunk: C,
+    on_end: Option<E>,
+    length: usize,
+    trailers: Option<HeaderMap>,
+}
+
+// the callbacks are never pinned
+impl<C, E> Unpin for InspectStream<C, E> {}
+
+impl<C, E> Stream for InspectStream<C, E>
+where
+    C: FnMut(&Bytes),
+    E: FnOnce(usize, Option<&HeaderMap>),
+{
+    type Item = Result<BodyComponent, anyhow::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let next = match this.inner.poll_next_unpin(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(next) => next,
+        };
+        match &next {
+            Some(Ok(BodyComponent::Data(data))) => {
+                this.length += data.len();
+                (this.on_chunk)(data);
+            }
+            Some(Ok(BodyComponent::Trailers(trailers))) => {
+                this.trailers = Some(trailers.clone());
+            }
+            Some(Err(_)) => {
+                this.on_end = None;
+            }
+            None => {
+                if let Some(on_end) = this.on_end.take() {
+                    on_end(this.length, this.trailers.as_ref());
+                }
+            }
+        }
+        Poll::Ready(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+struct MapDataStream<F> {
+    /// `None` once `f` failed.
+    inner: Option<BodyStream>,
+    f: F,
+}
+
+// `f` is never pinned
+impl<F> Unpin for MapDataStream<F> {}
+
+impl<F> Stream for MapDataStream<F>
+where
+    F: FnMut(Bytes) -> Result<Bytes, anyhow::Error>,
+{
+    type Item = Result<BodyComponent, anyhow::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let Some(inner) = this.inner.as_mut() else {
+            return Poll::Ready(None);
+        };
+        match inner.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(BodyComponent::Data(data)))) => match (this.f)(data) {
+                Ok(data) => Poll::Ready(Some(Ok(BodyComponent::Data(data)))),
+                Err(e) => {
+                    this.inner = None;
+                    Poll::Ready(Some(Err(e)))
+                }
+            },
+            other => other,
+        }
+    }
+}
+
 impl Into<Body> for Vec<u8> {
     fn into(self) -> Body {
         Body::Bytes(self)
@@ -248,8 +470,61 @@ impl http_body::Body for BodyWrapper {
                 size_hint,
                 stream: _,
             } => size_hint
-                .map(|x| SizeHint::with_exact(x as u64))
+                // the hint is for the whole body, but only the remainder is left to stream
+                .map(|x| SizeHint::with_exact(x.saturating_sub(self.bytes_streamed) as u64))
                 .unwrap_or_default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn test_inspect_passes_through() {
+        let chunks = Arc::new(Mutex::new(vec![]));
+        let end = Arc::new(Mutex::new(None));
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", "abc");
+        let body = Body::Bytes(b"hello".to_vec())
+            .with_trailers(trailers.clone())
+            .inspect(
+                {
+                    let chunks = chunks.clone();
+                    move |x| chunks.lock().unwrap().push(x.clone())
+                },
+                {
+                    let end = end.clone();
+                    move |length, trailers| *end.lock().unwrap() = Some((length, trailers.cloned()))
+                },
+            );
+        assert_eq!(body.size_hint(), Some(5));
+        let (data, received) = futures::executor::block_on(body.collect_with_trailers()).unwrap();
+        assert_eq!(data, b"hello");
+        assert_eq!(received, Some(trailers.clone()));
+        assert_eq!(*chunks.lock().unwrap(), vec![Bytes::from_static(b"hello")]);
+        assert_eq!(*end.lock().unwrap(), Some((5, Some(trailers))));
+    }
+
+    #[test]
+    fn test_map_data() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", "abc");
+        let body = Body::Bytes(b"hello".to_vec())
+            .with_trailers(trailers.clone())
+            .map_data(|x| Ok(x.to_ascii_uppercase().into()));
+        let (data, received) = futures::executor::block_on(body.collect_with_trailers()).unwrap();
+        assert_eq!(data, b"HELLO");
+        assert_eq!(received, Some(trailers));
+
+        let body = Body::Bytes(b"hello".to_vec())
+            .with_trailers(HeaderMap::new())
+            .map_data(|_| Err(anyhow::anyhow!("failed")));
+        let mut stream = body.into_stream();
+        assert!(futures::executor::block_on(stream.next()).unwrap().is_err());
+        assert!(futures::executor::block_on(stream.next()).is_none());
+    }
+}
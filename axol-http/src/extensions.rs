@@ -20,6 +20,8 @@ struct ExtensionInner {
 struct ExtensionItem {
     index: usize,
     ever_fetched: bool,
+    /// From `std::any::type_name`, for debugging only
+    type_name: &'static str,
 }
 
 pub enum InsertEffect {
@@ -64,6 +66,7 @@ impl Extensions {
             ExtensionItem {
                 index: target_index,
                 ever_fetched: false,
+                type_name: std::any::type_name::<T>(),
             },
         );
         inner.values.push(Some(Arc::new(val)));
@@ -87,6 +90,16 @@ impl Extensions {
         Some(unsafe { std::mem::transmute(value) })
     }
 
+    /// Gets a clone of an extension value.
+    /// Unlike `get`, this doesn't prevent the value from being removed later, and unlike `get_arc`, it doesn't hold a reference that blocks removal.
+    /// Prefer this over `get` for small `Clone` values that are peeked at by middleware (i.e. configuration or ids), when a handler might want to `remove` them.
+    pub fn get_cloned<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        let inner = self.inner.lock().unwrap();
+        let index = inner.map.get(&TypeId::of::<T>())?;
+        let value: &T = (**inner.values.get(index.index)?.as_ref()?).downcast_ref()?;
+        Some(value.clone())
+    }
+
     /// Gets a reference to an extension value.
     /// Since it returns an `Arc` and tracks it's deallocation, it does not prevent a value from being manually removed.
     /// However, while the `Arc` is alive, it cannot be removed.
@@ -115,6 +128,34 @@ impl Extensions {
         }
     }
 
+    /// Number of extensions present, not counting removed ones.
+    pub fn len(&self) -> usize {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .map
+            .values()
+            .filter(|x| matches!(inner.values.get(x.index), Some(Some(_))))
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Type names of the extensions present, sorted. i.e. for logging why an `Extension<T>` extractor failed.
+    /// Names come from `std::any::type_name`, so they are only meant for debugging.
+    pub fn type_names(&self) -> Vec<&'static str> {
+        let inner = self.inner.lock().unwrap();
+        let mut out = inner
+            .map
+            .values()
+            .filter(|x| matches!(inner.values.get(x.index), Some(Some(_))))
+            .map(|x| x.type_name)
+            .collect::<Vec<_>>();
+        out.sort_unstable();
+        out
+    }
+
     pub fn extend(&self, other: &Extensions) {
         let inner = other.inner.lock().unwrap();
         let mut this = self.inner.lock().unwrap();
@@ -130,6 +171,7 @@ impl Extensions {
                 index: this.values.len(),
                 // the old lifetime is necessarily over since it's being dropped
                 ever_fetched: false,
+                type_name: index.type_name,
             };
             this.map.insert(type_id, ext_item);
             this.values.push(Some(item));
@@ -173,6 +215,8 @@ impl From<http::Extensions> for Extensions {
                 let item = ExtensionItem {
                     index: inner.values.len(),
                     ever_fetched: false,
+                    // `http::Extensions` doesn't keep type names
+                    type_name: "<unknown>",
                 };
                 inner.map.insert(type_id, item);
                 inner.values.push(Some(Arc::from(value)));
@@ -184,6 +228,56 @@ impl From<http::Extensions> for Extensions {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Value(u32);
+
+    #[test]
+    fn test_get_cloned_allows_remove() {
+        let extensions = Extensions::new();
+        extensions.insert(Value(1));
+        assert_eq!(extensions.get_cloned::<Value>(), Some(Value(1)));
+        assert!(matches!(
+            extensions.remove::<Value>(),
+            Some(Removed::Removed(Value(1)))
+        ));
+        assert_eq!(extensions.get_cloned::<Value>(), None);
+    }
+
+    #[test]
+    fn test_type_names() {
+        let extensions = Extensions::new();
+        assert!(extensions.is_empty());
+        extensions.insert(Value(1));
+        extensions.insert(Value(2));
+        extensions.insert(5u8);
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(
+            extensions.type_names(),
+            vec!["axol_http::extensions::tests::Value", "u8"]
+        );
+        extensions.remove::<u8>();
+        assert_eq!(
+            extensions.type_names(),
+            vec!["axol_http::extensions::tests::Value"]
+        );
+    }
+
+    #[test]
+    fn test_get_invalidates_remove() {
+        let extensions = Extensions::new();
+        extensions.insert(Value(1));
+        assert_eq!(extensions.get::<Value>(), Some(&Value(1)));
+        assert!(matches!(
+            extensions.remove::<Value>(),
+            Some(Removed::Invalidated)
+        ));
+    }
+}
+
 // not possible in stable rust atm (converting Arc<T> -> Box<T> while ?Sized)
 // maybe possible with lots of assumptions and asm?
 
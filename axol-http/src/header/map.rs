@@ -6,6 +6,9 @@ use thiserror::Error;
 
 use super::{header_name, TypedHeader};
 
+/// Headers whose values can't be combined into a single comma-separated value, see RFC 9110 section 5.3.
+const NON_COMBINABLE_HEADERS: &[&str] = &["set-cookie"];
+
 /// This is a multimap representing HTTP headers.
 /// Not that this is not a true hashmap, as the count of headers is generally too small to be worth representing as a map.
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
@@ -468,6 +471,12 @@ impl HeaderMap {
         H::decode(raw).ok()
     }
 
+    /// Like `get_typed`, but malformed headers produce `Some(Err(_))` rather than `None`.
+    pub fn try_get_typed<H: TypedHeader>(&self) -> Option<Result<H, crate::typed_headers::Error>> {
+        let raw = self.get(H::name())?;
+        Some(H::decode(raw))
+    }
+
     /// Returns a view of all values associated with a key.
     ///
     /// The returned view does not incur any allocations and allows iterating
@@ -558,6 +567,39 @@ impl HeaderMap {
             .map(|x| &mut x.1)
     }
 
+    /// Gets the given header's entry in the map for in-place manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use axol_http::header::HeaderMap;
+    /// let mut map = HeaderMap::new();
+    /// map.entry("content-type").or_insert("text/plain");
+    /// map.entry("content-type").or_insert("application/json");
+    ///
+    /// assert_eq!(map.get("content-type").unwrap(), "text/plain");
+    /// ```
+    pub fn entry(&mut self, name: impl AsRef<str>) -> Entry<'_> {
+        let name = header_name(name.as_ref());
+        match self
+            .items
+            .iter()
+            .position(|(entry_name, _)| entry_name.eq_ignore_ascii_case(&name))
+        {
+            Some(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            None => Entry::Vacant(VacantEntry { map: self, name }),
+        }
+    }
+
+    /// Returns a mutable reference to the first value of the given header, inserting the result of `value` if absent.
+    pub fn get_or_insert_with<V: Into<String>>(
+        &mut self,
+        name: impl AsRef<str>,
+        value: impl FnOnce() -> V,
+    ) -> &mut Cow<'static, str> {
+        self.entry(name).or_insert_with(value)
+    }
+
     /// Removes a key from the map, returning the value associated with the key.
     ///
     /// Returns an empty vec if the map does not contain the key. If there are
@@ -590,6 +632,21 @@ impl HeaderMap {
         out
     }
 
+    /// Removes all values of the header `H`, returning those that could be decoded.
+    /// Malformed values are removed as well.
+    pub fn remove_typed<H: TypedHeader>(&mut self) -> Vec<H> {
+        self.remove(H::name())
+            .iter()
+            .filter_map(|x| H::decode(x).ok())
+            .collect()
+    }
+
+    /// Retains only the headers for which `f(name, value)` returns `true`, preserving order.
+    /// i.e. to remove a single value of a header with multiple values.
+    pub fn retain(&mut self, mut f: impl FnMut(&str, &str) -> bool) {
+        self.items.retain(|(name, value)| f(name, value));
+    }
+
     /// An iterator visiting all key-value pairs.
     ///
     /// The iteration order is in insertion order.
@@ -613,6 +670,44 @@ impl HeaderMap {
         self.items.iter().map(|(name, value)| (&**name, &**value))
     }
 
+    /// Combines multiple values of the same header into a single comma-separated value, at the position of the first value.
+    /// Headers that can't be combined (`set-cookie`) are left as separate values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use axol_http::header::HeaderMap;
+    /// let mut map = HeaderMap::new();
+    /// map.append("vary", "accept");
+    /// map.append("set-cookie", "a=1");
+    /// map.append("vary", "origin");
+    /// map.append("set-cookie", "b=2");
+    /// map.coalesce();
+    ///
+    /// assert_eq!(
+    ///     map.iter().collect::<Vec<_>>(),
+    ///     vec![("vary", "accept, origin"), ("set-cookie", "a=1"), ("set-cookie", "b=2")],
+    /// );
+    /// ```
+    pub fn coalesce(&mut self) {
+        let mut out: Vec<(Cow<'static, str>, Cow<'static, str>)> =
+            Vec::with_capacity(self.items.len());
+        for (name, value) in std::mem::take(&mut self.items) {
+            let combinable = !NON_COMBINABLE_HEADERS
+                .iter()
+                .any(|x| x.eq_ignore_ascii_case(&name));
+            match out.iter_mut().find(|(x, _)| combinable && *x == name) {
+                Some((_, existing)) => {
+                    let existing = existing.to_mut();
+                    existing.push_str(", ");
+                    existing.push_str(&value);
+                }
+                None => out.push((name, value)),
+            }
+        }
+        self.items = out;
+    }
+
     pub fn grouped(&self) -> Vec<(&str, SmallVec<[&str; 2]>)> {
         let mut names = self.iter().collect::<Vec<_>>();
         names.sort_by_key(|x| x.0);
@@ -629,6 +724,105 @@ impl HeaderMap {
     }
 }
 
+/// A view into a single header of a `HeaderMap`, which may be present or absent. See `HeaderMap::entry`.
+#[derive(Debug)]
+pub enum Entry<'a> {
+    Occupied(OccupiedEntry<'a>),
+    Vacant(VacantEntry<'a>),
+}
+
+/// A header present in a `HeaderMap`, referencing its first value.
+#[derive(Debug)]
+pub struct OccupiedEntry<'a> {
+    map: &'a mut HeaderMap,
+    index: usize,
+}
+
+/// A header absent from a `HeaderMap`.
+#[derive(Debug)]
+pub struct VacantEntry<'a> {
+    map: &'a mut HeaderMap,
+    name: Cow<'static, str>,
+}
+
+impl<'a> Entry<'a> {
+    /// The normalized name of this entry's header.
+    pub fn name(&self) -> &str {
+        match self {
+            Entry::Occupied(entry) => entry.name(),
+            Entry::Vacant(entry) => entry.name(),
+        }
+    }
+
+    /// Inserts `value` if the header is absent, returning a mutable reference to the first value.
+    pub fn or_insert(self, value: impl Into<String>) -> &'a mut Cow<'static, str> {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(value),
+        }
+    }
+
+    /// Inserts the result of `value` if the header is absent, returning a mutable reference to the first value.
+    pub fn or_insert_with<V: Into<String>>(
+        self,
+        value: impl FnOnce() -> V,
+    ) -> &'a mut Cow<'static, str> {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(value()),
+        }
+    }
+}
+
+impl<'a> OccupiedEntry<'a> {
+    pub fn name(&self) -> &str {
+        &self.map.items[self.index].0
+    }
+
+    /// The first value of this header.
+    pub fn get(&self) -> &str {
+        &self.map.items[self.index].1
+    }
+
+    pub fn get_mut(&mut self) -> &mut Cow<'static, str> {
+        &mut self.map.items[self.index].1
+    }
+
+    pub fn into_mut(self) -> &'a mut Cow<'static, str> {
+        &mut self.map.items[self.index].1
+    }
+
+    /// Replaces all values of this header with `value`, returning the previous first value.
+    pub fn insert(&mut self, value: impl Into<String>) -> Cow<'static, str> {
+        let name = self.map.items[self.index].0.clone();
+        let old = std::mem::replace(self.get_mut(), Cow::Owned(value.into()));
+        // the entry is always the first value of the header, so keep that one
+        let mut seen = false;
+        self.map.items.retain(|(entry_name, _)| {
+            !entry_name.eq_ignore_ascii_case(&name) || !std::mem::replace(&mut seen, true)
+        });
+        old
+    }
+
+    /// Removes all values of this header, returning them.
+    pub fn remove(self) -> Vec<Cow<'static, str>> {
+        let name = self.map.items[self.index].0.clone();
+        self.map.remove(name)
+    }
+}
+
+impl<'a> VacantEntry<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Appends `value` to the map, returning a mutable reference to it.
+    pub fn insert(self, value: impl Into<String>) -> &'a mut Cow<'static, str> {
+        self.map.items.push((self.name, Cow::Owned(value.into())));
+        &mut self.map.items.last_mut().unwrap().1
+    }
+}
+
 impl<K: Into<Cow<'static, str>>, V: Into<String>> Extend<(K, V)> for HeaderMap {
     fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
         let iter = iter.into_iter();
@@ -736,3 +930,28 @@ impl Into<http::HeaderMap> for HeaderMap {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry() {
+        let mut map = HeaderMap::new();
+        map.append("vary", "accept");
+        map.append("x-other", "1");
+        map.append("Vary", "origin");
+
+        assert_eq!(map.get_or_insert_with("VARY", || "cookie"), "accept");
+        assert_eq!(map.get_or_insert_with("x-new", || "2"), "2");
+
+        let Entry::Occupied(mut entry) = map.entry("vary") else {
+            panic!("vary missing");
+        };
+        assert_eq!(entry.insert("cookie"), "accept");
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![("vary", "cookie"), ("x-other", "1"), ("x-new", "2")]
+        );
+    }
+}
@@ -3,8 +3,12 @@ use axol_http::{
     response::ResponsePartsRef,
     RequestPartsRef,
 };
-pub use cookie::{Cookie, Expiration, SameSite};
+#[cfg(any(feature = "cookie-signed", feature = "cookie-private"))]
+pub use cookie::Key;
+pub use cookie::{Cookie, CookieBuilder, Expiration, SameSite};
 
+#[cfg(any(feature = "cookie-signed", feature = "cookie-private"))]
+use crate::Error;
 use crate::{FromRequestParts, IntoResponseParts, Result};
 
 /// Extractor that grabs cookies from the request and manages the jar.
@@ -20,7 +24,7 @@ pub struct CookieJar {
 #[async_trait::async_trait]
 impl<'a> FromRequestParts<'a> for CookieJar {
     async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
-        Ok(Self::from_headers(&request.headers))
+        Ok(Self::from_headers(request.headers))
     }
 }
 
@@ -82,6 +86,10 @@ impl CookieJar {
 
     /// Remove a cookie from the jar.
     ///
+    /// If the request carried the cookie, an expired `set-cookie` (`Max-Age=0` and a past `Expires`) is sent for it.
+    /// Browsers only remove a cookie if the `Path` and `Domain` of the removal match those it was set with,
+    /// so `cookie` must carry the same attributes. See [`CookieJar::remove_with`].
+    ///
     /// # Example
     ///
     /// ```rust
@@ -98,6 +106,16 @@ impl CookieJar {
         self
     }
 
+    /// Same as [`CookieJar::remove`], taking a builder to specify the `Path`/`Domain` the cookie was set with.
+    ///
+    /// ```ignore
+    /// jar.remove_with(Cookie::build("session", "").path("/").domain("example.com"))
+    /// ```
+    #[must_use]
+    pub fn remove_with(self, cookie: CookieBuilder<'static>) -> Self {
+        self.remove(cookie.finish())
+    }
+
     /// Add a cookie to the jar.
     ///
     /// The value will automatically be percent-encoded.
@@ -140,3 +158,195 @@ fn set_cookies(jar: cookie::CookieJar, headers: &mut HeaderMap) {
     // we don't need to call `jar.reset_delta()` because `into_response_parts` consumes the cookie
     // jar so it cannot be called multiple times.
 }
+
+#[cfg(any(feature = "cookie-signed", feature = "cookie-private"))]
+fn key_from_request<T>(request: RequestPartsRef<'_>) -> Result<Key> {
+    request.extensions.get::<Key>().cloned().ok_or_else(|| {
+        Error::internal(anyhow::anyhow!(
+            "missing `cookie::Key` request extension for `{}`, add one with `Router::extension`",
+            std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// Extractor that grabs signed cookies from the request and manages the jar.
+///
+/// Cookies are signed with the [`Key`] request extension, i.e. inserted with `Router::extension`.
+/// Cookies whose signature doesn't verify are dropped on extraction. Their values remain readable by the client.
+///
+/// Like [`CookieJar`], this value _must_ be returned from the handler as part of the response for changes to be propagated.
+#[cfg(feature = "cookie-signed")]
+#[derive(Clone)]
+pub struct SignedCookieJar {
+    jar: cookie::CookieJar,
+    key: Key,
+}
+
+#[cfg(feature = "cookie-signed")]
+impl std::fmt::Debug for SignedCookieJar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignedCookieJar")
+            .field("jar", &self.jar)
+            .field("key", &"REDACTED")
+            .finish()
+    }
+}
+
+#[cfg(feature = "cookie-signed")]
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for SignedCookieJar {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        let key = key_from_request::<Self>(request)?;
+        Ok(Self::from_headers(request.headers, key))
+    }
+}
+
+#[cfg(feature = "cookie-signed")]
+impl SignedCookieJar {
+    /// Create a new `SignedCookieJar` from a map of request headers, keeping only cookies signed with `key`.
+    pub fn from_headers(headers: &HeaderMap, key: Key) -> Self {
+        let mut jar = cookie::CookieJar::new();
+        let mut signed_jar = jar.signed_mut(&key);
+        for cookie in cookies_from_request(headers) {
+            if let Some(cookie) = signed_jar.verify(cookie) {
+                signed_jar.add_original(cookie);
+            }
+        }
+        Self { jar, key }
+    }
+
+    /// Create a new empty `SignedCookieJar`.
+    pub fn new(key: Key) -> Self {
+        Self {
+            jar: cookie::CookieJar::default(),
+            key,
+        }
+    }
+
+    /// Get a verified cookie from the jar.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.signed(&self.key).get(name)
+    }
+
+    /// Remove a cookie from the jar.
+    #[must_use]
+    pub fn remove(mut self, cookie: Cookie<'static>) -> Self {
+        self.jar.signed_mut(&self.key).remove(cookie);
+        self
+    }
+
+    /// Add a cookie to the jar, signing its value.
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, cookie: Cookie<'static>) -> Self {
+        self.jar.signed_mut(&self.key).add(cookie);
+        self
+    }
+
+    /// Get an iterator over all verified cookies in the jar.
+    pub fn iter(&self) -> impl Iterator<Item = Cookie<'static>> + '_ {
+        let signed = self.jar.signed(&self.key);
+        self.jar
+            .iter()
+            .filter_map(move |cookie| signed.get(cookie.name()))
+    }
+}
+
+#[cfg(feature = "cookie-signed")]
+impl IntoResponseParts for SignedCookieJar {
+    fn into_response_parts(self, res: &mut ResponsePartsRef<'_>) -> Result<()> {
+        set_cookies(self.jar, res.headers);
+        Ok(())
+    }
+}
+
+/// Extractor that grabs encrypted cookies from the request and manages the jar.
+///
+/// Cookies are encrypted with the [`Key`] request extension, i.e. inserted with `Router::extension`.
+/// Cookies which fail to decrypt are dropped on extraction. Their values are neither readable nor modifiable by the client.
+///
+/// Like [`CookieJar`], this value _must_ be returned from the handler as part of the response for changes to be propagated.
+#[cfg(feature = "cookie-private")]
+#[derive(Clone)]
+pub struct PrivateCookieJar {
+    jar: cookie::CookieJar,
+    key: Key,
+}
+
+#[cfg(feature = "cookie-private")]
+impl std::fmt::Debug for PrivateCookieJar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrivateCookieJar")
+            .field("jar", &self.jar)
+            .field("key", &"REDACTED")
+            .finish()
+    }
+}
+
+#[cfg(feature = "cookie-private")]
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for PrivateCookieJar {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        let key = key_from_request::<Self>(request)?;
+        Ok(Self::from_headers(request.headers, key))
+    }
+}
+
+#[cfg(feature = "cookie-private")]
+impl PrivateCookieJar {
+    /// Create a new `PrivateCookieJar` from a map of request headers, keeping only cookies encrypted with `key`.
+    pub fn from_headers(headers: &HeaderMap, key: Key) -> Self {
+        let mut jar = cookie::CookieJar::new();
+        let mut private_jar = jar.private_mut(&key);
+        for cookie in cookies_from_request(headers) {
+            if let Some(cookie) = private_jar.decrypt(cookie) {
+                private_jar.add_original(cookie);
+            }
+        }
+        Self { jar, key }
+    }
+
+    /// Create a new empty `PrivateCookieJar`.
+    pub fn new(key: Key) -> Self {
+        Self {
+            jar: cookie::CookieJar::default(),
+            key,
+        }
+    }
+
+    /// Get a decrypted cookie from the jar.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.private(&self.key).get(name)
+    }
+
+    /// Remove a cookie from the jar.
+    #[must_use]
+    pub fn remove(mut self, cookie: Cookie<'static>) -> Self {
+        self.jar.private_mut(&self.key).remove(cookie);
+        self
+    }
+
+    /// Add a cookie to the jar, encrypting its value.
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, cookie: Cookie<'static>) -> Self {
+        self.jar.private_mut(&self.key).add(cookie);
+        self
+    }
+
+    /// Get an iterator over all decrypted cookies in the jar.
+    pub fn iter(&self) -> impl Iterator<Item = Cookie<'static>> + '_ {
+        let private = self.jar.private(&self.key);
+        self.jar
+            .iter()
+            .filter_map(move |cookie| private.get(cookie.name()))
+    }
+}
+
+#[cfg(feature = "cookie-private")]
+impl IntoResponseParts for PrivateCookieJar {
+    fn into_response_parts(self, res: &mut ResponsePartsRef<'_>) -> Result<()> {
+        set_cookies(self.jar, res.headers);
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use axol_http::{mime::Mime, request::RequestPartsRef, response::Response, Body};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{Error, FromRequest, IntoResponse, Result};
+
+/// CSV request/response body (`text/csv`).
+///
+/// As a response, `records` is any iterable of serializable records (i.e. a `Vec<T>` or `&[T]`).
+/// As an extractor, `Csv<Vec<T>>` deserializes each row into a `T`.
+///
+/// Header row presence follows the RFC 4180 `header` parameter of the content type (`text/csv; header=absent`),
+/// defaulting to present.
+///
+/// ```ignore
+/// async fn export() -> Csv<Vec<Row>> {
+///     Csv::new(load_rows().await).attachment("rows.csv")
+/// }
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Csv<T> {
+    pub records: T,
+    has_headers: bool,
+    filename: Option<String>,
+}
+
+impl<T> Csv<T> {
+    pub fn new(records: T) -> Self {
+        Self {
+            records,
+            has_headers: true,
+            filename: None,
+        }
+    }
+
+    /// Whether a header row is written (or expected, for extracted bodies). Defaults to `true`.
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Sets `content-disposition: attachment; filename=...` so browsers download the response.
+    pub fn attachment(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    pub fn into_inner(self) -> T {
+        self.records
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a, T: DeserializeOwned + Send + Sync + 'a> FromRequest<'a> for Csv<Vec<T>> {
+    async fn from_request(request: RequestPartsRef<'a>, body: Body) -> Result<Self> {
+        let content_type = request
+            .headers
+            .get("content-type")
+            .and_then(|x| x.parse::<Mime>().ok())
+            .filter(|x| x.essence_str() == axol_http::mime::TEXT_CSV.essence_str())
+            .ok_or_else(|| {
+                Error::unsupported_media_type("Expected request with `Content-Type: text/csv`")
+            })?;
+        // `Option::is_none_or` would raise the MSRV
+        #[allow(clippy::unnecessary_map_or)]
+        let has_headers = content_type
+            .get_param("header")
+            .map_or(true, |x| x.as_str() != "absent");
+
+        let bytes = body.collect().await?;
+        let records = csv::ReaderBuilder::new()
+            .has_headers(has_headers)
+            .from_reader(&bytes[..])
+            .into_deserialize()
+            .collect::<Result<Vec<T>, _>>()
+            .map_err(|e| {
+                Error::unprocessable_entity(format!("Failed to deserialize the CSV body: {e}"))
+            })?;
+
+        Ok(Csv::new(records).has_headers(has_headers))
+    }
+}
+
+impl<T, R> IntoResponse for Csv<T>
+where
+    T: IntoIterator<Item = R>,
+    R: Serialize,
+{
+    fn into_response(self) -> Result<Response> {
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(self.has_headers)
+            .from_writer(vec![]);
+        for record in self.records {
+            writer.serialize(record).map_err(Error::internal)?;
+        }
+        let body = writer
+            .into_inner()
+            .map_err(|e| Error::internal(e.into_error()))?;
+
+        let mut out = Response::default();
+        let header = if self.has_headers {
+            "present"
+        } else {
+            "absent"
+        };
+        out.headers.insert(
+            "content-type",
+            format!("text/csv; charset=utf-8; header={header}"),
+        );
+        if let Some(filename) = &self.filename {
+            let filename = filename.replace(['"', '\\'], "_");
+            out.headers.insert(
+                "content-disposition",
+                format!("attachment; filename=\"{filename}\""),
+            );
+        }
+        out.body = Body::Bytes(body);
+        Ok(out)
+    }
+}
@@ -9,6 +9,14 @@ use crate::{Error, FromRequestParts, IntoResponseParts, Result};
 #[must_use]
 pub struct Extension<T>(pub T);
 
+fn missing_extension<T>(request: RequestPartsRef<'_>) -> Error {
+    Error::internal(anyhow!(
+        "missing request extension `{}`, present extensions: {:?}",
+        std::any::type_name::<T>(),
+        request.extensions.type_names()
+    ))
+}
+
 #[async_trait::async_trait]
 impl<'a, T: Send + Sync + Clone + 'static> FromRequestParts<'a> for Extension<T> {
     async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
@@ -16,7 +24,7 @@ impl<'a, T: Send + Sync + Clone + 'static> FromRequestParts<'a> for Extension<T>
             request
                 .extensions
                 .get::<T>()
-                .ok_or_else(|| Error::internal(anyhow!("missing request extension")))?
+                .ok_or_else(|| missing_extension::<T>(request))?
                 .clone(),
         ))
     }
@@ -41,7 +49,7 @@ impl<'a, T: Send + Sync + Clone + 'static> FromRequestParts<'a> for ExtensionArc
             request
                 .extensions
                 .get_arc::<T>()
-                .ok_or_else(|| Error::internal(anyhow!("missing request extension")))?
+                .ok_or_else(|| missing_extension::<T>(request))?
                 .clone(),
         ))
     }
@@ -66,7 +74,7 @@ impl<'a, T: Send + Sync + Clone + 'static> FromRequestParts<'a> for ExtensionRem
             request
                 .extensions
                 .remove::<T>()
-                .ok_or_else(|| Error::internal(anyhow!("missing request extension")))?
+                .ok_or_else(|| missing_extension::<T>(request))?
                 .clone(),
         ))
     }
@@ -5,7 +5,9 @@ use axol_http::{
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::{Error, FromRequest, FromRequestParts, IntoResponse, Result, Typed};
+use crate::{
+    check_content_length, request::urlencoded_de, Error, FromRequest, IntoResponse, Result,
+};
 
 #[derive(Debug, Clone, Copy, Default)]
 #[must_use]
@@ -25,32 +27,47 @@ impl<T> DerefMut for Form<T> {
     }
 }
 
+/// Request extension configuring the `Form` extractor, i.e. `router.extension("/", FormConfig { limit: 64 * 1024 })`.
+#[derive(Debug, Clone, Copy)]
+pub struct FormConfig {
+    /// Maximum body size in bytes. Larger bodies are rejected with `413 Payload Too Large` before parsing.
+    pub limit: usize,
+}
+
+impl Default for FormConfig {
+    fn default() -> Self {
+        Self {
+            limit: 2 * 1024 * 1024,
+        }
+    }
+}
+
+/// Deserializes an `application/x-www-form-urlencoded` body.
+/// Repeated and bracketed keys are handled the same as for `Query`, i.e. `a=1&a=2` deserializes into a `Vec<u32>` field `a`.
 #[async_trait::async_trait]
 impl<'a, T: DeserializeOwned + Send + Sync + 'a> FromRequest<'a> for Form<T> {
     async fn from_request(request: RequestPartsRef<'a>, body: Body) -> Result<Self> {
-        let content_type: Mime = Typed::<ContentType>::from_request_parts(request)
-            .await?
-            .0
-            .into();
-        if content_type.essence_str()
-            != axol_http::mime::APPLICATION_WWW_FORM_URLENCODED.essence_str()
+        let content_type = request
+            .headers
+            .get("content-type")
+            .and_then(|x| x.parse::<Mime>().ok());
+        if content_type.as_ref().map(|x| x.essence_str())
+            != Some(axol_http::mime::APPLICATION_WWW_FORM_URLENCODED.essence_str())
         {
             return Err(Error::unsupported_media_type(
                 "Expected request with `Content-Type: application/x-www-form-urlencoded`",
             ));
         }
-        let bytes = body.collect().await?;
-        let deserializer = serde_urlencoded::Deserializer::new(form_urlencoded::parse(&bytes));
+        let config = request
+            .extensions
+            .get_cloned::<FormConfig>()
+            .unwrap_or_default();
+        check_content_length(request, config.limit)?;
+        let bytes = body.collect_limited(config.limit).await?;
 
-        let value = match serde_path_to_error::deserialize(deserializer) {
-            Ok(value) => value,
-            Err(err) => {
-                let rejection = Error::bad_request(format!(
-                    "Failed to parse the request body as a form: {err}"
-                ));
-                return Err(rejection);
-            }
-        };
+        let value = urlencoded_de::from_bytes(&bytes).map_err(|err| {
+            Error::bad_request(format!("Failed to parse the request body as a form: {err}"))
+        })?;
 
         Ok(Form(value))
     }
@@ -1,10 +1,17 @@
 use axol_http::{request::RequestPartsRef, response::Response, Body};
+use base64::{engine::general_purpose::STANDARD, Engine};
 use prost::Message;
 
 use crate::{grpc::Status, Error, FromRequest, FromRequestParts, IntoResponse, Result, Typed};
 
-use super::{Encoding, GrpcContentType};
+use super::{AcceptEncoding, Encoding, GrpcContentType};
 
+/// A single length-prefixed gRPC message.
+///
+/// Compressed requests are decompressed according to `grpc-encoding`, and responses are compressed with `encoding`.
+/// Use `Encoding::negotiate` to pick a response encoding the client accepts.
+///
+/// gRPC-Web requests are accepted as well, responding to them requires the `GrpcWeb` wrap.
 pub struct Grpc<T: Message> {
     pub encoding: Encoding,
     pub content_type: GrpcContentType,
@@ -24,23 +31,26 @@ impl<'a, T: Default + Message + 'a> FromRequest<'a> for Grpc<T> {
 
         if content_type == GrpcContentType::Invalid {
             return Err(Error::UnsupportedMediaType);
-        } else if content_type != GrpcContentType::Proto {
+        } else if content_type != GrpcContentType::Proto && !content_type.is_web() {
             return Err(Error::GrpcMessage(
                 Status::Internal,
                 "only proto subtype is supported".to_string(),
             ));
         }
 
-        if encoding != Encoding::Identity {
-            //TODO: support more encodings
-            fn x() {}
+        if !encoding.is_supported() {
             return Err(Error::GrpcMessage(
-                Status::Internal,
-                "only identity encoding is supported".to_string(),
+                Status::Unimplemented,
+                format!("unsupported message encoding: {encoding:?}"),
             ));
         }
 
-        let body = body.collect().await?;
+        let mut body = body.collect().await?;
+        if content_type == GrpcContentType::WebText {
+            body = STANDARD.decode(&body).map_err(|e| {
+                Error::GrpcMessage(Status::Internal, format!("invalid base64 body: {e}"))
+            })?;
+        }
 
         if body.len() < 5 {
             return Err(Error::GrpcMessage(
@@ -58,16 +68,27 @@ impl<'a, T: Default + Message + 'a> FromRequest<'a> for Grpc<T> {
                 ));
             }
         };
-        if is_compressed != (encoding != Encoding::Identity) {
+        // senders may skip compression per message, but a compressed message needs a grpc-encoding
+        if is_compressed && encoding == Encoding::Identity {
             return Err(Error::GrpcMessage(
                 Status::Internal,
-                "compression flag mismatch with headers".to_string(),
+                "compressed message without grpc-encoding".to_string(),
             ));
         }
         let length = u32::from_be_bytes((&body[1..5]).try_into().unwrap());
         //TODO: should we assert length == body.len() - 5?
-        let body: T = T::decode(&body[5..5 + length as usize])
-            .map_err(|e| Error::GrpcMessage(Status::Internal, format!("decode failure: {e}")))?;
+        let Some(message) = body.get(5..5 + length as usize) else {
+            return Err(Error::GrpcMessage(
+                Status::Internal,
+                "truncated body".to_string(),
+            ));
+        };
+        let body: T = if is_compressed {
+            T::decode(&encoding.decompress(message)?[..])
+        } else {
+            T::decode(message)
+        }
+        .map_err(|e| Error::GrpcMessage(Status::Internal, format!("decode failure: {e}")))?;
         Ok(Self {
             encoding,
             content_type,
@@ -78,18 +99,21 @@ impl<'a, T: Default + Message + 'a> FromRequest<'a> for Grpc<T> {
 
 impl<T: Message + Default> IntoResponse for Grpc<T> {
     fn into_response(self) -> Result<Response> {
+        let message = self.body.encode_to_vec();
         let is_compressed = self.encoding != Encoding::Identity;
+        let message = self.encoding.compress(&message)?;
 
-        let mut out = vec![is_compressed as u8, 0, 0, 0, 0];
-        self.body
-            .encode(&mut out)
-            .map_err(|e| Error::GrpcMessage(Status::Internal, format!("encode failure: {e}")))?;
-        let out_len = (out.len() - 5) as u32;
-        out[1..5].copy_from_slice(&out_len.to_be_bytes()[..]);
+        let mut out = Vec::with_capacity(message.len() + 5);
+        out.push(is_compressed as u8);
+        out.extend_from_slice(&(message.len() as u32).to_be_bytes()[..]);
+        out.extend_from_slice(&message);
         // must return a Body::Bytes or we will break downstream stuff
         let mut response = Response::new(Body::Bytes(out));
         response.headers.insert_typed(&self.content_type);
         response.headers.insert_typed(&self.encoding);
+        response
+            .headers
+            .insert_typed(&AcceptEncoding(Encoding::supported()));
         Ok(response)
     }
 }
@@ -13,6 +13,10 @@ pub enum GrpcContentType {
     Proto,
     Json,
     Other(String),
+    /// gRPC-Web, `application/grpc-web` or `application/grpc-web+proto`
+    WebProto,
+    /// base64 encoded gRPC-Web, `application/grpc-web-text` or `application/grpc-web-text+proto`
+    WebText,
 }
 
 impl Default for GrpcContentType {
@@ -25,11 +29,26 @@ impl GrpcContentType {
     fn as_str(&self) -> &str {
         match self {
             GrpcContentType::Invalid => unimplemented!(),
-            GrpcContentType::Proto => "proto",
+            GrpcContentType::Proto | GrpcContentType::WebProto | GrpcContentType::WebText => {
+                "proto"
+            }
             GrpcContentType::Json => "json",
             GrpcContentType::Other(x) => x,
         }
     }
+
+    fn subtype(&self) -> &'static str {
+        match self {
+            GrpcContentType::WebProto => "grpc-web",
+            GrpcContentType::WebText => "grpc-web-text",
+            _ => "grpc",
+        }
+    }
+
+    /// Whether this is a gRPC-Web content type, see `GrpcWeb`.
+    pub fn is_web(&self) -> bool {
+        matches!(self, GrpcContentType::WebProto | GrpcContentType::WebText)
+    }
 }
 
 static CONTENT_TYPE: HeaderName = HeaderName::from_static("content-type");
@@ -45,20 +64,25 @@ impl Header for GrpcContentType {
     {
         let content_type = ContentType::decode(values)?;
         let content_type: Mime = content_type.into();
-        if content_type.type_() != "application" || content_type.subtype() != "grpc" {
+        if content_type.type_() != "application" {
             return Ok(Self::Invalid);
         }
+        let suffix = content_type.suffix().map(|x| x.as_str());
 
-        Ok(match content_type.suffix().map(|x| x.as_str()) {
-            Some("proto") | None => GrpcContentType::Proto,
-            Some("json") => GrpcContentType::Json,
-            Some(x) => GrpcContentType::Other(x.to_string()),
+        Ok(match (content_type.subtype().as_str(), suffix) {
+            ("grpc", Some("proto") | None) => GrpcContentType::Proto,
+            ("grpc", Some("json")) => GrpcContentType::Json,
+            ("grpc", Some(x)) => GrpcContentType::Other(x.to_string()),
+            // gRPC-Web is only supported with protobuf
+            ("grpc-web", Some("proto") | None) => GrpcContentType::WebProto,
+            ("grpc-web-text", Some("proto") | None) => GrpcContentType::WebText,
+            _ => GrpcContentType::Invalid,
         })
     }
 
     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
         let content_type: ContentType =
-            Mime::from_str(&format!("application/grpc+{}", self.as_str()))
+            Mime::from_str(&format!("application/{}+{}", self.subtype(), self.as_str()))
                 .unwrap()
                 .into();
         content_type.encode(values);
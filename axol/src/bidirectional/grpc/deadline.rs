@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use axol_http::{request::RequestPartsRef, response::Response};
+use tokio::time::Instant;
+
+use crate::{Cancellation, Error, FromRequestParts, Result, Wrap, WrapState};
+
+use super::{GrpcTimeout, Status};
+
+/// The deadline of the current gRPC call, as set by `GrpcDeadlines` from the client's `grpc-timeout`.
+///
+/// Only present if the client sent a timeout. Pass `timeout()` along as `grpc-timeout` on downstream calls
+/// so they give up when the client does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrpcDeadline(pub Instant);
+
+impl GrpcDeadline {
+    /// Time left until the deadline, zero if it has passed.
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.0 <= Instant::now()
+    }
+
+    /// The remaining time as a `grpc-timeout` header for downstream calls.
+    pub fn timeout(&self) -> GrpcTimeout {
+        GrpcTimeout(self.remaining())
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for GrpcDeadline {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        request
+            .extensions
+            .get_cloned::<GrpcDeadline>()
+            .ok_or_else(|| Error::internal(anyhow!("missing GrpcDeadline extension")))
+    }
+}
+
+/// Enforces the client's `grpc-timeout`, failing the call with `Status::DeadlineExceeded` once it passes.
+/// The deadline is available to handlers as `GrpcDeadline`. Requests without a timeout are not limited, unless `max` is set.
+///
+/// Like `Timeout`, only producing the response is covered, and the handler future is dropped on expiry.
+/// If `Trace` is registered, the resulting status is recorded in the span's `rpc.*` fields, and the message as `exception.message`.
+///
+/// ```ignore
+/// router.wrap("/", GrpcDeadlines::default())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GrpcDeadlines {
+    /// Upper bound for the timeout of any call, applied to calls without a `grpc-timeout` as well.
+    pub max: Option<Duration>,
+}
+
+#[async_trait::async_trait]
+impl Wrap for GrpcDeadlines {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let timeout = state
+            .request()
+            .headers
+            .get_typed::<GrpcTimeout>()
+            .map(|x| x.0);
+        let timeout = match (timeout, self.max) {
+            (Some(timeout), Some(max)) => Some(timeout.min(max)),
+            (timeout, max) => timeout.or(max),
+        };
+        let Some(timeout) = timeout else {
+            return state.next().await;
+        };
+        let deadline = Instant::now() + timeout;
+        state.request().extensions.insert(GrpcDeadline(deadline));
+        let cancellation = state.request().extensions.get_cloned::<Cancellation>();
+        match tokio::time::timeout_at(deadline, state.next()).await {
+            Ok(response) => response,
+            Err(_) => {
+                if let Some(cancellation) = cancellation {
+                    cancellation.cancel();
+                }
+                Err(Error::GrpcMessage(
+                    Status::DeadlineExceeded,
+                    "deadline exceeded".to_string(),
+                ))
+            }
+        }
+    }
+}
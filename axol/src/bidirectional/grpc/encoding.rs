@@ -1,4 +1,14 @@
+use std::io::{Read, Write};
+
 use axol_http::typed_headers::{Error as HeaderError, Header, HeaderName, HeaderValue};
+use flate2::{
+    read::{GzDecoder, ZlibDecoder},
+    write::{GzEncoder, ZlibEncoder},
+};
+
+use crate::{Error, Result};
+
+use super::Status;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Encoding {
@@ -35,6 +45,71 @@ impl Encoding {
             Encoding::Other(x) => x,
         }
     }
+
+    /// The encodings that `compress` and `decompress` support, in order of preference.
+    pub fn supported() -> Vec<Self> {
+        vec![Encoding::Gzip, Encoding::Deflate, Encoding::Identity]
+    }
+
+    pub fn is_supported(&self) -> bool {
+        matches!(
+            self,
+            Encoding::Identity | Encoding::Gzip | Encoding::Deflate
+        )
+    }
+
+    /// Picks the encoding for a response message from the client's `grpc-accept-encoding`.
+    /// Falls back to `Identity`, which every client must accept.
+    pub fn negotiate(accept: Option<&AcceptEncoding>) -> Self {
+        let Some(accept) = accept else {
+            return Encoding::Identity;
+        };
+        accept
+            .0
+            .iter()
+            .find(|x| x.is_supported())
+            .cloned()
+            .unwrap_or(Encoding::Identity)
+    }
+
+    fn unsupported(&self) -> Error {
+        Error::GrpcMessage(
+            Status::Unimplemented,
+            format!("unsupported message encoding: {}", self.as_str()),
+        )
+    }
+
+    /// Compresses a single message. Fails with `Status::Unimplemented` for unsupported encodings.
+    pub fn compress(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let out = match self {
+            Encoding::Identity => return Ok(message.to_vec()),
+            Encoding::Gzip => {
+                let mut encoder = GzEncoder::new(vec![], flate2::Compression::default());
+                encoder.write_all(message).and_then(|_| encoder.finish())
+            }
+            Encoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(vec![], flate2::Compression::default());
+                encoder.write_all(message).and_then(|_| encoder.finish())
+            }
+            _ => return Err(self.unsupported()),
+        };
+        out.map_err(|e| Error::GrpcMessage(Status::Internal, format!("compression failure: {e}")))
+    }
+
+    /// Decompresses a single message. Fails with `Status::Unimplemented` for unsupported encodings.
+    pub fn decompress(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let mut out = vec![];
+        let result = match self {
+            Encoding::Identity => return Ok(message.to_vec()),
+            Encoding::Gzip => GzDecoder::new(message).read_to_end(&mut out),
+            Encoding::Deflate => ZlibDecoder::new(message).read_to_end(&mut out),
+            _ => return Err(self.unsupported()),
+        };
+        result.map_err(|e| {
+            Error::GrpcMessage(Status::Internal, format!("decompression failure: {e}"))
+        })?;
+        Ok(out)
+    }
 }
 
 static GRPC_ENCODING: HeaderName = HeaderName::from_static("grpc-encoding");
@@ -1,6 +1,9 @@
 mod timeout;
 pub use timeout::GrpcTimeout;
 
+mod deadline;
+pub use deadline::{GrpcDeadline, GrpcDeadlines};
+
 mod content_type;
 pub use content_type::GrpcContentType;
 
@@ -19,6 +22,9 @@ pub use status::*;
 mod body;
 pub use body::Grpc;
 
+mod web;
+pub use web::GrpcWeb;
+
 mod request;
 pub use request::GrpcRequest;
 
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use axol_http::typed_headers::{Error as HeaderError, Header, HeaderName, HeaderValue};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct GrpcTimeout(pub Duration);
 
 static GRPC_TIMEOUT: HeaderName = HeaderName::from_static("grpc-timeout");
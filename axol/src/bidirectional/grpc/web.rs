@@ -0,0 +1,70 @@
+use axol_http::{header::HeaderMap, response::Response, Body};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::{Error, Result, Wrap, WrapState};
+
+use super::{GrpcContentType, Status};
+
+/// Serves gRPC handlers to browsers over gRPC-Web, for requests with a gRPC-Web content type. Other requests pass through untouched.
+///
+/// Browsers can't read HTTP trailers, so the response trailers (`grpc-status` etc.) are appended to the body as a frame flagged `0x80`,
+/// and for `application/grpc-web-text` the whole body is base64 encoded. Responses are buffered, which is fine for the unary calls `Grpc` supports.
+///
+/// Cross-origin browser clients can only read response headers listed in `access-control-expose-headers`,
+/// so expose `grpc-status` and `grpc-message` with `cors::Cors::expose_headers` for trailers-only responses.
+///
+/// ```ignore
+/// router.wrap("/", GrpcWeb)
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GrpcWeb;
+
+#[async_trait::async_trait]
+impl Wrap for GrpcWeb {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let content_type = state.request().headers.get_typed::<GrpcContentType>();
+        let Some(content_type) = content_type.filter(|x| x.is_web()) else {
+            return state.next().await;
+        };
+        let mut response = match state.next().await {
+            Ok(response) => response,
+            // error hooks run after us, by then the status would end up in HTTP trailers
+            Err(e @ (Error::Grpc(_) | Error::GrpcMessage(_, _))) => e.into_response(),
+            Err(e) => return Err(e),
+        };
+        let (mut body, trailers) = std::mem::take(&mut response.body)
+            .collect_with_trailers()
+            .await
+            .map_err(Error::internal)?;
+        let trailers = trailers.unwrap_or_else(|| {
+            let mut trailers = HeaderMap::new();
+            let status = response.extensions.get::<Status>().copied();
+            trailers.insert_typed(&status.unwrap_or_default());
+            trailers
+        });
+        body.extend_from_slice(&trailer_frame(&trailers));
+        if content_type == GrpcContentType::WebText {
+            body = STANDARD.encode(body).into_bytes();
+        }
+        response.headers.insert_typed(&content_type);
+        response.headers.remove("content-length");
+        response.body = Body::Bytes(body);
+        Ok(response)
+    }
+}
+
+/// Encodes trailers as a gRPC-Web trailer frame, an HTTP/1 style header block behind a length-prefix with the `0x80` flag.
+fn trailer_frame(trailers: &HeaderMap) -> Vec<u8> {
+    let mut block = String::new();
+    for (name, value) in trailers.iter() {
+        block.push_str(name);
+        block.push(':');
+        block.push_str(value);
+        block.push_str("\r\n");
+    }
+    let mut out = Vec::with_capacity(block.len() + 5);
+    out.push(0x80);
+    out.extend_from_slice(&(block.len() as u32).to_be_bytes()[..]);
+    out.extend_from_slice(block.as_bytes());
+    out
+}
@@ -5,7 +5,7 @@ use axol_http::{
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::{Error, FromRequest, FromRequestParts, IntoResponse, Result, Typed};
+use crate::{Error, FromRequest, IntoResponse, Result};
 
 #[derive(Debug, Clone, Copy, Default)]
 #[must_use]
@@ -25,32 +25,89 @@ impl<T> DerefMut for Json<T> {
     }
 }
 
+/// Request extension configuring the `Json` extractor, i.e. `router.extension("/", JsonConfig::strict())`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonConfig {
+    /// Reject objects containing fields the target type doesn't know about, as if the type had `#[serde(deny_unknown_fields)]`.
+    pub deny_unknown_fields: bool,
+}
+
+impl JsonConfig {
+    pub fn strict() -> Self {
+        Self {
+            deny_unknown_fields: true,
+        }
+    }
+}
+
+/// Body of the error response produced when a `Json` body fails to parse or deserialize.
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonRejection {
+    pub message: String,
+    /// Path to the offending value, i.e. `items[2].name`. `None` if the error was at the root.
+    pub path: Option<String>,
+    /// Location of the error in the body, if known.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+fn is_json_content_type(request: RequestPartsRef<'_>) -> bool {
+    let Some(content_type) = request.headers.get("content-type") else {
+        return false;
+    };
+    let Ok(mime) = content_type.parse::<Mime>() else {
+        return false;
+    };
+    mime.type_() == axol_http::mime::APPLICATION
+        && (mime.subtype() == axol_http::mime::JSON || mime.suffix() == Some(axol_http::mime::JSON))
+}
+
 #[async_trait::async_trait]
 impl<'a, T: DeserializeOwned + Send + Sync + 'a> FromRequest<'a> for Json<T> {
     async fn from_request(request: RequestPartsRef<'a>, body: Body) -> Result<Self> {
-        let content_type: Mime = Typed::<ContentType>::from_request_parts(request)
-            .await?
-            .0
-            .into();
-        if content_type.essence_str() != axol_http::mime::APPLICATION_JSON.essence_str() {
+        if !is_json_content_type(request) {
             return Err(Error::unsupported_media_type(
                 "Expected request with `Content-Type: application/json`",
             ));
         }
+        let config = request
+            .extensions
+            .get_cloned::<JsonConfig>()
+            .unwrap_or_default();
         let bytes = body.collect().await?;
         let deserializer = &mut serde_json::Deserializer::from_slice(&bytes);
 
-        let value = match serde_path_to_error::deserialize(deserializer) {
+        let mut track = serde_path_to_error::Track::new();
+        let mut unknown_fields = vec![];
+        let value = {
+            let deserializer =
+                serde_path_to_error::Deserializer::new(&mut *deserializer, &mut track);
+            if config.deny_unknown_fields {
+                serde_ignored::deserialize(deserializer, |path| {
+                    unknown_fields.push(path.to_string())
+                })
+            } else {
+                T::deserialize(deserializer)
+            }
+        }
+        .and_then(|value| deserializer.end().map(|_| value));
+
+        let value = match value {
             Ok(value) => value,
             Err(err) => {
-                let rejection = match err.inner().classify() {
-                    serde_json::error::Category::Data => Error::unprocessable_entity(format!(
-                        "Failed to deserialize the JSON body into the target type: {err}"
-                    )),
+                let path = track.path().to_string();
+                let rejection = JsonRejection {
+                    message: err.to_string(),
+                    path: (path != ".").then_some(path),
+                    line: (err.line() != 0).then_some(err.line()),
+                    column: (err.column() != 0).then_some(err.column()),
+                };
+                return Err(match err.classify() {
+                    serde_json::error::Category::Data => {
+                        Error::unprocessable_entity(Json(rejection))
+                    }
                     serde_json::error::Category::Syntax | serde_json::error::Category::Eof => {
-                        Error::bad_request(format!(
-                            "Failed to parse the request body as JSON: {err}"
-                        ))
+                        Error::bad_request(Json(rejection))
                     }
                     serde_json::error::Category::Io => {
                         if cfg!(debug_assertions) {
@@ -58,15 +115,20 @@ impl<'a, T: DeserializeOwned + Send + Sync + 'a> FromRequest<'a> for Json<T> {
                             // bodies first, so we shouldn't encounter any IO errors
                             unreachable!()
                         } else {
-                            Error::bad_request(format!(
-                                "Failed to parse the request body as JSON: {err}"
-                            ))
+                            Error::bad_request(Json(rejection))
                         }
                     }
-                };
-                return Err(rejection);
+                });
             }
         };
+        if let Some(path) = unknown_fields.into_iter().next() {
+            return Err(Error::unprocessable_entity(Json(JsonRejection {
+                message: format!("unknown field `{path}`"),
+                path: Some(path),
+                line: None,
+                column: None,
+            })));
+        }
 
         Ok(Json(value))
     }
@@ -80,3 +142,10 @@ impl<T: Serialize> IntoResponse for Json<T> {
         Ok(out)
     }
 }
+
+/// Ad-hoc JSON responses, i.e. from `serde_json::json!`. Same as `Json(value)`.
+impl IntoResponse for serde_json::Value {
+    fn into_response(self) -> Result<Response> {
+        Json(self).into_response()
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axol_http::{body::BodyComponent, response::Response, Body};
+use futures::{ready, Stream};
+use log::error;
+use serde::Serialize;
+
+use crate::{IntoResponse, Result};
+
+/// Streams newline-delimited JSON (`application/x-ndjson`), one line per item, without buffering the whole response.
+///
+/// Errors from the inner stream or from serialization are logged and end the body with an error,
+/// since the status and headers have already been sent by then.
+///
+/// ```ignore
+/// async fn export(State(db): State<Db>) -> Jsonl<impl Stream<Item = Result<Row>>> {
+///     Jsonl::new(db.rows_stream())
+/// }
+/// ```
+#[must_use]
+pub struct Jsonl<S> {
+    stream: S,
+}
+
+impl<S> Jsonl<S> {
+    pub fn new(stream: S) -> Self {
+        Self { stream }
+    }
+}
+
+impl<S, T, E> IntoResponse for Jsonl<S>
+where
+    S: Stream<Item = Result<T, E>> + Send + Sync + 'static,
+    T: Serialize + Send + Sync + 'static,
+    E: Into<anyhow::Error> + Send + Sync + 'static,
+{
+    fn into_response(self) -> Result<Response> {
+        let mut out = Response::default();
+        out.headers.insert("content-type", "application/x-ndjson");
+        out.body = Body::Stream {
+            size_hint: None,
+            stream: Box::pin(JsonlStream {
+                inner: Box::pin(self.stream),
+                done: false,
+                _item: PhantomData,
+            }),
+        };
+        Ok(out)
+    }
+}
+
+struct JsonlStream<S, T> {
+    inner: Pin<Box<S>>,
+    done: bool,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<S, T, E> Stream for JsonlStream<S, T>
+where
+    S: Stream<Item = Result<T, E>>,
+    T: Serialize,
+    E: Into<anyhow::Error>,
+{
+    type Item = Result<BodyComponent, anyhow::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        let item = match ready!(self.inner.as_mut().poll_next(cx)) {
+            Some(Ok(item)) => item,
+            Some(Err(e)) => {
+                let e = e.into();
+                error!("jsonl stream failed: {e:#}");
+                self.done = true;
+                return Poll::Ready(Some(Err(e)));
+            }
+            None => {
+                self.done = true;
+                return Poll::Ready(None);
+            }
+        };
+        match serde_json::to_vec(&item) {
+            Ok(mut line) => {
+                line.push(b'\n');
+                Poll::Ready(Some(Ok(BodyComponent::Data(line.into()))))
+            }
+            Err(e) => {
+                error!("failed to serialize jsonl item: {e}");
+                self.done = true;
+                Poll::Ready(Some(Err(e.into())))
+            }
+        }
+    }
+}
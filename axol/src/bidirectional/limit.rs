@@ -0,0 +1,113 @@
+use std::ops::{Deref, DerefMut};
+
+use axol_http::{request::RequestPartsRef, Body};
+
+use crate::{Error, FromRequest, Result};
+
+/// Wraps another body extractor (i.e. `Json<T>`, `Vec<u8>`) and rejects bodies larger than `N` bytes with `413 Payload Too Large`.
+///
+/// The `content-length` header is checked before the body is read, see `check_content_length`.
+/// If it is absent (i.e. a chunked upload), the body is drained incrementally and rejected as soon as more than `N` bytes have been received,
+/// so at most `N` bytes are ever buffered. The buffered body (and any trailers) are then handed to the inner extractor.
+/// The raw body extractors (`Vec<u8>`, `Bytes`, `String`) still enforce `BodyConfig` on it, so the lower of the two limits applies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContentLengthLimit<T, const N: usize>(pub T);
+
+impl<T, const N: usize> Deref for ContentLengthLimit<T, N> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> DerefMut for ContentLengthLimit<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a, T: FromRequest<'a>, const N: usize> FromRequest<'a> for ContentLengthLimit<T, N> {
+    async fn from_request(request: RequestPartsRef<'a>, body: Body) -> Result<Self> {
+        if let Some(length) = request.headers.get("content-length") {
+            if length.trim().parse::<usize>().is_err() {
+                return Err(Error::bad_request("invalid `content-length` header"));
+            }
+        }
+        check_content_length(request, N)?;
+        let (bytes, trailers) = body.collect_limited_with_trailers(N).await?;
+        let body = match trailers {
+            Some(trailers) => Body::bytes_and_trailers(bytes, trailers),
+            None => Body::Bytes(bytes),
+        };
+        Ok(Self(T::from_request(request, body).await?))
+    }
+}
+
+/// Rejects requests whose `content-length` exceeds `limit`, for extractors enforcing a body limit before reading the body.
+///
+/// For requests sent with `expect: 100-continue`, the interim `100 Continue` is only sent once the body is first read,
+/// so rejecting here spares the client from uploading the body at all. Those requests are rejected with `417 Expectation Failed`,
+/// others with `413 Payload Too Large`.
+///
+/// An unparsable `content-length` is ignored here, the limit is still enforced while reading the body.
+pub fn check_content_length(request: RequestPartsRef<'_>, limit: usize) -> Result<()> {
+    let Some(length) = request
+        .headers
+        .get("content-length")
+        .and_then(|x| x.trim().parse::<usize>().ok())
+    else {
+        return Ok(());
+    };
+    if length <= limit {
+        return Ok(());
+    }
+    if request
+        .headers
+        .get("expect")
+        .is_some_and(|x| x.trim().eq_ignore_ascii_case("100-continue"))
+    {
+        return Err(Error::ExpectationFailed);
+    }
+    Err(Error::PayloadTooLarge)
+}
+
+/// Request extension configuring the raw body extractors (`Vec<u8>`, `Bytes`, `String`),
+/// i.e. `router.extension("/upload", BodyConfig { limit: 64 * 1024 * 1024 })`.
+///
+/// For other extractors, use `ContentLengthLimit` or their own config (i.e. `FormConfig`).
+#[derive(Debug, Clone, Copy)]
+pub struct BodyConfig {
+    /// Maximum body size in bytes. Larger bodies are rejected with `413 Payload Too Large`.
+    pub limit: usize,
+}
+
+impl Default for BodyConfig {
+    fn default() -> Self {
+        Self {
+            limit: 2 * 1024 * 1024,
+        }
+    }
+}
+
+/// Buffers `body` for the raw body extractors, enforcing `BodyConfig`.
+///
+/// Bodies that are already buffered (i.e. by an enclosing `ContentLengthLimit`) are checked against the limit without copying.
+pub(crate) async fn collect_configured(
+    request: RequestPartsRef<'_>,
+    body: Body,
+) -> Result<Vec<u8>> {
+    let config = request
+        .extensions
+        .get_cloned::<BodyConfig>()
+        .unwrap_or_default();
+    if let Body::Bytes(bytes) = body {
+        if bytes.len() > config.limit {
+            return Err(Error::PayloadTooLarge);
+        }
+        return Ok(bytes);
+    }
+    check_content_length(request, config.limit)?;
+    Ok(body.collect_limited(config.limit).await?)
+}
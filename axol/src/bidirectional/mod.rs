@@ -4,6 +4,19 @@ pub use form::*;
 mod json;
 pub use json::*;
 
+mod jsonl;
+pub use jsonl::*;
+
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "csv")]
+pub use self::csv::*;
+
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "msgpack")]
+pub use msgpack::*;
+
 #[cfg(feature = "grpc")]
 pub mod grpc;
 
@@ -18,6 +31,16 @@ pub use typed::*;
 mod extension;
 pub use extension::*;
 
+mod limit;
+pub(crate) use limit::collect_configured;
+pub use limit::*;
+
+mod sse;
+pub use sse::*;
+
+mod range;
+pub use range::*;
+
 #[cfg(feature = "cookie")]
 mod cookie_jar;
 #[cfg(feature = "cookie")]
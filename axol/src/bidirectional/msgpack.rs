@@ -0,0 +1,85 @@
+use std::ops::{Deref, DerefMut};
+
+use axol_http::{mime::Mime, request::RequestPartsRef, response::Response, Body};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{Error, FromRequest, IntoResponse, Result};
+
+/// [MessagePack](https://msgpack.org) request/response body (`application/msgpack`).
+///
+/// Structs are serialized as maps (with field names), so payloads stay compatible with most other MessagePack implementations.
+#[derive(Debug, Clone, Copy, Default)]
+#[must_use]
+pub struct MsgPack<T>(pub T);
+
+impl<T> Deref for MsgPack<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for MsgPack<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+fn is_msgpack_content_type(request: RequestPartsRef<'_>) -> bool {
+    let Some(content_type) = request.headers.get("content-type") else {
+        return false;
+    };
+    let Ok(mime) = content_type.parse::<Mime>() else {
+        return false;
+    };
+    mime.type_() == axol_http::mime::APPLICATION
+        && (matches!(mime.subtype().as_str(), "msgpack" | "x-msgpack")
+            || mime.suffix().map(|x| x.as_str()) == Some("msgpack"))
+}
+
+#[async_trait::async_trait]
+impl<'a, T: DeserializeOwned + Send + Sync + 'a> FromRequest<'a> for MsgPack<T> {
+    async fn from_request(request: RequestPartsRef<'a>, body: Body) -> Result<Self> {
+        if !is_msgpack_content_type(request) {
+            return Err(Error::unsupported_media_type(
+                "Expected request with `Content-Type: application/msgpack`",
+            ));
+        }
+        let bytes = body.collect().await?;
+        let mut remaining = &bytes[..];
+        let value = T::deserialize(&mut rmp_serde::Deserializer::new(&mut remaining));
+
+        match value {
+            Ok(_) if !remaining.is_empty() => Err(Error::bad_request(
+                "Failed to parse the request body as MessagePack: trailing bytes",
+            )),
+            Ok(value) => Ok(MsgPack(value)),
+            Err(err) => {
+                let message = format!("Failed to deserialize the MessagePack body: {err}");
+                Err(
+                    if matches!(
+                        err,
+                        rmp_serde::decode::Error::InvalidMarkerRead(_)
+                            | rmp_serde::decode::Error::InvalidDataRead(_)
+                            | rmp_serde::decode::Error::DepthLimitExceeded
+                    ) {
+                        // malformed or truncated input, rather than a value that doesn't fit `T`
+                        Error::bad_request(message)
+                    } else {
+                        Error::unprocessable_entity(message)
+                    },
+                )
+            }
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for MsgPack<T> {
+    fn into_response(self) -> Result<Response> {
+        let mut out = Response::default();
+        out.headers.insert("content-type", "application/msgpack");
+        out.body = Body::Bytes(rmp_serde::to_vec_named(&self.0).map_err(Error::internal)?);
+        Ok(out)
+    }
+}
@@ -12,10 +12,13 @@ use axol_http::{Body, StatusCode};
 use bytes::Bytes;
 use futures_util::stream::Stream;
 use std::{
+    collections::HashMap,
     fmt,
+    path::Path,
     pin::Pin,
     task::{Context, Poll},
 };
+use tokio::io::AsyncWriteExt;
 use tokio_stream::StreamExt;
 
 use crate::{Error, FromRequest, IntoResponse, Result};
@@ -56,6 +59,38 @@ use crate::{Error, FromRequest, IntoResponse, Result};
 #[derive(Debug)]
 pub struct Multipart {
     inner: multer::Multipart<'static>,
+    config: MultipartConfig,
+}
+
+/// Request extension configuring `Multipart::into_map`, i.e. `router.extension("/upload", MultipartConfig { field_limit: 1 << 20, total_limit: 1 << 24 })`.
+///
+/// Fields read manually via `Multipart::next_field` are not limited.
+#[derive(Debug, Clone, Copy)]
+pub struct MultipartConfig {
+    /// Maximum size of a single field in bytes. Larger fields are rejected with `413 Payload Too Large`.
+    pub field_limit: usize,
+    /// Maximum combined size of all fields in bytes. Larger bodies are rejected with `413 Payload Too Large`.
+    pub total_limit: usize,
+}
+
+impl Default for MultipartConfig {
+    fn default() -> Self {
+        Self {
+            field_limit: 2 * 1024 * 1024,
+            total_limit: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// A buffered multipart field, see `Multipart::into_map`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultipartValue {
+    Text(String),
+    File {
+        filename: String,
+        content_type: Option<String>,
+        bytes: Bytes,
+    },
 }
 
 #[async_trait]
@@ -72,7 +107,13 @@ impl<'a> FromRequest<'a> for Multipart {
             })
         });
         let multipart = multer::Multipart::new(stream, boundary);
-        Ok(Self { inner: multipart })
+        Ok(Self {
+            inner: multipart,
+            config: request
+                .extensions
+                .get_cloned::<MultipartConfig>()
+                .unwrap_or_default(),
+        })
     }
 }
 
@@ -95,6 +136,42 @@ impl Multipart {
             Ok(None)
         }
     }
+
+    /// Buffers all fields by name, enforcing `MultipartConfig`.
+    ///
+    /// Fields with a file name become `MultipartValue::File`, all others `MultipartValue::Text`, which must be valid UTF-8.
+    /// Unnamed fields are skipped, and repeated names keep the last value.
+    /// Use `next_field` to stream large uploads instead.
+    pub async fn into_map(mut self) -> Result<HashMap<String, MultipartValue>> {
+        let config = self.config;
+        let mut total = 0usize;
+        let mut out = HashMap::new();
+        while let Some(mut field) = self.next_field().await? {
+            let Some(name) = field.name().map(|x| x.to_string()) else {
+                continue;
+            };
+            let filename = field.file_name().map(|x| x.to_string());
+            let content_type = field.content_type().map(|x| x.to_string());
+            let mut bytes = Vec::new();
+            while let Some(chunk) = field.chunk().await? {
+                total += chunk.len();
+                if bytes.len() + chunk.len() > config.field_limit || total > config.total_limit {
+                    return Err(Error::PayloadTooLarge);
+                }
+                bytes.extend_from_slice(&chunk);
+            }
+            let value = match filename {
+                Some(filename) => MultipartValue::File {
+                    filename,
+                    content_type,
+                    bytes: bytes.into(),
+                },
+                None => MultipartValue::Text(String::from_utf8(bytes).map_err(|_| Error::BadUtf8)?),
+            };
+            out.insert(name, value);
+        }
+        Ok(out)
+    }
 }
 
 /// A single field in a multipart stream.
@@ -164,6 +241,34 @@ impl<'a> Field<'a> {
             .map_err(MultipartError::into_error)
     }
 
+    /// Streams the field data into a newly created (or truncated) file at `path`, returning the number of bytes written.
+    ///
+    /// If the field is larger than `limit` bytes, `413 Payload Too Large` is returned.
+    /// On any failure, the partially written file is removed.
+    pub async fn save_to(mut self, path: impl AsRef<Path>, limit: Option<u64>) -> Result<u64> {
+        let path = path.as_ref();
+        let mut file = tokio::fs::File::create(path).await?;
+        let result = async move {
+            let mut written = 0u64;
+            while let Some(chunk) = self.chunk().await? {
+                written += chunk.len() as u64;
+                if limit.is_some_and(|limit| written > limit) {
+                    return Err(Error::PayloadTooLarge);
+                }
+                file.write_all(&chunk).await?;
+            }
+            file.flush().await?;
+            Ok(written)
+        }
+        .await;
+        if result.is_err() {
+            if let Err(e) = tokio::fs::remove_file(path).await {
+                log::warn!("failed to remove partial upload {}: {e}", path.display());
+            }
+        }
+        result
+    }
+
     /// Stream a chunk of the field data.
     ///
     /// When the field data has been exhausted, this will return [`None`].
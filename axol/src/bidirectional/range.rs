@@ -0,0 +1,111 @@
+use std::ops::Range;
+
+use axol_http::{request::RequestPartsRef, response::Response, Body, StatusCode};
+
+use crate::{Error, FromRequestParts, IntoResponse, Result};
+
+/// Parses a `range` header against a body of `length` bytes.
+///
+/// Only single `bytes` ranges are supported. Other units, multiple ranges, and malformed headers produce `Ok(None)`,
+/// which per RFC 9110 means the full body should be served. Well-formed ranges that don't overlap the body produce a `416 Range Not Satisfiable` error
+/// with a `content-range: bytes */<length>` header.
+pub fn parse_range(header: &str, length: u64) -> Result<Option<Range<u64>>> {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+    let (start, end) = (start.trim(), end.trim());
+    let range = if start.is_empty() {
+        // suffix range, the last `end` bytes
+        let Ok(suffix) = end.parse::<u64>() else {
+            return Ok(None);
+        };
+        if suffix == 0 || length == 0 {
+            return Err(not_satisfiable(length));
+        }
+        length.saturating_sub(suffix)..length
+    } else {
+        let Ok(start) = start.parse::<u64>() else {
+            return Ok(None);
+        };
+        let end = if end.is_empty() {
+            length
+        } else {
+            let Ok(end) = end.parse::<u64>() else {
+                return Ok(None);
+            };
+            if end < start {
+                return Ok(None);
+            }
+            end.saturating_add(1).min(length)
+        };
+        if start >= length {
+            return Err(not_satisfiable(length));
+        }
+        start..end
+    };
+    Ok(Some(range))
+}
+
+fn not_satisfiable(length: u64) -> Error {
+    Error::range_not_satisfiable([("content-range", format!("bytes */{length}"))])
+}
+
+/// Formats a `content-range` header value for a range returned by `parse_range`.
+pub fn content_range(range: &Range<u64>, length: u64) -> String {
+    format!("bytes {}-{}/{length}", range.start, range.end - 1)
+}
+
+/// Extracts the request's `range` header, for serving partial content from buffered responses.
+///
+/// ```ignore
+/// async fn video(range: Ranged) -> Result<Response> {
+///     range.respond(load_video().await?)
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Ranged(pub Option<String>);
+
+impl Ranged {
+    /// Converts `response` into a `206 Partial Content` response if a satisfiable range was requested.
+    ///
+    /// Only successful `Body::Bytes` responses are sliced. `Body::Stream` responses can't be seeked,
+    /// so they are always served in full with a `200` and no `accept-ranges` header.
+    pub fn respond(&self, response: impl IntoResponse) -> Result<Response> {
+        let mut response = response.into_response()?;
+        if response.status != StatusCode::Ok {
+            return Ok(response);
+        }
+        let Body::Bytes(bytes) = &mut response.body else {
+            return Ok(response);
+        };
+        response.headers.insert("accept-ranges", "bytes");
+        let Some(header) = &self.0 else {
+            return Ok(response);
+        };
+        let length = bytes.len() as u64;
+        let Some(range) = parse_range(header, length)? else {
+            return Ok(response);
+        };
+        bytes.truncate(range.end as usize);
+        bytes.drain(..range.start as usize);
+        response.status = StatusCode::PartialContent;
+        response
+            .headers
+            .insert("content-range", content_range(&range, length));
+        response.headers.remove("content-length");
+        Ok(response)
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for Ranged {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        Ok(Self(request.headers.get("range").map(|x| x.to_string())))
+    }
+}
@@ -0,0 +1,213 @@
+use std::{
+    fmt::Write,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use axol_http::{body::BodyComponent, response::Response, Body};
+use futures::Stream;
+use serde::Serialize;
+use tokio::time::Sleep;
+
+use crate::{Error, IntoResponse, Result};
+
+/// A [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html) response.
+///
+/// ```ignore
+/// async fn events() -> Sse<impl Stream<Item = Result<Event>>> {
+///     let stream = futures::stream::iter((0..3).map(|i| Ok(Event::default().data(i.to_string()))));
+///     Sse::new(stream).keep_alive(KeepAlive::default())
+/// }
+/// ```
+#[must_use]
+pub struct Sse<S> {
+    stream: S,
+    keep_alive: Option<KeepAlive>,
+}
+
+impl<S> Sse<S> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            keep_alive: None,
+        }
+    }
+
+    /// Periodically send a comment while no events are sent, to keep the connection from being closed by proxies.
+    pub fn keep_alive(mut self, keep_alive: KeepAlive) -> Self {
+        self.keep_alive = Some(keep_alive);
+        self
+    }
+}
+
+/// Keep-alive configuration for `Sse`.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct KeepAlive {
+    interval: Duration,
+    text: String,
+}
+
+impl KeepAlive {
+    /// Defaults to an empty comment every 15 seconds.
+    pub fn new() -> Self {
+        Self {
+            interval: Duration::from_secs(15),
+            text: ":\n\n".to_string(),
+        }
+    }
+
+    /// Set the time without events after which a keep-alive comment is sent.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the text of the keep-alive comment. Must not contain newlines.
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Event::default().comment(text).finalize();
+        self
+    }
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single Server-Sent Event.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct Event {
+    buffer: String,
+}
+
+fn assert_no_newlines(field: &str, value: &str) {
+    assert!(
+        !value.contains(['\n', '\r']),
+        "SSE {field} must not contain newlines"
+    );
+}
+
+impl Event {
+    /// Set the event data. Multi-line data is split into multiple `data` fields.
+    pub fn data(mut self, data: impl AsRef<str>) -> Self {
+        for line in data.as_ref().split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            writeln!(&mut self.buffer, "data: {line}").unwrap();
+        }
+        self
+    }
+
+    /// Set the event data to the JSON serialization of `data`.
+    pub fn json_data(self, data: impl Serialize) -> Result<Self> {
+        let data = serde_json::to_string(&data).map_err(Error::internal)?;
+        Ok(self.data(data))
+    }
+
+    /// Set the event type (the `event` field).
+    pub fn event(mut self, event: impl AsRef<str>) -> Self {
+        assert_no_newlines("event", event.as_ref());
+        writeln!(&mut self.buffer, "event: {}", event.as_ref()).unwrap();
+        self
+    }
+
+    /// Set the event ID (the `id` field).
+    pub fn id(mut self, id: impl AsRef<str>) -> Self {
+        assert_no_newlines("id", id.as_ref());
+        assert!(!id.as_ref().contains('\0'), "SSE id must not contain NUL");
+        writeln!(&mut self.buffer, "id: {}", id.as_ref()).unwrap();
+        self
+    }
+
+    /// Set the client reconnection time (the `retry` field).
+    pub fn retry(mut self, retry: Duration) -> Self {
+        writeln!(&mut self.buffer, "retry: {}", retry.as_millis()).unwrap();
+        self
+    }
+
+    /// Add a comment, which is ignored by clients.
+    pub fn comment(mut self, comment: impl AsRef<str>) -> Self {
+        assert_no_newlines("comment", comment.as_ref());
+        writeln!(&mut self.buffer, ":{}", comment.as_ref()).unwrap();
+        self
+    }
+
+    fn finalize(mut self) -> String {
+        self.buffer.push('\n');
+        self.buffer
+    }
+}
+
+impl<S, E> IntoResponse for Sse<S>
+where
+    S: Stream<Item = Result<Event, E>> + Send + Sync + 'static,
+    E: Into<anyhow::Error>,
+{
+    fn into_response(self) -> Result<Response> {
+        let mut out = Response::default();
+        out.headers.insert("content-type", "text/event-stream");
+        out.headers.insert("cache-control", "no-cache");
+        let keep_alive = self.keep_alive.map(|keep_alive| KeepAliveState {
+            sleep: Box::pin(tokio::time::sleep(keep_alive.interval)),
+            keep_alive,
+        });
+        out.body = Body::Stream {
+            size_hint: None,
+            stream: Box::pin(SseStream {
+                inner: Box::pin(self.stream),
+                keep_alive,
+            }),
+        };
+        Ok(out)
+    }
+}
+
+struct KeepAliveState {
+    keep_alive: KeepAlive,
+    sleep: Pin<Box<Sleep>>,
+}
+
+struct SseStream<S> {
+    inner: Pin<Box<S>>,
+    keep_alive: Option<KeepAliveState>,
+}
+
+impl<S, E> Stream for SseStream<S>
+where
+    S: Stream<Item = Result<Event, E>>,
+    E: Into<anyhow::Error>,
+{
+    type Item = Result<BodyComponent, anyhow::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        match this.inner.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => {
+                if let Some(state) = &mut this.keep_alive {
+                    let deadline = tokio::time::Instant::now() + state.keep_alive.interval;
+                    state.sleep.as_mut().reset(deadline);
+                }
+                Poll::Ready(Some(Ok(BodyComponent::Data(event.finalize().into()))))
+            }
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e.into()))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => {
+                let Some(state) = &mut this.keep_alive else {
+                    return Poll::Pending;
+                };
+                if state.sleep.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                let deadline = tokio::time::Instant::now() + state.keep_alive.interval;
+                state.sleep.as_mut().reset(deadline);
+                Poll::Ready(Some(Ok(BodyComponent::Data(
+                    state.keep_alive.text.clone().into(),
+                ))))
+            }
+        }
+    }
+}
@@ -39,3 +39,39 @@ impl<'a, H: TypedHeader + Send + Sync + 'a> FromRequestParts<'a> for Typed<H> {
             .map(Typed)
     }
 }
+
+/// Like `Typed`, but distinguishes missing and malformed headers in its `400 Bad Request` rejection.
+#[derive(Debug, Clone, Copy, Default)]
+#[must_use]
+pub struct TypedRequired<H: TypedHeader>(pub H);
+
+impl<H: TypedHeader> Deref for TypedRequired<H> {
+    type Target = H;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<H: TypedHeader> DerefMut for TypedRequired<H> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a, H: TypedHeader + Send + Sync + 'a> FromRequestParts<'a> for TypedRequired<H> {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        match request.headers.try_get_typed::<H>() {
+            Some(Ok(header)) => Ok(TypedRequired(header)),
+            Some(Err(e)) => Err(Error::bad_request(format!(
+                "malformed header `{}`: {e}",
+                H::name()
+            ))),
+            None => Err(Error::bad_request(format!(
+                "missing header `{}`",
+                H::name()
+            ))),
+        }
+    }
+}
@@ -1,8 +1,15 @@
-use axol_http::{response::Response, StatusCode, Uri};
+use std::time::Duration;
+
+use axol_http::{
+    body::CollectError, response::Response, typed_headers::RetryAfter, StatusCode, Uri,
+};
 use url::Url;
 
 use crate::{AppendHeader, IntoResponse};
 
+#[cfg(feature = "problem-json")]
+mod problem;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, strum::Display)]
 pub enum RedirectMode {
     MovedPermanently,
@@ -54,6 +61,7 @@ impl IntoResponse for Url {
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Placeholder default for when an error has been taken
     #[error("not an error (you shouldn't see this)")]
@@ -129,7 +137,10 @@ pub enum Error {
     Response(Response),
     /// Returns a 500 Internal Service Error and logs the anyhow::Error to log::error (by default)
     #[error("{0:#}")]
-    Internal(anyhow::Error),
+    Internal(#[source] anyhow::Error),
+    /// Wraps an error with a human-readable detail, see `Error::with_detail`.
+    #[error("{0}: {1}")]
+    Detail(Box<Error>, String),
 
     #[cfg(feature = "grpc")]
     #[error("{0:?}")]
@@ -172,7 +183,55 @@ impl Default for Error {
 }
 
 impl Error {
+    /// The status code of the response this error turns into, before any `ErrorHook` runs.
+    /// `None` for `NotAnError` and `SkipMiddleware`, which don't turn into a response.
+    pub fn status(&self) -> Option<StatusCode> {
+        Some(match self {
+            Error::NotAnError | Error::SkipMiddleware => return None,
+            Error::Redirect(mode, _) | Error::RedirectUrl(mode, _) => mode.status(),
+            Error::BadRequest => StatusCode::BadRequest,
+            Error::Unauthorized => StatusCode::Unauthorized,
+            Error::Forbidden => StatusCode::Forbidden,
+            Error::NotFound => StatusCode::NotFound,
+            Error::MethodNotAllowed => StatusCode::MethodNotAllowed,
+            Error::NotAcceptable => StatusCode::NotAcceptable,
+            Error::RequestTimeout => StatusCode::RequestTimeout,
+            Error::Conflict => StatusCode::Conflict,
+            Error::Gone => StatusCode::Gone,
+            Error::PreconditionFailed => StatusCode::PreconditionFailed,
+            Error::PayloadTooLarge => StatusCode::PayloadTooLarge,
+            Error::UriTooLong => StatusCode::UriTooLong,
+            Error::UnsupportedMediaType => StatusCode::UnsupportedMediaType,
+            Error::RangeNotSatisfiable => StatusCode::RangeNotSatisfiable,
+            Error::ExpectationFailed => StatusCode::ExpectationFailed,
+            Error::UnprocessableEntity => StatusCode::UnprocessableEntity,
+            Error::TooManyRequests => StatusCode::TooManyRequests,
+            Error::UnavailableForLegalReasons => StatusCode::UnavailableForLegalReasons,
+
+            Error::InternalServerError => StatusCode::InternalServerError,
+            Error::NotImplemented => StatusCode::NotImplemented,
+            Error::BadGateway => StatusCode::BadGateway,
+            Error::ServiceUnavailable => StatusCode::ServiceUnavailable,
+            Error::GatewayTimeout => StatusCode::GatewayTimeout,
+
+            Error::BadUtf8 => StatusCode::UnprocessableEntity,
+            Error::Status(s) => *s,
+            Error::Response(r) => r.status,
+            Error::Internal(_) => StatusCode::InternalServerError,
+            Error::Detail(error, _) => return error.status(),
+            // gRPC errors are reported in trailers of a 200 response
+            #[cfg(feature = "grpc")]
+            Error::Grpc(_) | Error::GrpcMessage(_, _) => StatusCode::Ok,
+        })
+    }
+
+    /// With the `problem-json` feature, status code variants are rendered as RFC 7807 `application/problem+json` bodies.
+    /// Otherwise, they are rendered as bare status responses.
     pub fn into_response(self) -> Response {
+        #[cfg(feature = "problem-json")]
+        if let Some(problem) = problem::ProblemDetails::of(&self) {
+            return problem.into_response();
+        }
         match self {
             Error::NotAnError => unreachable!(),
             Error::BadRequest => StatusCode::BadRequest.into_response().unwrap(),
@@ -212,6 +271,7 @@ impl Error {
             Error::Status(s) => s.into_response().unwrap(),
             Error::Response(r) => r,
             Error::Internal(_) => StatusCode::InternalServerError.into_response().unwrap(),
+            Error::Detail(error, _) => error.into_response(),
             #[cfg(feature = "grpc")]
             Error::Grpc(status) => status.into_response().unwrap(),
             #[cfg(feature = "grpc")]
@@ -371,6 +431,15 @@ impl Error {
             .unwrap_or_else(|x| x)
     }
 
+    /// Same as `too_many_requests`, with a `retry-after` header of `retry_after` (in whole seconds, rounded up).
+    pub fn too_many_requests_after(retry_after: Duration, body: impl IntoResponse) -> Self {
+        body.into_response()
+            .map(|x| x.with_status(StatusCode::TooManyRequests))
+            .map(|x| with_retry_after(x, retry_after))
+            .map(Error::Response)
+            .unwrap_or_else(|x| x)
+    }
+
     pub fn unavailable_for_legal_reasons(body: impl IntoResponse) -> Self {
         body.into_response()
             .map(|x| x.with_status(StatusCode::UnavailableForLegalReasons))
@@ -406,6 +475,15 @@ impl Error {
             .unwrap_or_else(|x| x)
     }
 
+    /// Same as `service_unavailable`, with a `retry-after` header of `retry_after` (in whole seconds, rounded up).
+    pub fn service_unavailable_after(retry_after: Duration, body: impl IntoResponse) -> Self {
+        body.into_response()
+            .map(|x| x.with_status(StatusCode::ServiceUnavailable))
+            .map(|x| with_retry_after(x, retry_after))
+            .map(Error::Response)
+            .unwrap_or_else(|x| x)
+    }
+
     pub fn gateway_timeout(body: impl IntoResponse) -> Self {
         body.into_response()
             .map(|x| x.with_status(StatusCode::GatewayTimeout))
@@ -422,11 +500,53 @@ impl Error {
     pub fn internal(error: impl Into<anyhow::Error>) -> Self {
         Self::Internal(error.into())
     }
+
+    /// The underlying error of `Error::Internal`, i.e. for an `ErrorHook` to report its cause chain or backtrace.
+    /// Looks through `Error::Detail`.
+    pub fn as_internal(&self) -> Option<&anyhow::Error> {
+        match self {
+            Error::Internal(error) => Some(error),
+            Error::Detail(error, _) => error.as_internal(),
+            _ => None,
+        }
+    }
+
+    /// Attaches a human-readable detail, replacing any previous detail.
+    /// It is only rendered as the `detail` of `application/problem+json` responses with the `problem-json` feature.
+    pub fn with_detail(self, detail: impl Into<String>) -> Self {
+        match self {
+            Error::Detail(error, _) => Error::Detail(error, detail.into()),
+            error => Error::Detail(Box::new(error), detail.into()),
+        }
+    }
+}
+
+fn with_retry_after(mut response: Response, retry_after: Duration) -> Response {
+    // `RetryAfter` panics on sub-second durations. round up, so clients don't retry too early
+    let retry_after =
+        Duration::from_secs(retry_after.as_secs() + (retry_after.subsec_nanos() > 0) as u64);
+    response
+        .headers
+        .insert_typed(&RetryAfter::delay(retry_after));
+    response
 }
 
 impl From<anyhow::Error> for Error {
     fn from(value: anyhow::Error) -> Self {
-        Self::Internal(value)
+        // body streams can only carry `anyhow::Error`, so an `Error` smuggled through one is unwrapped here
+        match value.downcast::<Error>() {
+            Ok(error) => error,
+            Err(value) => Self::Internal(value),
+        }
+    }
+}
+
+impl From<CollectError> for Error {
+    fn from(value: CollectError) -> Self {
+        match value {
+            CollectError::TooLarge(_) => Self::PayloadTooLarge,
+            CollectError::Stream(e) => e.into(),
+        }
     }
 }
 
@@ -0,0 +1,63 @@
+use axol_http::{response::Response, Body, StatusCode};
+use serde::Serialize;
+
+use super::Error;
+
+/// An RFC 7807 `application/problem+json` body.
+#[derive(Serialize)]
+pub(super) struct ProblemDetails {
+    #[serde(skip)]
+    status_code: StatusCode,
+    #[serde(rename = "type")]
+    type_: &'static str,
+    title: &'static str,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+impl ProblemDetails {
+    fn new(status: StatusCode, detail: Option<String>) -> Self {
+        Self {
+            status_code: status,
+            type_: "about:blank",
+            title: status.canonical_reason(),
+            status: status.as_u16(),
+            detail,
+        }
+    }
+
+    /// Problem details for the status code variants of `error`. Other variants keep their own response.
+    pub(super) fn of(error: &Error) -> Option<Self> {
+        match error {
+            Error::Detail(error, detail) => Self::of(error).map(|x| Self {
+                detail: Some(detail.clone()),
+                ..x
+            }),
+            Error::BadUtf8 => Some(Self::new(
+                error.status()?,
+                Some("invalid UTF-8 in request".to_string()),
+            )),
+            Error::NotAnError
+            | Error::SkipMiddleware
+            | Error::Redirect(_, _)
+            | Error::RedirectUrl(_, _)
+            | Error::Response(_) => None,
+            #[cfg(feature = "grpc")]
+            Error::Grpc(_) | Error::GrpcMessage(_, _) => None,
+            error => Some(Self::new(error.status()?, None)),
+        }
+    }
+
+    pub(super) fn into_response(self) -> Response {
+        let mut out = Response {
+            status: self.status_code,
+            // serializing only strings and integers cannot fail
+            body: Body::Bytes(serde_json::to_vec(&self).unwrap()),
+            ..Default::default()
+        };
+        out.headers
+            .insert_static("content-type", "application/problem+json");
+        out
+    }
+}
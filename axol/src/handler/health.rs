@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use axol_http::{request::RequestPartsRef, response::Response, Body};
+use futures::future::BoxFuture;
+
+use crate::{Error, HandlerExpansion, IntoResponse, Result, Router, SkipLogging};
+
+type ReadyFn = dyn Fn() -> BoxFuture<'static, bool> + Send + Sync;
+
+/// Readiness probe, responding `200 OK` if the check resolves to `true` and `503 Service Unavailable` otherwise.
+/// See `Router::with_health`.
+#[derive(Clone)]
+pub struct Readiness(Arc<ReadyFn>);
+
+impl Readiness {
+    pub fn new<F>(ready: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, bool> + Send + Sync + 'static,
+    {
+        Self(Arc::new(ready))
+    }
+}
+
+#[async_trait::async_trait]
+impl HandlerExpansion<Readiness> for Readiness {
+    async fn call<'a>(&self, _request_parts: RequestPartsRef<'a>, _body: Body) -> Result<Response> {
+        if (self.0)().await {
+            "ready".into_response()
+        } else {
+            Err(Error::service_unavailable("not ready"))
+        }
+    }
+}
+
+async fn liveness() -> &'static str {
+    "ok"
+}
+
+impl Router {
+    /// Registers the usual Kubernetes probes: `liveness` always responds `200 OK`, and `readiness` is a `Readiness` probe of `ready`.
+    /// Both are marked with `SkipLogging`, so they don't flood the logs.
+    ///
+    /// ```ignore
+    /// router.with_health("/healthz", "/readyz", move || {
+    ///     let pool = pool.clone();
+    ///     Box::pin(async move { pool.get().await.is_ok() })
+    /// })
+    /// ```
+    pub fn with_health<F>(self, liveness_path: &str, readiness_path: &str, ready: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, bool> + Send + Sync + 'static,
+    {
+        self.get(liveness_path, liveness)
+            .get(readiness_path, Readiness::new(ready))
+            .extension(liveness_path, SkipLogging)
+            .extension(readiness_path, SkipLogging)
+    }
+}
@@ -1,12 +1,33 @@
 use crate::{FromRequest, FromRequestParts, IntoResponse, Result};
 use axol_http::{request::RequestPartsRef, response::Response, Body};
 use futures::Future;
+use std::sync::Arc;
+
+#[cfg(feature = "fs")]
+mod serve_dir;
+#[cfg(feature = "fs")]
+pub use serve_dir::*;
+
+#[cfg(feature = "proxy")]
+mod proxy;
+#[cfg(feature = "proxy")]
+pub use proxy::*;
+
+mod health;
+pub use health::*;
 
 #[async_trait::async_trait]
 pub trait Handler: Send + Sync + 'static {
     async fn call<'a>(&self, request_parts: RequestPartsRef<'a>, body: Body) -> Result<Response>;
 }
 
+#[async_trait::async_trait]
+impl<H: Handler + ?Sized> Handler for Arc<H> {
+    async fn call<'a>(&self, request_parts: RequestPartsRef<'a>, body: Body) -> Result<Response> {
+        (**self).call(request_parts, body).await
+    }
+}
+
 #[async_trait::async_trait]
 pub trait HandlerExpansion<G>: Send + Sync + 'static {
     async fn call<'a>(&self, request_parts: RequestPartsRef<'a>, body: Body) -> Result<Response>;
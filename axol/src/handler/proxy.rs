@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use axol_http::{
+    body::BodyWrapper, header::HeaderMap, request::RequestPartsRef, response::Response, Body, Uri,
+};
+use hyper::{client::HttpConnector, Body as HyperBody, Client, Request as HyperRequest};
+
+use crate::{server::BodyInputStream, Error, Handler, NestedPath, Result};
+
+/// Headers that only apply to a single connection, and must not be forwarded.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Forwards requests to an upstream HTTP server, streaming request and response bodies.
+///
+/// The upstream path is the path of `upstream` followed by the `NestedPath` of the request if any (see `Router::nest_service`),
+/// or else the full request path. The query string is forwarded as is.
+/// Hop-by-hop headers (and any listed in `connection`) are stripped in both directions.
+///
+/// Upstream connection failures produce `502 Bad Gateway`, and upstreams not responding within the timeout `504 Gateway Timeout`.
+/// Only plain `http` upstreams are supported.
+///
+/// ```ignore
+/// router.nest_service("/api", Proxy::new("http://127.0.0.1:8080/v1".parse().unwrap()))
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Proxy {
+    upstream: Uri,
+    client: Client<HttpConnector, BodyWrapper>,
+    rewrite_host: bool,
+    timeout: Option<Duration>,
+}
+
+impl Proxy {
+    /// Panics if `upstream` is not an absolute URI.
+    pub fn new(upstream: Uri) -> Self {
+        assert!(
+            upstream.scheme().is_some() && upstream.authority().is_some(),
+            "proxy upstream `{upstream}` must be an absolute URI, i.e. `http://127.0.0.1:8080`"
+        );
+        Self {
+            upstream,
+            client: Client::builder().build_http(),
+            rewrite_host: true,
+            timeout: Some(Duration::from_secs(30)),
+        }
+    }
+
+    /// Whether to set the `host` header to the upstream's authority. Defaults to `true`.
+    /// If `false`, the incoming `host` header is forwarded as is.
+    pub fn rewrite_host(mut self, rewrite_host: bool) -> Self {
+        self.rewrite_host = rewrite_host;
+        self
+    }
+
+    /// How long to wait for the upstream's response headers. Defaults to 30 seconds.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn upstream_uri(&self, request: RequestPartsRef<'_>) -> Result<Uri> {
+        let path = match request.extensions.get::<NestedPath>() {
+            Some(nested) => nested.0.clone(),
+            None => request.uri.path().to_string(),
+        };
+        let mut path_and_query = format!("{}{path}", self.upstream.path().trim_end_matches('/'));
+        if let Some(query) = request.uri.query() {
+            path_and_query.push('?');
+            path_and_query.push_str(query);
+        }
+        Uri::builder()
+            .scheme(self.upstream.scheme().cloned().unwrap())
+            .authority(self.upstream.authority().cloned().unwrap())
+            .path_and_query(path_and_query)
+            .build()
+            .map_err(|_| Error::BadRequest)
+    }
+}
+
+/// Copies `headers` minus hop-by-hop headers.
+fn forwarded_headers(headers: &HeaderMap) -> HeaderMap {
+    let connection_headers = headers
+        .get_all("connection")
+        .flat_map(|x| x.split(','))
+        .map(|x| x.trim())
+        .collect::<Vec<_>>();
+    let mut out = headers.clone();
+    out.retain(|name, _| {
+        !HOP_BY_HOP_HEADERS
+            .iter()
+            .chain(connection_headers.iter())
+            .any(|x| x.eq_ignore_ascii_case(name))
+    });
+    out
+}
+
+#[async_trait::async_trait]
+impl Handler for Proxy {
+    async fn call<'a>(&self, request: RequestPartsRef<'a>, body: Body) -> Result<Response> {
+        let mut headers = forwarded_headers(request.headers);
+        if self.rewrite_host {
+            // hyper fills in the upstream authority when `host` is absent
+            headers.remove("host");
+        }
+        let method: axol_http::http::Method = request.method.into();
+        let mut builder = HyperRequest::builder()
+            .method(method)
+            .uri(self.upstream_uri(request)?);
+        *builder.headers_mut().unwrap() = headers.into();
+        let upstream_request = builder
+            .body(BodyWrapper::from(body))
+            .map_err(Error::internal)?;
+
+        let response = self.client.request(upstream_request);
+        let response = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, response)
+                .await
+                .map_err(|_| Error::GatewayTimeout)?,
+            None => response.await,
+        };
+        let response = response.map_err(|e| {
+            log::warn!("proxy request to {} failed: {e}", self.upstream);
+            Error::BadGateway
+        })?;
+
+        let (parts, body) = response.into_parts();
+        let headers: HeaderMap = parts.headers.try_into().map_err(|_| Error::BadGateway)?;
+        Ok(Response {
+            status: parts.status.into(),
+            headers: forwarded_headers(&headers),
+            body: Body::Stream {
+                size_hint: <HyperBody as hyper::body::HttpBody>::size_hint(&body)
+                    .exact()
+                    .map(|x| x as usize),
+                stream: Box::pin(BodyInputStream::new(body)),
+            },
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,146 @@
+use std::{
+    io::{ErrorKind, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use axol_http::{
+    request::RequestPartsRef,
+    response::Response,
+    typed_headers::{ContentLength, ContentType, IfModifiedSince, LastModified},
+    Body, StatusCode,
+};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+
+use crate::{
+    content_range, parse_range, Error, FromRequestParts, HandlerExpansion, RawPath, Result,
+};
+
+/// Serves files from a directory, using the last path variable (usually a wildcard) as the path within the directory.
+///
+/// ```ignore
+/// router.get("/assets/*path", ServeDir::new("./public"))
+/// ```
+///
+/// Paths containing `..` segments are rejected with `403 Forbidden`, missing files produce `404 Not Found`.
+/// Directories are served via their `index.html`, if any.
+/// Single byte ranges (`range: bytes=0-99`) are served as `206 Partial Content`.
+#[derive(Debug, Clone)]
+pub struct ServeDir {
+    base: PathBuf,
+    index_file: Option<String>,
+}
+
+impl ServeDir {
+    pub fn new(base: impl Into<PathBuf>) -> Self {
+        Self {
+            base: base.into(),
+            index_file: Some("index.html".to_string()),
+        }
+    }
+
+    /// Set the file served when a directory is requested. Defaults to `index.html`.
+    /// If `None`, requests for directories produce `404 Not Found`.
+    pub fn index_file(mut self, index_file: Option<impl Into<String>>) -> Self {
+        self.index_file = index_file.map(Into::into);
+        self
+    }
+
+    fn resolve(&self, subpath: &str) -> Result<PathBuf> {
+        let mut path = self.base.clone();
+        for segment in subpath.split('/') {
+            match segment {
+                "" | "." => continue,
+                ".." => return Err(Error::Forbidden),
+                // don't let a decoded segment smuggle in a separator or a prefix on windows
+                segment if segment.contains(['\\', ':', '\0']) => return Err(Error::Forbidden),
+                segment => path.push(segment),
+            }
+        }
+        Ok(path)
+    }
+
+    async fn open(&self, path: &Path) -> Result<(File, std::fs::Metadata, PathBuf)> {
+        let mut path = path.to_path_buf();
+        let mut metadata = tokio::fs::metadata(&path).await.map_err(map_io_error)?;
+        if metadata.is_dir() {
+            let Some(index_file) = &self.index_file else {
+                return Err(Error::NotFound);
+            };
+            path.push(index_file);
+            metadata = tokio::fs::metadata(&path).await.map_err(map_io_error)?;
+            if !metadata.is_file() {
+                return Err(Error::NotFound);
+            }
+        }
+        let file = File::open(&path).await.map_err(map_io_error)?;
+        Ok((file, metadata, path))
+    }
+
+    async fn serve(&self, request: RequestPartsRef<'_>) -> Result<Response> {
+        let raw_path = RawPath::from_request_parts(request).await?;
+        let subpath = raw_path.last().map(|(_, x)| &**x).unwrap_or_default();
+        let path = self.resolve(subpath)?;
+        let (mut file, metadata, path) = self.open(&path).await?;
+
+        let mut response = Response::default();
+        let modified = metadata.modified().ok();
+        if let Some(modified) = modified {
+            response.headers.insert_typed(&LastModified::from(modified));
+            if let Some(if_modified_since) = request.headers.get_typed::<IfModifiedSince>() {
+                if !if_modified_since.is_modified(modified) {
+                    response.status = StatusCode::NotModified;
+                    return Ok(response);
+                }
+            }
+        }
+        let mime = mime_guess::from_path(&path).first_or_octet_stream();
+        response.headers.insert_typed(&ContentType::from(mime));
+
+        let length = metadata.len();
+        response.headers.insert("accept-ranges", "bytes");
+        let range = match request.headers.get("range") {
+            Some(header) => parse_range(header, length)?,
+            None => None,
+        };
+        let Some(range) = range else {
+            response.headers.insert_typed(&ContentLength(length));
+            response.body = file_body(file, length);
+            return Ok(response);
+        };
+        file.seek(SeekFrom::Start(range.start))
+            .await
+            .map_err(Error::internal)?;
+        response.status = StatusCode::PartialContent;
+        response
+            .headers
+            .insert("content-range", content_range(&range, length));
+        response
+            .headers
+            .insert_typed(&ContentLength(range.end - range.start));
+        response.body = file_body(file, range.end - range.start);
+        Ok(response)
+    }
+}
+
+fn map_io_error(e: std::io::Error) -> Error {
+    match e.kind() {
+        ErrorKind::NotFound => Error::NotFound,
+        ErrorKind::PermissionDenied => Error::Forbidden,
+        _ => Error::internal(e),
+    }
+}
+
+/// Streams up to `length` bytes from `file`.
+pub(crate) fn file_body(file: File, length: u64) -> Body {
+    Body::from_reader(file.take(length), Some(length as usize))
+}
+
+#[async_trait::async_trait]
+impl HandlerExpansion<ServeDir> for ServeDir {
+    async fn call<'a>(&self, request_parts: RequestPartsRef<'a>, _body: Body) -> Result<Response> {
+        self.serve(request_parts).await
+    }
+}
@@ -25,6 +25,11 @@ pub use server::*;
 mod middleware;
 pub use middleware::*;
 
+#[cfg(feature = "test-util")]
+mod test_client;
+#[cfg(feature = "test-util")]
+pub use test_client::*;
+
 pub use axol_http as http;
 
 pub mod prelude {
@@ -12,14 +12,17 @@ impl ErrorHook for DefaultErrorHook {
         error: &mut Error,
     ) -> Result<Option<Response>> {
         //TODO: log header
-        match &error {
-            Error::Internal(e) => {
+        match error.as_internal() {
+            Some(e) => {
                 log::error!("internal error: {e:#}");
             }
-            e => {
-                log::debug!("returning error response: {e}");
+            None => {
+                log::debug!("returning error response: {error}");
             }
         }
-        Ok(Some(std::mem::take(error).into_response()))
+        let response = std::mem::take(error).into_response();
+        // don't leave `NotAnError` behind for anything looking at the error afterwards
+        *error = Error::Status(response.status);
+        Ok(Some(response))
     }
 }
@@ -0,0 +1,317 @@
+use std::{
+    fmt::Write,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use axol_http::{
+    request::{Request, RequestPartsRef},
+    response::Response,
+    Body,
+};
+use log::Level;
+
+use crate::{ConnectInfo, LateResponseHook, Plugin, RequestHook, Result, Router, SkipLogging};
+
+/// Apache Common Log Format
+pub const COMMON_LOG_FORMAT: &str = "%h %l %u %t \"%r\" %>s %b";
+/// Apache Combined Log Format
+pub const COMBINED_LOG_FORMAT: &str =
+    "%h %l %u %t \"%r\" %>s %b \"%{Referer}i\" \"%{User-agent}i\"";
+
+/// Logs one line per request in an Apache `mod_log_config` style format, to the `axol::access_log` log target.
+///
+/// Supported directives are `%h` (client IP), `%l` and `%u` (always `-`), `%t` (time in UTC), `%r` (request line),
+/// `%s`/`%>s` (status), `%b`/`%B` (response size, `-`/`0` if unknown), `%m` (method), `%U` (path), `%q` (query string, with `?`),
+/// `%H` (protocol), `%D`/`%T` (time taken in microseconds/seconds), `%{Name}i` (request header), `%{Name}o` (response header), and `%%`.
+/// Unknown directives are logged as is.
+///
+/// Values that are unavailable, i.e. `%h` without `ConnectInfo`, are logged as `-`.
+/// `%D` and `%T` require the request hook half of this middleware, so it should be registered as a plugin.
+///
+/// ```ignore
+/// router.plugin("/", AccessLog::combined())
+/// ```
+#[derive(Debug, Clone)]
+pub struct AccessLog {
+    tokens: Vec<Token>,
+    pub level: Level,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    RemoteHost,
+    Dash,
+    Time,
+    RequestLine,
+    Status,
+    Size { zero: bool },
+    Method,
+    Path,
+    Query,
+    Protocol,
+    Elapsed { micros: bool },
+    RequestHeader(String),
+    ResponseHeader(String),
+}
+
+struct AccessLogInfo {
+    start: Instant,
+}
+
+impl AccessLog {
+    /// Create an `AccessLog` logging with the given format, see the type documentation for supported directives.
+    pub fn new(format: &str) -> Self {
+        Self {
+            tokens: parse_format(format),
+            level: Level::Info,
+        }
+    }
+
+    /// Apache Common Log Format, i.e. `127.0.0.1 - - [10/Oct/2000:13:55:36 +0000] "GET /a.gif HTTP/1.0" 200 2326`
+    pub fn common() -> Self {
+        Self::new(COMMON_LOG_FORMAT)
+    }
+
+    /// Apache Combined Log Format, which is the Common Log Format followed by the quoted `referer` and `user-agent` headers.
+    pub fn combined() -> Self {
+        Self::new(COMBINED_LOG_FORMAT)
+    }
+
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    fn format(&self, request: RequestPartsRef<'_>, response: &Response) -> String {
+        let mut out = String::new();
+        for token in &self.tokens {
+            match token {
+                Token::Literal(literal) => out.push_str(literal),
+                Token::RemoteHost => match request.extensions.get::<ConnectInfo>() {
+                    Some(connect_info) => write!(&mut out, "{}", connect_info.0.ip()).unwrap(),
+                    None => out.push('-'),
+                },
+                Token::Dash => out.push('-'),
+                Token::Time => write_clf_time(&mut out, SystemTime::now()),
+                Token::RequestLine => {
+                    let path = request
+                        .uri
+                        .path_and_query()
+                        .map(|x| x.as_str())
+                        .unwrap_or("/");
+                    write!(&mut out, "{} ", request.method).unwrap();
+                    write_escaped(&mut out, path);
+                    write!(&mut out, " {:?}", request.version).unwrap();
+                }
+                Token::Status => write!(&mut out, "{}", response.status.as_u16()).unwrap(),
+                Token::Size { zero } => match response_size(response) {
+                    Some(size) => write!(&mut out, "{size}").unwrap(),
+                    None if *zero => out.push('0'),
+                    None => out.push('-'),
+                },
+                Token::Method => write!(&mut out, "{}", request.method).unwrap(),
+                Token::Path => write_escaped(&mut out, request.uri.path()),
+                Token::Query => {
+                    if let Some(query) = request.uri.query() {
+                        out.push('?');
+                        write_escaped(&mut out, query);
+                    }
+                }
+                Token::Protocol => write!(&mut out, "{:?}", request.version).unwrap(),
+                Token::Elapsed { micros } => match request.extensions.get::<AccessLogInfo>() {
+                    Some(info) if *micros => {
+                        write!(&mut out, "{}", info.start.elapsed().as_micros()).unwrap()
+                    }
+                    Some(info) => write!(&mut out, "{}", info.start.elapsed().as_secs()).unwrap(),
+                    None => out.push('-'),
+                },
+                Token::RequestHeader(name) => match request.headers.get(name) {
+                    Some(value) => write_escaped(&mut out, value),
+                    None => out.push('-'),
+                },
+                Token::ResponseHeader(name) => match response.headers.get(name) {
+                    Some(value) => write_escaped(&mut out, value),
+                    None => out.push('-'),
+                },
+            }
+        }
+        out
+    }
+}
+
+fn parse_format(format: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+        let mut directive = String::from('%');
+        let mut argument = None;
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            let name: String = chars.by_ref().take_while(|x| *x != '}').collect();
+            directive.push_str(&format!("{{{name}}}"));
+            argument = Some(name);
+        }
+        if chars.peek() == Some(&'>') {
+            directive.push(chars.next().unwrap());
+        }
+        let Some(kind) = chars.next() else {
+            literal.push_str(&directive);
+            break;
+        };
+        let token = match (kind, argument) {
+            ('%', None) => {
+                literal.push('%');
+                continue;
+            }
+            ('h', None) => Token::RemoteHost,
+            ('l' | 'u', None) => Token::Dash,
+            ('t', None) => Token::Time,
+            ('r', None) => Token::RequestLine,
+            ('s', None) => Token::Status,
+            ('b', None) => Token::Size { zero: false },
+            ('B', None) => Token::Size { zero: true },
+            ('m', None) => Token::Method,
+            ('U', None) => Token::Path,
+            ('q', None) => Token::Query,
+            ('H', None) => Token::Protocol,
+            ('D', None) => Token::Elapsed { micros: true },
+            ('T', None) => Token::Elapsed { micros: false },
+            ('i', Some(name)) => Token::RequestHeader(name),
+            ('o', Some(name)) => Token::ResponseHeader(name),
+            (kind, _) => {
+                literal.push_str(&directive);
+                literal.push(kind);
+                continue;
+            }
+        };
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(std::mem::take(&mut literal)));
+        }
+        tokens.push(token);
+    }
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+    tokens
+}
+
+fn response_size(response: &Response) -> Option<u64> {
+    match &response.body {
+        Body::Bytes(bytes) => Some(bytes.len() as u64),
+        Body::Stream { size_hint, .. } => response
+            .headers
+            .get("content-length")
+            .and_then(|x| x.trim().parse().ok())
+            .or(size_hint.map(|x| x as u64)),
+    }
+}
+
+/// Escapes quotes, backslashes, and non-printable characters like Apache does, so log lines can't be forged.
+fn write_escaped(out: &mut String, value: &str) {
+    for byte in value.bytes() {
+        match byte {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            0x20..=0x7e => out.push(byte as char),
+            byte => write!(out, "\\x{byte:02x}").unwrap(),
+        }
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Writes `time` as `[10/Oct/2000:13:55:36 +0000]`
+fn write_clf_time(out: &mut String, time: SystemTime) {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or_default();
+    let (days, secs_of_day) = (secs / 86400, secs % 86400);
+
+    // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    write!(
+        out,
+        "[{day:02}/{}/{year}:{:02}:{:02}:{:02} +0000]",
+        MONTHS[month as usize - 1],
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    )
+    .unwrap();
+}
+
+#[async_trait::async_trait]
+impl RequestHook for AccessLog {
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        request.extensions.insert(AccessLogInfo {
+            start: Instant::now(),
+        });
+        Ok(None)
+    }
+}
+
+#[async_trait::async_trait]
+impl LateResponseHook for AccessLog {
+    async fn handle_response<'a>(&self, request: RequestPartsRef<'a>, response: &mut Response) {
+        if request.extensions.get::<SkipLogging>().is_some() {
+            return;
+        }
+        log::log!(target: "axol::access_log", self.level, "{}", self.format(request, response));
+    }
+}
+
+impl Plugin for AccessLog {
+    fn apply(self, router: Router, path: &str) -> Router {
+        router
+            .request_hook_direct(path, self.clone())
+            .late_response_hook_direct(path, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            parse_format("%h %>s \"%{Referer}i\" 100%% %z"),
+            vec![
+                Token::RemoteHost,
+                Token::Literal(" ".to_string()),
+                Token::Status,
+                Token::Literal(" \"".to_string()),
+                Token::RequestHeader("Referer".to_string()),
+                Token::Literal("\" 100% %z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clf_time() {
+        let mut out = String::new();
+        write_clf_time(
+            &mut out,
+            UNIX_EPOCH + std::time::Duration::from_secs(971_186_136),
+        );
+        assert_eq!(out, "[10/Oct/2000:13:55:36 +0000]");
+    }
+}
@@ -0,0 +1,183 @@
+use std::io::{self, Write};
+
+use flate2::write::{GzDecoder, GzEncoder, ZlibDecoder, ZlibEncoder};
+
+/// A `content-encoding` supported by the compression middleware.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    Gzip,
+    /// HTTP `deflate`, which is the zlib format (RFC 1950)
+    Deflate,
+    #[cfg(feature = "brotli")]
+    Brotli,
+}
+
+impl CompressionAlgorithm {
+    /// All supported algorithms, in default order of preference.
+    pub fn all() -> Vec<Self> {
+        vec![
+            #[cfg(feature = "brotli")]
+            CompressionAlgorithm::Brotli,
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Deflate,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Gzip => "gzip",
+            CompressionAlgorithm::Deflate => "deflate",
+            #[cfg(feature = "brotli")]
+            CompressionAlgorithm::Brotli => "br",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("gzip") || name.eq_ignore_ascii_case("x-gzip") {
+            Some(CompressionAlgorithm::Gzip)
+        } else if name.eq_ignore_ascii_case("deflate") {
+            Some(CompressionAlgorithm::Deflate)
+        } else {
+            #[cfg(feature = "brotli")]
+            if name.eq_ignore_ascii_case("br") {
+                return Some(CompressionAlgorithm::Brotli);
+            }
+            None
+        }
+    }
+}
+
+/// Incremental compressor writing into an internal buffer.
+pub(crate) enum Encoder {
+    Gzip(GzEncoder<Vec<u8>>),
+    Deflate(ZlibEncoder<Vec<u8>>),
+    #[cfg(feature = "brotli")]
+    Brotli(Box<brotli::CompressorWriter<Vec<u8>>>),
+}
+
+impl Encoder {
+    pub(crate) fn new(algorithm: CompressionAlgorithm) -> Self {
+        match algorithm {
+            CompressionAlgorithm::Gzip => {
+                Encoder::Gzip(GzEncoder::new(vec![], flate2::Compression::default()))
+            }
+            CompressionAlgorithm::Deflate => {
+                Encoder::Deflate(ZlibEncoder::new(vec![], flate2::Compression::default()))
+            }
+            #[cfg(feature = "brotli")]
+            CompressionAlgorithm::Brotli => {
+                Encoder::Brotli(Box::new(brotli::CompressorWriter::new(vec![], 4096, 5, 22)))
+            }
+        }
+    }
+
+    /// Takes any compressed output produced so far.
+    pub(crate) fn take_output(&mut self) -> Vec<u8> {
+        match self {
+            Encoder::Gzip(x) => std::mem::take(x.get_mut()),
+            Encoder::Deflate(x) => std::mem::take(x.get_mut()),
+            #[cfg(feature = "brotli")]
+            Encoder::Brotli(x) => std::mem::take(x.get_mut()),
+        }
+    }
+
+    /// Ends the compressed stream, returning any remaining output.
+    pub(crate) fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Encoder::Gzip(x) => x.finish(),
+            Encoder::Deflate(x) => x.finish(),
+            #[cfg(feature = "brotli")]
+            Encoder::Brotli(x) => Ok(x.into_inner()),
+        }
+    }
+}
+
+impl Write for Encoder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Encoder::Gzip(x) => x.write(buf),
+            Encoder::Deflate(x) => x.write(buf),
+            #[cfg(feature = "brotli")]
+            Encoder::Brotli(x) => x.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Encoder::Gzip(x) => x.flush(),
+            Encoder::Deflate(x) => x.flush(),
+            #[cfg(feature = "brotli")]
+            Encoder::Brotli(x) => x.flush(),
+        }
+    }
+}
+
+pub(crate) fn compress(algorithm: CompressionAlgorithm, data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = Encoder::new(algorithm);
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Incremental decompressor writing into an internal buffer.
+pub(crate) enum Decoder {
+    Gzip(GzDecoder<Vec<u8>>),
+    Deflate(ZlibDecoder<Vec<u8>>),
+    #[cfg(feature = "brotli")]
+    Brotli(Box<brotli::DecompressorWriter<Vec<u8>>>),
+}
+
+impl Decoder {
+    pub(crate) fn new(algorithm: CompressionAlgorithm) -> Self {
+        match algorithm {
+            CompressionAlgorithm::Gzip => Decoder::Gzip(GzDecoder::new(vec![])),
+            CompressionAlgorithm::Deflate => Decoder::Deflate(ZlibDecoder::new(vec![])),
+            #[cfg(feature = "brotli")]
+            CompressionAlgorithm::Brotli => {
+                Decoder::Brotli(Box::new(brotli::DecompressorWriter::new(vec![], 4096)))
+            }
+        }
+    }
+
+    /// Takes any decompressed output produced so far.
+    pub(crate) fn take_output(&mut self) -> Vec<u8> {
+        match self {
+            Decoder::Gzip(x) => std::mem::take(x.get_mut()),
+            Decoder::Deflate(x) => std::mem::take(x.get_mut()),
+            #[cfg(feature = "brotli")]
+            Decoder::Brotli(x) => std::mem::take(x.get_mut()),
+        }
+    }
+
+    /// Ends the compressed stream, returning any remaining output. Fails if the stream was truncated.
+    pub(crate) fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Decoder::Gzip(x) => x.finish(),
+            Decoder::Deflate(x) => x.finish(),
+            #[cfg(feature = "brotli")]
+            Decoder::Brotli(x) => x.into_inner().map_err(|_| {
+                io::Error::new(io::ErrorKind::UnexpectedEof, "truncated brotli stream")
+            }),
+        }
+    }
+}
+
+impl Write for Decoder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Decoder::Gzip(x) => x.write(buf),
+            Decoder::Deflate(x) => x.write(buf),
+            #[cfg(feature = "brotli")]
+            Decoder::Brotli(x) => x.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Decoder::Gzip(x) => x.flush(),
+            Decoder::Deflate(x) => x.flush(),
+            #[cfg(feature = "brotli")]
+            Decoder::Brotli(x) => x.flush(),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    io::Write,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axol_http::{
+    body::{BodyComponent, BodyStream},
+    header::HeaderMap,
+    request::Request,
+    response::Response,
+    Body,
+};
+use futures::{ready, Stream};
+
+use crate::{Error, Plugin, RequestHook, Result, Router};
+
+use super::{CompressionAlgorithm, Decoder};
+
+/// Request hook that transparently decompresses request bodies sent with a supported `content-encoding`.
+/// The `content-encoding` and `content-length` headers are removed from decoded requests.
+/// Malformed bodies surface as `Error::BadRequest` when the body is read.
+/// Requests with an unsupported `content-encoding` are passed through untouched.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct RequestDecompression;
+
+impl RequestDecompression {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHook for RequestDecompression {
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        let mut algorithms = vec![];
+        for value in request.headers.get_all("content-encoding") {
+            for coding in value.split(',') {
+                let coding = coding.trim();
+                if coding.is_empty() || coding.eq_ignore_ascii_case("identity") {
+                    continue;
+                }
+                let Some(algorithm) = CompressionAlgorithm::from_name(coding) else {
+                    return Ok(None);
+                };
+                algorithms.push(algorithm);
+            }
+        }
+        if algorithms.is_empty() {
+            return Ok(None);
+        }
+        request.headers.remove("content-encoding");
+        request.headers.remove("content-length");
+
+        // codings are listed in the order they were applied
+        let mut stream = std::mem::take(&mut request.body).into_stream();
+        for algorithm in algorithms.into_iter().rev() {
+            stream = Box::pin(DecompressStream {
+                inner: stream,
+                decoder: Some(Decoder::new(algorithm)),
+                pending_trailers: None,
+            });
+        }
+        request.body = Body::Stream {
+            size_hint: None,
+            stream,
+        };
+        Ok(None)
+    }
+}
+
+impl Plugin for RequestDecompression {
+    fn apply(self, router: Router, path: &str) -> Router {
+        router.request_hook_direct(path, self)
+    }
+}
+
+struct DecompressStream {
+    inner: BodyStream,
+    decoder: Option<Decoder>,
+    pending_trailers: Option<HeaderMap>,
+}
+
+fn decode_error(e: std::io::Error) -> anyhow::Error {
+    log::debug!("failed to decode request body: {e}");
+    Error::BadRequest.into()
+}
+
+impl Stream for DecompressStream {
+    type Item = Result<BodyComponent, anyhow::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            let Some(decoder) = this.decoder.as_mut() else {
+                return Poll::Ready(
+                    this.pending_trailers
+                        .take()
+                        .map(|x| Ok(BodyComponent::Trailers(x))),
+                );
+            };
+            let output = match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(BodyComponent::Data(data))) => {
+                    if let Err(e) = decoder.write_all(&data[..]) {
+                        this.decoder = None;
+                        return Poll::Ready(Some(Err(decode_error(e))));
+                    }
+                    decoder.take_output()
+                }
+                Some(Ok(BodyComponent::Trailers(trailers))) => {
+                    this.pending_trailers = Some(trailers);
+                    match this.decoder.take().unwrap().finish() {
+                        Ok(x) => x,
+                        Err(e) => return Poll::Ready(Some(Err(decode_error(e)))),
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => match this.decoder.take().unwrap().finish() {
+                    Ok(x) => x,
+                    Err(e) => return Poll::Ready(Some(Err(decode_error(e)))),
+                },
+            };
+            if !output.is_empty() {
+                return Poll::Ready(Some(Ok(BodyComponent::Data(output.into()))));
+            }
+        }
+    }
+}
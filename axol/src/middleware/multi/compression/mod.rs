@@ -0,0 +1,242 @@
+//! Middleware which compresses response bodies according to the request's `accept-encoding` header.
+//!
+//! ```ignore
+//! let router = Router::new()
+//!     .get("/", index)
+//!     .plugin("/", Compression::new().min_size(256));
+//! ```
+
+use std::{
+    io::Write,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axol_http::{
+    body::{BodyComponent, BodyStream},
+    header::HeaderMap,
+    response::Response,
+    Body, StatusCode,
+};
+use futures::{ready, Stream};
+
+use crate::{Error, Plugin, Result, Router, Wrap, WrapState};
+
+mod codec;
+pub use codec::CompressionAlgorithm;
+pub(crate) use codec::*;
+
+mod decompression;
+pub use decompression::RequestDecompression;
+
+/// Wrap that compresses response bodies with the best algorithm accepted by the client.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Compression {
+    algorithms: Vec<CompressionAlgorithm>,
+    min_size: usize,
+}
+
+impl Compression {
+    /// Create a new `Compression` with all enabled algorithms and a minimum body size of 1 KiB.
+    pub fn new() -> Self {
+        Self {
+            algorithms: CompressionAlgorithm::all(),
+            min_size: 1024,
+        }
+    }
+
+    /// Set the algorithms that may be used, in order of server preference.
+    /// The server preference is used to break ties between equal client `q` values.
+    pub fn algorithms(
+        mut self,
+        algorithms: impl IntoIterator<Item = CompressionAlgorithm>,
+    ) -> Self {
+        self.algorithms = algorithms.into_iter().collect();
+        self
+    }
+
+    /// Set the minimum body size (in bytes) for which compression is applied.
+    /// Streamed bodies without a size hint are always compressed.
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    fn negotiate(&self, accept_encoding: &HeaderMap) -> Option<CompressionAlgorithm> {
+        let mut wildcard = None;
+        let mut explicit: Vec<(CompressionAlgorithm, f32)> = vec![];
+        for value in accept_encoding.get_all("accept-encoding") {
+            for item in value.split(',') {
+                let mut parts = item.split(';');
+                let name = parts.next().unwrap_or_default().trim();
+                if name.is_empty() {
+                    continue;
+                }
+                let mut quality = 1.0f32;
+                for param in parts {
+                    let Some((key, value)) = param.split_once('=') else {
+                        continue;
+                    };
+                    if key.trim().eq_ignore_ascii_case("q") {
+                        quality = value.trim().parse().unwrap_or(0.0);
+                    }
+                }
+                if name == "*" {
+                    wildcard = Some(quality);
+                } else if let Some(algorithm) = CompressionAlgorithm::from_name(name) {
+                    explicit.push((algorithm, quality));
+                }
+            }
+        }
+        let mut best: Option<(CompressionAlgorithm, f32)> = None;
+        for algorithm in &self.algorithms {
+            let quality = explicit
+                .iter()
+                .find(|(x, _)| x == algorithm)
+                .map(|(_, q)| *q)
+                .or(wildcard)
+                .unwrap_or(0.0);
+            if quality <= 0.0 {
+                continue;
+            }
+            if best.map(|(_, q)| quality > q).unwrap_or(true) {
+                best = Some((*algorithm, quality));
+            }
+        }
+        best.map(|(x, _)| x)
+    }
+
+    fn should_compress(&self, response: &Response) -> bool {
+        if response.status.is_informational()
+            || matches!(
+                response.status,
+                StatusCode::NoContent | StatusCode::NotModified
+            )
+        {
+            return false;
+        }
+        if response.headers.contains_key("content-encoding")
+            || response.headers.contains_key("content-range")
+        {
+            return false;
+        }
+        if let Some(content_type) = response.headers.get("content-type") {
+            // grpc has its own per-message compression
+            if content_type.starts_with("application/grpc") {
+                return false;
+            }
+        }
+        match &response.body {
+            Body::Bytes(x) => x.len() >= self.min_size,
+            Body::Stream { size_hint, .. } => size_hint.map(|x| x >= self.min_size).unwrap_or(true),
+        }
+    }
+}
+
+/// Adds `accept-encoding` to `vary`, unless it's already listed.
+fn vary_accept_encoding(headers: &mut HeaderMap) {
+    let present = headers
+        .get_all("vary")
+        .flat_map(|x| x.split(','))
+        .map(str::trim)
+        .any(|x| x == "*" || x.eq_ignore_ascii_case("accept-encoding"));
+    if !present {
+        headers.append_static("vary", "accept-encoding");
+    }
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Wrap for Compression {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let algorithm = self.negotiate(state.request().headers);
+        let mut response = state.next().await?;
+        vary_accept_encoding(&mut response.headers);
+
+        let Some(algorithm) = algorithm else {
+            return Ok(response);
+        };
+        if !self.should_compress(&response) {
+            return Ok(response);
+        }
+
+        response.body = match std::mem::take(&mut response.body) {
+            Body::Bytes(bytes) => {
+                Body::Bytes(compress(algorithm, &bytes).map_err(Error::internal)?)
+            }
+            body => Body::Stream {
+                size_hint: None,
+                stream: Box::pin(CompressStream {
+                    inner: body.into_stream(),
+                    encoder: Some(Encoder::new(algorithm)),
+                    pending_trailers: None,
+                }),
+            },
+        };
+        response.headers.remove("content-length");
+        response
+            .headers
+            .insert_static("content-encoding", algorithm.as_str());
+        Ok(response)
+    }
+}
+
+impl Plugin for Compression {
+    fn apply(self, router: Router, path: &str) -> Router {
+        router.wrap(path, self)
+    }
+}
+
+/// Compresses a body stream chunk-by-chunk, flushing the encoder after every chunk.
+struct CompressStream {
+    inner: BodyStream,
+    encoder: Option<Encoder>,
+    pending_trailers: Option<HeaderMap>,
+}
+
+impl Stream for CompressStream {
+    type Item = Result<BodyComponent, anyhow::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            let Some(encoder) = this.encoder.as_mut() else {
+                return Poll::Ready(
+                    this.pending_trailers
+                        .take()
+                        .map(|x| Ok(BodyComponent::Trailers(x))),
+                );
+            };
+            let output = match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(BodyComponent::Data(data))) => {
+                    if let Err(e) = encoder.write_all(&data[..]).and_then(|_| encoder.flush()) {
+                        this.encoder = None;
+                        return Poll::Ready(Some(Err(e.into())));
+                    }
+                    encoder.take_output()
+                }
+                Some(Ok(BodyComponent::Trailers(trailers))) => {
+                    this.pending_trailers = Some(trailers);
+                    match this.encoder.take().unwrap().finish() {
+                        Ok(x) => x,
+                        Err(e) => return Poll::Ready(Some(Err(e.into()))),
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => match this.encoder.take().unwrap().finish() {
+                    Ok(x) => x,
+                    Err(e) => return Poll::Ready(Some(Err(e.into()))),
+                },
+            };
+            if !output.is_empty() {
+                return Poll::Ready(Some(Ok(BodyComponent::Data(output.into()))));
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use axol_http::response::Response;
+use tokio::sync::Semaphore;
+
+use crate::{Error, Result, Wrap, WrapState};
+
+/// Caps the number of requests concurrently executing the wrapped routes.
+/// Clones share the same permits, so one `ConcurrencyLimit` can be registered on several paths to give them a shared cap.
+///
+/// When saturated, requests either wait for a permit (the default) or are rejected immediately with `503 Service Unavailable`.
+/// The permit is released once the response is produced, so streaming response bodies are not counted.
+///
+/// Wraps registered first on the same path run outermost, so a `Timeout` registered before the limit also covers time spent queued:
+/// ```ignore
+/// let limit = ConcurrencyLimit::new(16);
+/// let router = Router::new()
+///     .get("/api", handler)
+///     .wrap("/api", Timeout(Duration::from_secs(5)))
+///     .wrap("/api", limit.clone());
+/// // for metrics
+/// let in_flight = limit.in_flight();
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ConcurrencyLimit {
+    semaphore: Arc<Semaphore>,
+    permits: usize,
+    reject_when_saturated: bool,
+}
+
+impl ConcurrencyLimit {
+    /// Create a new `ConcurrencyLimit` allowing `permits` concurrent requests, queueing any past that.
+    pub fn new(permits: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(permits)),
+            permits,
+            reject_when_saturated: false,
+        }
+    }
+
+    /// If `true`, requests arriving while all permits are taken are rejected with `503 Service Unavailable` instead of queued.
+    pub fn reject_when_saturated(mut self, reject_when_saturated: bool) -> Self {
+        self.reject_when_saturated = reject_when_saturated;
+        self
+    }
+
+    /// The number of requests currently holding a permit.
+    pub fn in_flight(&self) -> usize {
+        self.permits - self.semaphore.available_permits()
+    }
+
+    /// The maximum number of concurrent requests.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+}
+
+#[async_trait::async_trait]
+impl Wrap for ConcurrencyLimit {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let permit = if self.reject_when_saturated {
+            self.semaphore
+                .try_acquire()
+                .map_err(|_| Error::ServiceUnavailable)?
+        } else {
+            // the semaphore is never closed
+            self.semaphore
+                .acquire()
+                .await
+                .map_err(|_| Error::ServiceUnavailable)?
+        };
+        let response = state.next().await;
+        drop(permit);
+        response
+    }
+}
@@ -3,9 +3,14 @@ use std::{fmt, sync::Arc};
 use axol_http::{
     header::TypedHeader, request::RequestPartsRef, typed_headers::AccessControlAllowOrigin,
 };
+use futures::future::BoxFuture;
 
 use super::Any;
 
+type AsyncPredicateFn = Arc<
+    dyn for<'a> Fn(&'a str, RequestPartsRef<'a>) -> BoxFuture<'a, bool> + Send + Sync + 'static,
+>;
+
 /// Holds configuration for how to set the [`Access-Control-Allow-Origin`][mdn] header.
 ///
 /// See [`CorsLayer::allow_origin`] for more details.
@@ -18,6 +23,9 @@ pub enum AllowOrigin {
     Const(String),
     List(Vec<String>),
     Predicate(Arc<dyn for<'a> Fn(&'a str, RequestPartsRef<'a>) -> bool + Send + Sync + 'static>),
+    AsyncPredicate(AsyncPredicateFn),
+    #[cfg(feature = "cors-regex")]
+    Patterns(Vec<regex::Regex>),
 }
 
 impl Default for AllowOrigin {
@@ -77,6 +85,47 @@ impl AllowOrigin {
         AllowOrigin::Predicate(Arc::new(f))
     }
 
+    /// Set the allowed origins from regular expressions, i.e. `https://[a-z0-9-]+\.example\.com` for any subdomain of `example.com`.
+    ///
+    /// Each pattern must match the whole origin, anchors are added as needed.
+    /// Patterns are compiled once up front, so matching stays cheap per request.
+    ///
+    /// See [`CorsLayer::allow_origin`] for more details.
+    ///
+    /// [`CorsLayer::allow_origin`]: super::CorsLayer::allow_origin
+    #[cfg(feature = "cors-regex")]
+    pub fn patterns<I: IntoIterator<Item = regex::Regex>>(patterns: I) -> Self {
+        let patterns = patterns
+            .into_iter()
+            .map(|x| {
+                // a partial match would let `https://example.com.evil.com` through
+                regex::Regex::new(&format!("^(?:{})$", x.as_str()))
+                    .expect("anchoring a valid pattern produced an invalid one")
+            })
+            .collect();
+        Self::Patterns(patterns)
+    }
+
+    /// Set the allowed origins from an async predicate, i.e. to look up origins in a database.
+    /// The predicate runs for every CORS request, prefer `predicate` when the check needs no I/O.
+    ///
+    /// ```ignore
+    /// AllowOrigin::async_predicate(move |origin, _| {
+    ///     let db = db.clone();
+    ///     async move { db.is_tenant_origin(origin).await }.boxed()
+    /// })
+    /// ```
+    ///
+    /// See [`CorsLayer::allow_origin`] for more details.
+    ///
+    /// [`CorsLayer::allow_origin`]: super::CorsLayer::allow_origin
+    pub fn async_predicate<F>(f: F) -> Self
+    where
+        F: for<'a> Fn(&'a str, RequestPartsRef<'a>) -> BoxFuture<'a, bool> + Send + Sync + 'static,
+    {
+        AllowOrigin::AsyncPredicate(Arc::new(f))
+    }
+
     /// Allow any origin, by mirroring the request origin
     ///
     /// This is equivalent to
@@ -89,12 +138,18 @@ impl AllowOrigin {
         Self::predicate(|_, _| true)
     }
 
+    /// Only `*` is a wildcard, patterns and predicates always send a concrete origin.
     #[allow(clippy::borrow_interior_mutable_const)]
     pub(super) fn is_wildcard(&self) -> bool {
         matches!(self, AllowOrigin::Const(x) if x == "*")
     }
 
-    pub(super) fn to_header(
+    /// Whether the header value depends on the request origin.
+    pub(super) fn is_dynamic(&self) -> bool {
+        !matches!(self, AllowOrigin::Const(_))
+    }
+
+    pub(super) async fn to_header(
         &self,
         origin: Option<&str>,
         parts: RequestPartsRef<'_>,
@@ -107,6 +162,16 @@ impl AllowOrigin {
             Self::Predicate(predicate) => origin
                 .filter(|origin| predicate(origin, parts))
                 .map(|x| AccessControlAllowOrigin::decode(x).unwrap()),
+            Self::AsyncPredicate(predicate) => {
+                let origin = origin?;
+                predicate(origin, parts)
+                    .await
+                    .then(|| AccessControlAllowOrigin::decode(origin).unwrap())
+            }
+            #[cfg(feature = "cors-regex")]
+            Self::Patterns(patterns) => origin
+                .filter(|origin| patterns.iter().any(|x| x.is_match(origin)))
+                .map(|x| AccessControlAllowOrigin::decode(x).unwrap()),
         }
     }
 }
@@ -123,6 +188,9 @@ impl fmt::Debug for AllowOrigin {
             Self::Const(arg0) => f.debug_tuple("Const").field(arg0).finish(),
             Self::List(arg0) => f.debug_tuple("List").field(arg0).finish(),
             Self::Predicate(_) => f.debug_tuple("Predicate").finish(),
+            Self::AsyncPredicate(_) => f.debug_tuple("AsyncPredicate").finish(),
+            #[cfg(feature = "cors-regex")]
+            Self::Patterns(arg0) => f.debug_tuple("Patterns").field(arg0).finish(),
         }
     }
 }
@@ -432,7 +432,7 @@ impl Cors {
     ) -> Result<Option<HeaderMap>> {
         let origin = parts.headers.get("origin");
         let mut headers = HeaderMap::new();
-        if let Some(header) = cors.allow_origin.to_header(origin, parts.as_ref()) {
+        if let Some(header) = cors.allow_origin.to_header(origin, parts.as_ref()).await {
             headers.append_typed(&header);
         }
         if let Some(header) = cors.allow_credentials.to_header(origin, parts.as_ref()) {
@@ -444,6 +444,9 @@ impl Cors {
         for value in cors.vary.values() {
             headers.append("vary", value);
         }
+        if cors.allow_origin.is_dynamic() {
+            vary_origin(&mut headers);
+        }
         if let Some(header) = cors.allow_methods.to_header(parts.as_ref()) {
             headers.append_typed(&header);
         }
@@ -465,7 +468,7 @@ impl Cors {
     ) -> Response {
         let origin = parts.headers.get("origin");
 
-        if let Some(header) = cors.allow_origin.to_header(origin, parts.as_ref()) {
+        if let Some(header) = cors.allow_origin.to_header(origin, parts.as_ref()).await {
             response.headers.append_typed(&header);
         }
         if let Some(header) = cors.allow_credentials.to_header(origin, parts.as_ref()) {
@@ -477,6 +480,9 @@ impl Cors {
         for value in cors.vary.values() {
             response.headers.append("vary", value);
         }
+        if cors.allow_origin.is_dynamic() {
+            vary_origin(&mut response.headers);
+        }
         if let Some(header) = cors.expose_headers.to_header(parts.as_ref()) {
             response.headers.append_typed(&header);
         }
@@ -495,6 +501,19 @@ impl Plugin for Cors {
     }
 }
 
+/// A dynamic `access-control-allow-origin` differs between origins, so shared caches must key responses on the origin,
+/// even if `vary` was configured without it.
+fn vary_origin(headers: &mut HeaderMap) {
+    let present = headers
+        .get_all("vary")
+        .flat_map(|x| x.split(','))
+        .map(str::trim)
+        .any(|x| x == "*" || x.eq_ignore_ascii_case("origin"));
+    if !present {
+        headers.append("vary", "origin");
+    }
+}
+
 fn ensure_usable_cors_rules(layer: &Cors) {
     if layer.allow_credentials.is_true() {
         assert!(
@@ -8,6 +8,11 @@ use log::Level;
 
 use crate::{ConnectInfo, LateResponseHook, Plugin, RequestHook, Result, Router};
 
+/// Request extension making `Logger`, `AccessLog` and `Trace` ignore requests, i.e. for noisy health checks:
+/// `router.extension("/healthz", SkipLogging)`.
+#[derive(Debug, Clone, Copy)]
+pub struct SkipLogging;
+
 #[derive(Clone)]
 pub struct Logger {
     pub default_log_level: Level,
@@ -38,6 +43,9 @@ impl RequestHook for Logger {
 #[async_trait::async_trait]
 impl LateResponseHook for Logger {
     async fn handle_response<'a>(&self, request: RequestPartsRef<'a>, response: &mut Response) {
+        if request.extensions.get::<SkipLogging>().is_some() {
+            return;
+        }
         let Some(log_info) = request.extensions.get::<LogInfo>() else {
             // we got inserted part-way through?
             return;
@@ -0,0 +1,145 @@
+use std::time::Instant;
+
+use axol_http::{request::RequestPartsRef, response::Response, StatusCode};
+use metrics::Label;
+
+use crate::{MatchedPath, Result, Wrap, WrapState};
+
+/// Records per-request metrics through the [`metrics`](https://docs.rs/metrics) facade, for use with i.e. a Prometheus exporter.
+///
+/// With the default prefix of `axol`, the recorded metrics are:
+/// * `axol_requests_total`, a counter of completed requests
+/// * `axol_requests_in_flight`, a gauge of requests currently being handled
+/// * `axol_request_duration_seconds`, a histogram of time taken to produce a response (not including streaming the body)
+///
+/// Requests are labeled by `method`, `route` (the matched route pattern, i.e. `/users/:id`, never the raw path),
+/// and `status` (the status class, i.e. `2xx`), each of which can be turned off to limit cardinality.
+/// The in-flight gauge has no `status` label. Requests that never complete, because the handler panicked or
+/// the request was cancelled (i.e. by a `Timeout` registered before this, which runs outside of it), are recorded with a status of `incomplete`.
+///
+/// ```ignore
+/// router.wrap("/", Metrics::default().method_label(false))
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Metrics {
+    requests_total: String,
+    requests_in_flight: String,
+    request_duration: String,
+    method_label: bool,
+    route_label: bool,
+    status_label: bool,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new("axol")
+    }
+}
+
+impl Metrics {
+    /// Create a new `Metrics`, with metric names prefixed by `prefix` and an underscore.
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            requests_total: format!("{prefix}_requests_total"),
+            requests_in_flight: format!("{prefix}_requests_in_flight"),
+            request_duration: format!("{prefix}_request_duration_seconds"),
+            method_label: true,
+            route_label: true,
+            status_label: true,
+        }
+    }
+
+    /// Whether to label metrics with the request method. Defaults to `true`.
+    pub fn method_label(mut self, enabled: bool) -> Self {
+        self.method_label = enabled;
+        self
+    }
+
+    /// Whether to label metrics with the matched route pattern. Defaults to `true`.
+    pub fn route_label(mut self, enabled: bool) -> Self {
+        self.route_label = enabled;
+        self
+    }
+
+    /// Whether to label metrics with the response status class. Defaults to `true`.
+    pub fn status_label(mut self, enabled: bool) -> Self {
+        self.status_label = enabled;
+        self
+    }
+
+    fn request_labels(&self, request: RequestPartsRef<'_>) -> Vec<Label> {
+        let mut labels = vec![];
+        if self.method_label {
+            labels.push(Label::new("method", request.method.as_str().to_string()));
+        }
+        if self.route_label {
+            let route = request
+                .extensions
+                .get::<MatchedPath>()
+                .map(|x| x.0.to_string())
+                .unwrap_or_else(|| "unmatched".to_string());
+            labels.push(Label::new("route", route));
+        }
+        labels
+    }
+}
+
+fn status_class(status: StatusCode) -> &'static str {
+    match status.as_u16() / 100 {
+        1 => "1xx",
+        2 => "2xx",
+        3 => "3xx",
+        4 => "4xx",
+        _ => "5xx",
+    }
+}
+
+/// Tracks a request in flight, so it's recorded even if the wrap future is dropped before completing.
+struct InFlight<'a> {
+    metrics: &'a Metrics,
+    labels: Vec<Label>,
+    start: Instant,
+    status: Option<&'static str>,
+}
+
+impl<'a> InFlight<'a> {
+    fn start(metrics: &'a Metrics, labels: Vec<Label>) -> Self {
+        metrics::gauge!(metrics.requests_in_flight.clone(), labels.clone()).increment(1.0);
+        Self {
+            metrics,
+            labels,
+            start: Instant::now(),
+            status: None,
+        }
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        let metrics = self.metrics;
+        metrics::gauge!(metrics.requests_in_flight.clone(), self.labels.clone()).decrement(1.0);
+        let mut labels = std::mem::take(&mut self.labels);
+        if metrics.status_label {
+            labels.push(Label::new("status", self.status.unwrap_or("incomplete")));
+        }
+        metrics::counter!(metrics.requests_total.clone(), labels.clone()).increment(1);
+        metrics::histogram!(metrics.request_duration.clone(), labels)
+            .record(self.start.elapsed().as_secs_f64());
+    }
+}
+
+#[async_trait::async_trait]
+impl Wrap for Metrics {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let mut in_flight = InFlight::start(self, self.request_labels(state.request()));
+        let response = state.next().await;
+        let status = match &response {
+            Ok(response) => response.status,
+            // errors without a response of their own end up as a 500
+            Err(error) => error.status().unwrap_or(StatusCode::InternalServerError),
+        };
+        in_flight.status = Some(status_class(status));
+        response
+    }
+}
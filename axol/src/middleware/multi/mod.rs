@@ -1,7 +1,35 @@
 mod logger;
 pub use logger::*;
 
+mod access_log;
+pub use access_log::*;
+
+mod timeout;
+pub use timeout::*;
+
+mod concurrency_limit;
+pub use concurrency_limit::*;
+
+mod request_id;
+pub use request_id::*;
+
+mod when;
+pub use when::*;
+
+#[cfg(feature = "session")]
+mod session;
+#[cfg(feature = "session")]
+pub use session::*;
+
 pub mod cors;
 
 #[cfg(feature = "trace")]
 pub mod trace;
+
+#[cfg(feature = "compression")]
+pub mod compression;
+
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use self::metrics::Metrics;
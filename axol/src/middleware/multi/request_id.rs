@@ -0,0 +1,99 @@
+use std::ops::Deref;
+
+use anyhow::anyhow;
+use axol_http::{
+    request::{Request, RequestPartsRef},
+    response::Response,
+};
+
+use crate::{Error, FromRequestParts, LateResponseHook, Plugin, RequestHook, Result, Router};
+
+/// Longest incoming request id that is accepted as is, longer ones are replaced.
+const MAX_INCOMING_LENGTH: usize = 128;
+
+/// Correlation id of the current request, as assigned by `RequestIdHook`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(pub String);
+
+impl Deref for RequestId {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for RequestId {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        request
+            .extensions
+            .get_cloned::<RequestId>()
+            .ok_or_else(|| Error::internal(anyhow!("missing RequestId extension")))
+    }
+}
+
+/// Assigns each request a `RequestId`, taken from the incoming `x-request-id` header or generated as a UUIDv4,
+/// and echoes it back on the response.
+///
+/// Incoming ids that are empty, overly long, or contain anything but visible ASCII are replaced.
+/// If `Trace` is registered, the id is recorded in the span's `request_id` field.
+#[derive(Debug, Clone)]
+pub struct RequestIdHook {
+    pub header_name: String,
+}
+
+impl Default for RequestIdHook {
+    fn default() -> Self {
+        Self {
+            header_name: "x-request-id".to_string(),
+        }
+    }
+}
+
+impl RequestIdHook {
+    pub fn new(header_name: impl Into<String>) -> Self {
+        Self {
+            header_name: header_name.into(),
+        }
+    }
+}
+
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_INCOMING_LENGTH
+        && value.bytes().all(|x| x.is_ascii_graphic())
+}
+
+#[async_trait::async_trait]
+impl RequestHook for RequestIdHook {
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        let request_id = match request.headers.get(&self.header_name) {
+            Some(value) if is_valid_request_id(value.trim()) => value.trim().to_string(),
+            _ => uuid::Uuid::new_v4().to_string(),
+        };
+        #[cfg(feature = "trace")]
+        crate::trace::record_request_id(request.parts(), &request_id);
+        request.extensions.insert(RequestId(request_id));
+        Ok(None)
+    }
+}
+
+#[async_trait::async_trait]
+impl LateResponseHook for RequestIdHook {
+    async fn handle_response<'a>(&self, request: RequestPartsRef<'a>, response: &mut Response) {
+        let Some(request_id) = request.extensions.get_cloned::<RequestId>() else {
+            // the request never reached our request hook, i.e. a routing error
+            return;
+        };
+        response.headers.insert(&self.header_name, request_id.0);
+    }
+}
+
+impl Plugin for RequestIdHook {
+    fn apply(self, router: Router, path: &str) -> Router {
+        router
+            .request_hook_direct(path, self.clone())
+            .late_response_hook_direct(path, self)
+    }
+}
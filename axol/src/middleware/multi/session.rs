@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use axol_http::{
+    request::{Request, RequestPartsRef},
+    response::Response,
+};
+use log::error;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::{Mutex as AsyncMutex, MutexGuard};
+
+use crate::{
+    Cookie, Error, FromRequestParts, IntoResponseParts, Key, LateResponseHook, Plugin, RequestHook,
+    Result, Router, SameSite, SignedCookieJar,
+};
+
+/// The values of a session, serialized as JSON.
+pub type SessionData = HashMap<String, serde_json::Value>;
+
+/// Persists session data by session id, for `Sessions`.
+#[async_trait::async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    /// Returns `None` if there is no session with this id, i.e. it expired.
+    async fn load(&self, id: &str) -> Result<Option<SessionData>>;
+
+    async fn store(&self, id: &str, data: &SessionData) -> Result<()>;
+
+    async fn delete(&self, id: &str) -> Result<()>;
+}
+
+/// Keeps sessions in process memory. Sessions are lost on restart and are not shared between instances.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    sessions: Arc<Mutex<HashMap<String, SessionData>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionStore for MemoryStore {
+    async fn load(&self, id: &str) -> Result<Option<SessionData>> {
+        Ok(self.sessions.lock().unwrap().get(id).cloned())
+    }
+
+    async fn store(&self, id: &str, data: &SessionData) -> Result<()> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), data.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.sessions.lock().unwrap().remove(id);
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct SessionState {
+    id: Option<String>,
+    data: SessionData,
+    loaded: bool,
+    changed: bool,
+    destroyed: bool,
+}
+
+/// Extractor for the session of the current request, see `Sessions`.
+///
+/// The session is loaded from the store on first access. Changes are persisted, and the session cookie set,
+/// after the response is produced.
+#[derive(Clone)]
+pub struct Session {
+    store: Arc<dyn SessionStore>,
+    state: Arc<AsyncMutex<SessionState>>,
+}
+
+impl Session {
+    async fn loaded(&self) -> Result<MutexGuard<'_, SessionState>> {
+        let mut state = self.state.lock().await;
+        if !state.loaded {
+            if let Some(id) = state.id.clone() {
+                match self.store.load(&id).await? {
+                    Some(data) => state.data = data,
+                    // never reuse unknown ids, so clients can't pick their own
+                    None => state.id = None,
+                }
+            }
+            state.loaded = true;
+        }
+        Ok(state)
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let state = self.loaded().await?;
+        state
+            .data
+            .get(key)
+            .map(|x| serde_json::from_value(x.clone()))
+            .transpose()
+            .map_err(Error::internal)
+    }
+
+    pub async fn insert<T: Serialize>(&self, key: impl Into<String>, value: T) -> Result<()> {
+        let value = serde_json::to_value(value).map_err(Error::internal)?;
+        let mut state = self.loaded().await?;
+        state.data.insert(key.into(), value);
+        state.changed = true;
+        Ok(())
+    }
+
+    pub async fn remove(&self, key: &str) -> Result<()> {
+        let mut state = self.loaded().await?;
+        if state.data.remove(key).is_some() {
+            state.changed = true;
+        }
+        Ok(())
+    }
+
+    /// Deletes the session from the store and removes the session cookie.
+    /// Values inserted afterwards start a new session with a new id, i.e. to rotate the session id on login.
+    pub async fn destroy(&self) -> Result<()> {
+        let mut state = self.loaded().await?;
+        state.data.clear();
+        state.changed = false;
+        state.destroyed = true;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for Session {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        request.extensions.get_cloned::<Session>().ok_or_else(|| {
+            Error::internal(anyhow!(
+                "missing Session extension, register the `Sessions` plugin"
+            ))
+        })
+    }
+}
+
+/// Provides the `Session` extractor, identifying sessions by a session id in a cookie signed with `key`.
+#[derive(Clone)]
+pub struct Sessions {
+    store: Arc<dyn SessionStore>,
+    key: Key,
+    cookie_name: String,
+    cookie_path: String,
+    max_age: Option<Duration>,
+    secure: bool,
+}
+
+impl Sessions {
+    pub fn new(store: impl SessionStore, key: Key) -> Self {
+        Self {
+            store: Arc::new(store),
+            key,
+            cookie_name: "session".to_string(),
+            cookie_path: "/".to_string(),
+            max_age: None,
+            secure: true,
+        }
+    }
+
+    /// Name of the session cookie. Defaults to `session`.
+    pub fn cookie_name(mut self, cookie_name: impl Into<String>) -> Self {
+        self.cookie_name = cookie_name.into();
+        self
+    }
+
+    /// Path of the session cookie. Defaults to `/`.
+    pub fn cookie_path(mut self, cookie_path: impl Into<String>) -> Self {
+        self.cookie_path = cookie_path.into();
+        self
+    }
+
+    /// `Max-Age` of the session cookie, refreshed whenever the session changes.
+    /// Defaults to none, making the cookie last until the browser is closed.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Whether the session cookie is only sent over HTTPS. Defaults to `true`.
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    fn cookie(&self, value: String) -> Cookie<'static> {
+        let mut cookie = Cookie::build(self.cookie_name.clone(), value)
+            .path(self.cookie_path.clone())
+            .http_only(true)
+            .secure(self.secure)
+            .same_site(SameSite::Lax)
+            .finish();
+        if let Some(max_age) = self.max_age {
+            cookie.set_max_age(cookie::time::Duration::seconds(max_age.as_secs() as i64));
+        }
+        cookie
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHook for Sessions {
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        let id = SignedCookieJar::from_headers(&request.headers, self.key.clone())
+            .get(&self.cookie_name)
+            .map(|x| x.value().to_string());
+        request.extensions.insert(Session {
+            store: self.store.clone(),
+            state: Arc::new(AsyncMutex::new(SessionState {
+                id,
+                ..Default::default()
+            })),
+        });
+        Ok(None)
+    }
+}
+
+#[async_trait::async_trait]
+impl LateResponseHook for Sessions {
+    async fn handle_response<'a>(&self, request: RequestPartsRef<'a>, response: &mut Response) {
+        let Some(session) = request.extensions.get_cloned::<Session>() else {
+            // the request never reached our request hook, i.e. a routing error
+            return;
+        };
+        let mut state = session.state.lock().await;
+        let mut jar = SignedCookieJar::from_headers(request.headers, self.key.clone());
+        if state.destroyed {
+            if let Some(id) = state.id.take() {
+                if let Err(e) = self.store.delete(&id).await {
+                    error!("failed to delete session: {e}");
+                }
+            }
+            jar = jar.remove(self.cookie(String::new()));
+        }
+        if state.changed {
+            let id = state
+                .id
+                .get_or_insert_with(|| uuid::Uuid::new_v4().to_string())
+                .clone();
+            if let Err(e) = self.store.store(&id, &state.data).await {
+                error!("failed to store session: {e}");
+                return;
+            }
+            jar = jar.add(self.cookie(id));
+        }
+        if let Err(e) = jar.into_response_parts(&mut response.parts_mut()) {
+            error!("failed to set session cookie: {e}");
+        }
+    }
+}
+
+impl Plugin for Sessions {
+    fn apply(self, router: Router, path: &str) -> Router {
+        router
+            .request_hook_direct(path, self.clone())
+            .late_response_hook_direct(path, self)
+    }
+}
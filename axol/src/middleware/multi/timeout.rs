@@ -0,0 +1,102 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use axol_http::{
+    body::{BodyComponent, BodyStream},
+    response::Response,
+    Body,
+};
+use futures::Stream;
+use tokio::time::{Instant, Sleep};
+
+use crate::{Cancellation, Error, Result, Wrap, WrapState};
+
+/// Fails a request with `408 Request Timeout` if the inner wraps and handler don't produce a response within the given duration.
+/// The response body is not covered, see `TimeoutWithBody` for that.
+///
+/// On timeout the handler future is dropped, along with the request body if the handler had taken it,
+/// and the request's `Cancellation` is cancelled.
+///
+/// ```ignore
+/// router.wrap("/slow", Timeout(Duration::from_secs(5)))
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Timeout(pub Duration);
+
+#[async_trait::async_trait]
+impl Wrap for Timeout {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let cancellation = state.request().extensions.get_cloned::<Cancellation>();
+        match tokio::time::timeout(self.0, state.next()).await {
+            Ok(response) => response,
+            Err(_) => {
+                if let Some(cancellation) = cancellation {
+                    cancellation.cancel();
+                }
+                Err(Error::RequestTimeout)
+            }
+        }
+    }
+}
+
+/// Like `Timeout`, but the deadline also covers streaming the response body.
+/// If the deadline passes while the body is streaming, the headers have already been sent, so the body stream is terminated with an error instead.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutWithBody(pub Duration);
+
+#[async_trait::async_trait]
+impl Wrap for TimeoutWithBody {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let deadline = Instant::now() + self.0;
+        let cancellation = state.request().extensions.get_cloned::<Cancellation>();
+        let mut response = match tokio::time::timeout_at(deadline, state.next()).await {
+            Ok(response) => response?,
+            Err(_) => {
+                if let Some(cancellation) = cancellation {
+                    cancellation.cancel();
+                }
+                return Err(Error::RequestTimeout);
+            }
+        };
+        response.body = match std::mem::take(&mut response.body) {
+            Body::Stream { size_hint, stream } => Body::Stream {
+                size_hint,
+                stream: Box::pin(DeadlineStream {
+                    inner: Some(stream),
+                    sleep: Box::pin(tokio::time::sleep_until(deadline)),
+                }),
+            },
+            body => body,
+        };
+        Ok(response)
+    }
+}
+
+struct DeadlineStream {
+    inner: Option<BodyStream>,
+    sleep: Pin<Box<Sleep>>,
+}
+
+impl Stream for DeadlineStream {
+    type Item = Result<BodyComponent, anyhow::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(inner) = self.inner.as_mut() else {
+            return Poll::Ready(None);
+        };
+        if let Poll::Ready(item) = inner.as_mut().poll_next(cx) {
+            return Poll::Ready(item);
+        }
+        if self.sleep.as_mut().poll(cx).is_ready() {
+            // drop the inner body now rather than when the connection is torn down
+            self.inner = None;
+            return Poll::Ready(Some(Err(anyhow!("response body timed out"))));
+        }
+        Poll::Pending
+    }
+}
@@ -7,8 +7,8 @@ use opentelemetry::{StringValue, Value};
 use tracing::{field::Empty, Instrument, Level, Span};
 
 use crate::{
-    trace::body::TraceBody, ConnectInfo, LateResponseHook, MatchedPath, Plugin, Result, Router,
-    Wrap, WrapState,
+    request_host, trace::body::TraceBody, ConnectInfo, LateResponseHook, MatchedPath, Plugin,
+    RequestId, Result, Router, Scheme, SkipLogging, Wrap, WrapState, FORWARDED_PROTO,
 };
 use tracing_opentelemetry::{OpenTelemetrySpanExt};
 
@@ -89,21 +89,31 @@ pub fn http_flavor(version: Version) -> Cow<'static, str> {
     }
 }
 
+/// Records `request_id` in the span of the request, if it is being traced.
+pub(crate) fn record_request_id(request: RequestPartsRef<'_>, request_id: &str) {
+    if let Some(info) = request.extensions.get::<TraceInfo>() {
+        info.span.record("request_id", request_id);
+    }
+}
+
 impl Trace {
     fn make_span(&self, request: RequestPartsRef<'_>) -> Span {
-        let host = request
-            .headers
-            .get("host")
-            .or(request.uri.host())
-            .unwrap_or_default();
+        let host = request_host(request).unwrap_or_default();
         let port = request.uri.port().map(|x| x.as_u16());
         let connect_info = request
             .extensions
             .get::<ConnectInfo>()
             .map(|x| x.0.ip().to_string());
         let user_agent = request.headers.get("user-agent");
-        let scheme = request.uri.scheme().map(|x| x.as_str());
+        let scheme = Scheme::detect(request, FORWARDED_PROTO).as_str();
         let route = request.extensions.get::<MatchedPath>().map(|x| &**x.0);
+        #[cfg(feature = "tls")]
+        let negotiated_protocol = request
+            .extensions
+            .get::<crate::NegotiatedProtocol>()
+            .map(|x| &*x.0);
+        #[cfg(not(feature = "tls"))]
+        let negotiated_protocol: Option<&str> = None;
         let name = format!("{} {}", request.method, route.unwrap_or_default());
         let span = tracing::info_span!(
             target: "otel::tracing",
@@ -118,6 +128,7 @@ impl Trace {
             url.path = request.uri.path(),
             url.query = request.uri.query(),
             url.scheme = scheme,
+            tls.next_protocol = negotiated_protocol,
             otel.name = name,
             otel.kind = ?opentelemetry_api::trace::SpanKind::Server,
             http.response.status_code = Empty, // to set on response
@@ -161,7 +172,27 @@ impl Trace {
 #[async_trait::async_trait]
 impl Wrap for Trace {
     async fn wrap(&self, mut state: WrapState<'_>) -> Result<Response> {
+        if state.request().extensions.get::<SkipLogging>().is_some() {
+            return state.next().await;
+        }
         let span = self.make_span(state.request());
+        let request = state.request();
+        // `RequestIdHook` records its id later if it runs after us, fall back to the raw header until then
+        let request_id = request
+            .extensions
+            .get_cloned::<RequestId>()
+            .map(|x| x.0)
+            .or_else(|| request.headers.get("x-request-id").map(str::to_string));
+        if let Some(request_id) = request_id {
+            span.record("request_id", request_id.as_str());
+        }
+        if let Some(length) = request
+            .headers
+            .get("content-length")
+            .and_then(|x| x.trim().parse::<u64>().ok())
+        {
+            span.record("http.request.body.size", length);
+        }
         state
             .request()
             .extensions
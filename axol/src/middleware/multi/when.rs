@@ -0,0 +1,33 @@
+use axol_http::{request::RequestPartsRef, response::Response};
+
+use crate::{Result, Wrap, WrapState};
+
+/// Runs the wrap `W` only for requests matching the predicate, other requests skip it.
+/// Useful when the condition is header based rather than path based.
+///
+/// ```ignore
+/// router.wrap(
+///     "/",
+///     When(
+///         |request: RequestPartsRef<'_>| request.headers.get("accept") == Some("application/json"),
+///         Timeout(Duration::from_secs(5)),
+///     ),
+/// )
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct When<F, W>(pub F, pub W);
+
+#[async_trait::async_trait]
+impl<F, W> Wrap for When<F, W>
+where
+    F: for<'a> Fn(RequestPartsRef<'a>) -> bool + Send + Sync + 'static,
+    W: Wrap,
+{
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        if (self.0)(state.request()) {
+            self.1.wrap(state).await
+        } else {
+            state.next().await
+        }
+    }
+}
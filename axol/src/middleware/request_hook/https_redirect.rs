@@ -0,0 +1,95 @@
+use axol_http::{request::Request, response::Response, Uri};
+
+use crate::{
+    Error, Host, IntoResponse, Redirect, RedirectMode, RequestHook, Result, Scheme, FORWARDED_PROTO,
+};
+
+/// Redirects plain HTTP requests to their `https://` equivalent.
+///
+/// Requests count as secure if they were accepted via `TlsIncoming`, have an `https` URI scheme,
+/// or carry `x-forwarded-proto: https` from a TLS-terminating proxy.
+/// Clients sending the forwarded header themselves only skip their own redirect, so it is trusted unconditionally.
+///
+/// ```ignore
+/// router.request_hook_direct("/", HttpsRedirect::new().skip("/health"))
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpsRedirect {
+    mode: RedirectMode,
+    https_port: Option<u16>,
+    skip: Vec<String>,
+    forwarded_proto_header: String,
+}
+
+impl Default for HttpsRedirect {
+    fn default() -> Self {
+        Self {
+            mode: RedirectMode::PermanentRedirect,
+            https_port: None,
+            skip: vec![],
+            forwarded_proto_header: FORWARDED_PROTO.to_string(),
+        }
+    }
+}
+
+impl HttpsRedirect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redirect status, i.e. `RedirectMode::MovedPermanently` for old clients that don't know `308`.
+    /// Defaults to `RedirectMode::PermanentRedirect`, which keeps the method and body.
+    pub fn mode(mut self, mode: RedirectMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Port to redirect to, if HTTPS isn't served on `443`.
+    pub fn https_port(mut self, https_port: u16) -> Self {
+        self.https_port = Some(https_port);
+        self
+    }
+
+    /// Don't redirect requests for this exact path, i.e. health checks by a load balancer.
+    pub fn skip(mut self, path: impl Into<String>) -> Self {
+        self.skip.push(path.into());
+        self
+    }
+
+    /// Header a TLS-terminating proxy reports the original scheme in. Defaults to `x-forwarded-proto`.
+    pub fn forwarded_proto_header(mut self, header: impl Into<String>) -> Self {
+        self.forwarded_proto_header = header.into();
+        self
+    }
+
+    fn is_secure(&self, request: &Request) -> bool {
+        Scheme::detect(request.parts(), &self.forwarded_proto_header) == Scheme::Https
+    }
+
+    fn location(&self, request: &Request) -> Result<Uri> {
+        let host = Host::parse(request.parts())?;
+        let host = match self.https_port {
+            Some(port) if port != 443 => format!("{}:{port}", host.hostname()),
+            _ => host.hostname().to_string(),
+        };
+        let path_and_query = request
+            .uri
+            .path_and_query()
+            .map(|x| x.as_str())
+            .unwrap_or("/");
+        format!("https://{host}{path_and_query}")
+            .parse()
+            .map_err(|_| Error::bad_request("invalid request uri"))
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHook for HttpsRedirect {
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        if self.is_secure(request) || self.skip.iter().any(|x| x == request.uri.path()) {
+            return Ok(None);
+        }
+        let location = self.location(request)?;
+        Redirect::new(self.mode, location).into_response().map(Some)
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use axol_http::{request::Request, response::Response};
+
+use crate::{Error, RequestHook, Result};
+
+/// Rejects requests with `503 Service Unavailable` and a `retry-after` header while maintenance mode is enabled.
+/// Operators flip it at runtime through a `MaintenanceHandle`, i.e. from an admin endpoint or a signal handler.
+///
+/// ```ignore
+/// let maintenance = MaintenanceMode::new().allow("/healthz");
+/// let handle = maintenance.handle();
+/// router.request_hook_direct("/", maintenance);
+/// // later
+/// handle.enable();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaintenanceMode {
+    enabled: Arc<AtomicBool>,
+    retry_after: Duration,
+    message: String,
+    allow: Vec<String>,
+}
+
+impl Default for MaintenanceMode {
+    fn default() -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(false)),
+            retry_after: Duration::from_secs(60),
+            message: "Service is down for maintenance".to_string(),
+            allow: vec![],
+        }
+    }
+}
+
+impl MaintenanceMode {
+    /// Starts out disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether maintenance mode starts out enabled.
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        self
+    }
+
+    /// Sent as `retry-after`. Defaults to 60 seconds.
+    pub fn retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Body of the `503` response.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Keep serving this exact path during maintenance, i.e. health checks by a load balancer.
+    pub fn allow(mut self, path: impl Into<String>) -> Self {
+        self.allow.push(path.into());
+        self
+    }
+
+    /// Returns a handle toggling this (and every clone of this) `MaintenanceMode`.
+    pub fn handle(&self) -> MaintenanceHandle {
+        MaintenanceHandle(self.enabled.clone())
+    }
+}
+
+/// Toggles a `MaintenanceMode` at runtime. Clones control the same `MaintenanceMode`.
+#[derive(Debug, Clone)]
+pub struct MaintenanceHandle(Arc<AtomicBool>);
+
+impl MaintenanceHandle {
+    pub fn enable(&self) {
+        self.set(true);
+    }
+
+    pub fn disable(&self) {
+        self.set(false);
+    }
+
+    pub fn set(&self, enabled: bool) {
+        self.0.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHook for MaintenanceMode {
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let path = request.uri.path();
+        if self.allow.iter().any(|x| x == path) {
+            return Ok(None);
+        }
+        Err(Error::service_unavailable_after(
+            self.retry_after,
+            self.message.clone(),
+        ))
+    }
+}
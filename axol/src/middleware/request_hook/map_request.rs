@@ -0,0 +1,32 @@
+use axol_http::{request::Request, response::Response};
+use futures::future::BoxFuture;
+
+use crate::{RequestHook, Result};
+
+/// Request hook running a closure on every request, see `Router::map_request`.
+pub struct MapRequest<F>(pub F);
+
+#[async_trait::async_trait]
+impl<F> RequestHook for MapRequest<F>
+where
+    F: Fn(&mut Request) + Send + Sync + 'static,
+{
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        (self.0)(request);
+        Ok(None)
+    }
+}
+
+/// Request hook running an async closure on every request, see `Router::map_request_async`.
+pub struct MapRequestAsync<F>(pub F);
+
+#[async_trait::async_trait]
+impl<F> RequestHook for MapRequestAsync<F>
+where
+    F: for<'a> Fn(&'a mut Request) -> BoxFuture<'a, ()> + Send + Sync + 'static,
+{
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        (self.0)(request).await;
+        Ok(None)
+    }
+}
@@ -6,11 +6,26 @@ use crate::Result;
 use crate::{FromRequestParts, IntoResponse};
 
 mod realip;
+pub use ipnet::IpNet;
 pub use realip::RealIp;
 
+mod rate_limit;
+pub use rate_limit::RateLimit;
+
+mod https_redirect;
+pub use https_redirect::HttpsRedirect;
+
+mod maintenance;
+pub use maintenance::{MaintenanceHandle, MaintenanceMode};
+
+mod map_request;
+pub use map_request::{MapRequest, MapRequestAsync};
+
 #[async_trait::async_trait]
 pub trait RequestHook: Send + Sync + 'static {
     /// Called on an inbound request
+    /// Returning `Ok(Some(response))` skips the remaining request hooks and the handler.
+    /// The response, like an `Err`, still goes through the early (or error) and late response hooks, so those can rely on seeing every response.
     async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>>;
 }
 
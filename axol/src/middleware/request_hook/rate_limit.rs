@@ -0,0 +1,139 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axol_http::{
+    request::{Request, RequestPartsRef},
+    response::Response,
+};
+
+use crate::{ConnectInfo, Error, RequestHook, Result};
+
+type KeyFn = dyn Fn(RequestPartsRef<'_>) -> String + Send + Sync;
+
+/// How often idle buckets are swept from the table
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Token-bucket rate limiter.
+///
+/// Each key gets a bucket holding up to `burst` tokens, refilled at `requests_per_second`. Each request takes one token,
+/// and requests finding an empty bucket are rejected with `429 Too Many Requests` and a `retry-after` header.
+///
+/// By default, requests are keyed by the client IP from `ConnectInfo`, so `RealIp` should be registered first when behind a proxy.
+/// Requests with no `ConnectInfo` (i.e. not from a remote connection) are not limited under the default key.
+///
+/// Clones share the same buckets.
+#[derive(Clone)]
+#[must_use]
+pub struct RateLimit {
+    requests_per_second: f64,
+    burst: f64,
+    key: Option<Arc<KeyFn>>,
+    state: Arc<Mutex<RateLimitState>>,
+}
+
+struct RateLimitState {
+    buckets: HashMap<String, Bucket>,
+    last_eviction: Instant,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_update: Instant,
+}
+
+impl fmt::Debug for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimit")
+            .field("requests_per_second", &self.requests_per_second)
+            .field("burst", &self.burst)
+            .field("custom_key", &self.key.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RateLimit {
+    /// Create a new `RateLimit` with the given sustained rate, and a burst of the same size.
+    pub fn new(requests_per_second: f64) -> Self {
+        assert!(
+            requests_per_second > 0.0,
+            "requests_per_second must be positive"
+        );
+        Self {
+            requests_per_second,
+            burst: requests_per_second.max(1.0),
+            key: None,
+            state: Arc::new(Mutex::new(RateLimitState {
+                buckets: HashMap::new(),
+                last_eviction: Instant::now(),
+            })),
+        }
+    }
+
+    /// Set the number of requests that may be made back-to-back by a key with a full bucket.
+    pub fn burst(mut self, burst: u32) -> Self {
+        assert!(burst > 0, "burst must be positive");
+        self.burst = burst as f64;
+        self
+    }
+
+    /// Override the function used to derive the rate limit key from a request.
+    pub fn key(
+        mut self,
+        key: impl Fn(RequestPartsRef<'_>) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.key = Some(Arc::new(key));
+        self
+    }
+
+    /// Attempts to take a token for `key`. Returns how long until a token is available on failure.
+    fn acquire(&self, key: String) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        if now.duration_since(state.last_eviction) >= EVICTION_INTERVAL {
+            // an idle bucket which has refilled completely is indistinguishable from a new one
+            let refill_time = Duration::from_secs_f64(self.burst / self.requests_per_second);
+            state
+                .buckets
+                .retain(|_, bucket| now.duration_since(bucket.last_update) < refill_time);
+            state.last_eviction = now;
+        }
+
+        let bucket = state.buckets.entry(key).or_insert(Bucket {
+            tokens: self.burst,
+            last_update: now,
+        });
+        let elapsed = now.duration_since(bucket.last_update).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.burst);
+        bucket.last_update = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.requests_per_second,
+            ))
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHook for RateLimit {
+    async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
+        let key = match &self.key {
+            Some(key) => key(request.parts()),
+            None => match request.extensions.get::<ConnectInfo>() {
+                Some(info) => info.ip().to_string(),
+                None => return Ok(None),
+            },
+        };
+        if let Err(retry_after) = self.acquire(key) {
+            return Err(Error::too_many_requests_after(retry_after, ()));
+        }
+        Ok(None)
+    }
+}
@@ -1,25 +1,115 @@
 use std::net::{IpAddr, SocketAddr};
 
 use axol_http::{request::Request, response::Response};
+use ipnet::IpNet;
 
-use crate::{ConnectInfo, Error, RequestHook, Result};
+use crate::{ConnectInfo, RequestHook, Result};
 
-pub struct RealIp(pub String);
+/// Replaces the `ConnectInfo` address with the client address reported by reverse proxies.
+///
+/// The forwarded header is only used if the direct peer is one of the trusted proxies, otherwise anyone could claim any address.
+/// Requests from other peers keep the socket address. No proxies are trusted by default.
+///
+/// The header is either a comma-separated list of addresses (`x-forwarded-for`, `x-real-ip`) or, if named `forwarded`,
+/// the RFC 7239 format (`forwarded: for=192.0.2.60;proto=https, for="[2001:db8::1]:4711"`).
+/// Each proxy appends the address it received the request from, so the list is walked from the right-most (closest) address,
+/// skipping trusted proxies. The first untrusted address is the client. The left-most address is only used if every hop is trusted,
+/// since it is whatever the client chose to send. Hops that aren't IP addresses (`unknown`, obfuscated identifiers) end the walk.
+///
+/// ```ignore
+/// router.request_hook_direct("/", RealIp::new("x-forwarded-for").trust_proxy("10.0.0.0/8".parse().unwrap()))
+/// ```
+#[derive(Debug, Clone)]
+pub struct RealIp {
+    header: String,
+    trusted_proxies: Vec<IpNet>,
+}
+
+impl RealIp {
+    pub fn new(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            trusted_proxies: vec![],
+        }
+    }
+
+    /// Trust peers in `network` to report client addresses, and skip them when walking the forwarded addresses.
+    pub fn trust_proxy(mut self, network: IpNet) -> Self {
+        self.trusted_proxies.push(network);
+        self
+    }
+
+    pub fn trust_proxies(mut self, networks: impl IntoIterator<Item = IpNet>) -> Self {
+        self.trusted_proxies.extend(networks);
+        self
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        // `::ffff:10.0.0.1` from a dual-stack socket should match `10.0.0.0/8`
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+            ip => ip,
+        };
+        self.trusted_proxies.iter().any(|x| x.contains(&ip))
+    }
+
+    /// The forwarded addresses from left to right, `None` for hops that aren't IP addresses.
+    fn hops(&self, request: &Request) -> Vec<Option<IpAddr>> {
+        let is_forwarded = self.header.eq_ignore_ascii_case("forwarded");
+        request
+            .headers
+            .get_all(&self.header)
+            .flat_map(|x| x.split(','))
+            .map(|hop| {
+                if is_forwarded {
+                    parse_forwarded_for(hop)
+                } else {
+                    hop.trim().parse().ok()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parses the `for` parameter of a `forwarded` element, i.e. `for="[2001:db8::1]:4711";proto=https`.
+fn parse_forwarded_for(element: &str) -> Option<IpAddr> {
+    let value = element.split(';').find_map(|pair| {
+        let (name, value) = pair.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("for")
+            .then(|| value.trim().trim_matches('"'))
+    })?;
+    if let Some(v6) = value.strip_prefix('[') {
+        return v6.split_once(']')?.0.parse().ok();
+    }
+    value
+        .parse()
+        .ok()
+        .or_else(|| value.parse::<SocketAddr>().ok().map(|x| x.ip()))
+}
 
 #[async_trait::async_trait]
 impl RequestHook for RealIp {
     async fn handle_request(&self, request: &mut Request) -> Result<Option<Response>> {
-        let Some(value) = request.headers.get(&self.0) else {
+        let Some(peer) = request.extensions.get::<ConnectInfo>().map(|x| x.0) else {
             return Ok(None);
         };
-        let connect_info = request.extensions.get::<ConnectInfo>();
-        let Some(new_ip) = value.split_once(',').map(|x| x.0).unwrap_or(value).trim().parse::<IpAddr>().ok() else {
-            return Err(Error::bad_request(format!("invalid '{}' header", self.0)));
-        };
-        request.extensions.insert(ConnectInfo(SocketAddr::new(
-            new_ip,
-            connect_info.map(|x| x.0.port()).unwrap_or_default(),
-        )));
+        if !self.is_trusted(peer.ip()) {
+            return Ok(None);
+        }
+        let mut client = peer.ip();
+        for hop in self.hops(request).into_iter().rev() {
+            let Some(hop) = hop else {
+                break;
+            };
+            client = hop;
+            if !self.is_trusted(hop) {
+                break;
+            }
+        }
+        request
+            .extensions
+            .insert(ConnectInfo(SocketAddr::new(client, peer.port())));
         Ok(None)
     }
 }
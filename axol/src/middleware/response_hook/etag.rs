@@ -0,0 +1,72 @@
+use std::fmt::Write;
+
+use axol_http::{request::RequestPartsRef, response::Response, Body, Method, StatusCode};
+use sha2::{Digest, Sha256};
+
+use crate::{LateResponseHook, Plugin, Router};
+
+/// Sets a strong `etag` header on successful buffered responses, and answers matching `if-none-match` requests with `304 Not Modified`.
+///
+/// The tag is the SHA-256 of the body. Responses which already have an `etag` keep it, but are still checked against `if-none-match`.
+/// Streamed bodies (without an existing `etag`) are skipped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ETag;
+
+impl ETag {
+    fn compute(body: &[u8]) -> String {
+        let digest = Sha256::digest(body);
+        let mut out = String::with_capacity(2 + digest.len() * 2);
+        out.push('"');
+        for byte in digest {
+            write!(&mut out, "{byte:02x}").unwrap();
+        }
+        out.push('"');
+        out
+    }
+}
+
+/// Weak comparison, as required for `if-none-match`
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(|x| x.trim())
+        .any(|x| x == "*" || x.trim_start_matches("W/") == etag)
+}
+
+#[async_trait::async_trait]
+impl LateResponseHook for ETag {
+    async fn handle_response<'a>(&self, request: RequestPartsRef<'a>, response: &mut Response) {
+        if !matches!(request.method, Method::Get | Method::Head)
+            || response.status != StatusCode::Ok
+        {
+            return;
+        }
+        let etag = match response.headers.get("etag") {
+            Some(etag) => etag.to_string(),
+            None => {
+                let Body::Bytes(body) = &response.body else {
+                    return;
+                };
+                let etag = Self::compute(body);
+                response.headers.insert("etag", etag.clone());
+                etag
+            }
+        };
+        let matched = request
+            .headers
+            .get_all("if-none-match")
+            .any(|x| etag_matches(x, &etag));
+        if matched {
+            response.status = StatusCode::NotModified;
+            response.body = Body::empty();
+            response.headers.remove("content-length");
+        }
+    }
+}
+
+impl Plugin for ETag {
+    fn apply(self, router: Router, path: &str) -> Router {
+        router.late_response_hook_direct(path, self)
+    }
+}
@@ -2,6 +2,14 @@ use crate::{FromRequestParts, Result};
 use axol_http::{request::RequestPartsRef, response::Response};
 use futures::Future;
 
+#[cfg(feature = "etag")]
+mod etag;
+#[cfg(feature = "etag")]
+pub use etag::ETag;
+
+mod security_headers;
+pub use security_headers::SecurityHeaders;
+
 #[async_trait::async_trait]
 pub trait EarlyResponseHook: Send + Sync + 'static {
     /// Called when a handler returns an Ok(response)
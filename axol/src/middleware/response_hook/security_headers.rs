@@ -0,0 +1,123 @@
+use axol_http::{request::RequestPartsRef, response::Response};
+
+use crate::{LateResponseHook, Plugin, Router};
+
+/// Sets common security headers on every response. Headers already present on a response are left alone,
+/// so handlers can override them, i.e. to allow framing a single page.
+///
+/// `SecurityHeaders::new()` sets `x-content-type-options: nosniff`, `x-frame-options: SAMEORIGIN` and `referrer-policy: strict-origin-when-cross-origin`.
+/// `strict-transport-security` and `content-security-policy` depend on the application, so they are only set if configured, or by `strict()`.
+///
+/// ```ignore
+/// router.plugin("/", SecurityHeaders::new().content_security_policy(Some("default-src 'self'")))
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct SecurityHeaders {
+    strict_transport_security: Option<String>,
+    content_type_options: Option<String>,
+    frame_options: Option<String>,
+    referrer_policy: Option<String>,
+    content_security_policy: Option<String>,
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self {
+            strict_transport_security: None,
+            content_type_options: Some("nosniff".to_string()),
+            frame_options: Some("SAMEORIGIN".to_string()),
+            referrer_policy: Some("strict-origin-when-cross-origin".to_string()),
+            content_security_policy: None,
+        }
+    }
+}
+
+impl SecurityHeaders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A locked down preset for sites served only over HTTPS that don't load anything from other origins:
+    ///
+    /// - `strict-transport-security: max-age=63072000; includeSubDomains`
+    /// - `x-content-type-options: nosniff`
+    /// - `x-frame-options: DENY`
+    /// - `referrer-policy: no-referrer`
+    /// - `content-security-policy: default-src 'self'; frame-ancestors 'none'; object-src 'none'; base-uri 'self'`
+    pub fn strict() -> Self {
+        Self::new()
+            .strict_transport_security(Some("max-age=63072000; includeSubDomains"))
+            .frame_options(Some("DENY"))
+            .referrer_policy(Some("no-referrer"))
+            .content_security_policy(Some(
+                "default-src 'self'; frame-ancestors 'none'; object-src 'none'; base-uri 'self'",
+            ))
+    }
+
+    /// Browsers ignore this header on plain HTTP responses. Once sent, browsers refuse plain HTTP to the host for `max-age` seconds.
+    pub fn strict_transport_security(mut self, value: Option<impl Into<String>>) -> Self {
+        self.strict_transport_security = value.map(Into::into);
+        self
+    }
+
+    pub fn content_type_options(mut self, value: Option<impl Into<String>>) -> Self {
+        self.content_type_options = value.map(Into::into);
+        self
+    }
+
+    /// `DENY` or `SAMEORIGIN`. Superseded by the `frame-ancestors` directive of `content-security-policy` in current browsers.
+    pub fn frame_options(mut self, value: Option<impl Into<String>>) -> Self {
+        self.frame_options = value.map(Into::into);
+        self
+    }
+
+    pub fn referrer_policy(mut self, value: Option<impl Into<String>>) -> Self {
+        self.referrer_policy = value.map(Into::into);
+        self
+    }
+
+    pub fn content_security_policy(mut self, value: Option<impl Into<String>>) -> Self {
+        self.content_security_policy = value.map(Into::into);
+        self
+    }
+
+    fn headers(&self) -> [(&'static str, Option<&str>); 5] {
+        [
+            (
+                "strict-transport-security",
+                self.strict_transport_security.as_deref(),
+            ),
+            (
+                "x-content-type-options",
+                self.content_type_options.as_deref(),
+            ),
+            ("x-frame-options", self.frame_options.as_deref()),
+            ("referrer-policy", self.referrer_policy.as_deref()),
+            (
+                "content-security-policy",
+                self.content_security_policy.as_deref(),
+            ),
+        ]
+    }
+}
+
+#[async_trait::async_trait]
+impl LateResponseHook for SecurityHeaders {
+    async fn handle_response<'a>(&self, _request: RequestPartsRef<'a>, response: &mut Response) {
+        for (name, value) in self.headers() {
+            let Some(value) = value else {
+                continue;
+            };
+            if !response.headers.contains_key(name) {
+                response.headers.insert(name, value.to_string());
+            }
+        }
+    }
+}
+
+impl Plugin for SecurityHeaders {
+    fn apply(self, router: Router, path: &str) -> Router {
+        router.late_response_hook_direct(path, self)
+    }
+}
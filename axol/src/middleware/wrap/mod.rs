@@ -62,6 +62,8 @@ impl<'a> WrapState<'a> {
     }
 }
 
+/// Middleware around the rest of the request processing, registered with `Router::wrap` or `Router::outer_wrap`.
+/// `WrapState::next` runs the inner wraps and the handler, for outer wraps also the request hooks and all regular wraps.
 #[async_trait::async_trait]
 pub trait Wrap: Send + Sync + 'static {
     async fn wrap(&self, state: WrapState<'_>) -> Result<Response>;
@@ -1,6 +1,7 @@
 use axol_http::{request::RequestPartsRef, Body};
+use bytes::Bytes;
 
-use crate::{Error, FromRequestParts, Result};
+use crate::{bidirectional::collect_configured, Error, FromRequestParts, Result};
 
 mod private {
     #[derive(Debug, Clone, Copy)]
@@ -43,17 +44,27 @@ impl<'a> FromRequest<'a> for Body {
     }
 }
 
+/// Buffers the whole body, up to `BodyConfig::limit` bytes.
 #[async_trait::async_trait]
 impl<'a> FromRequest<'a> for Vec<u8> {
-    async fn from_request(_: RequestPartsRef<'a>, body: Body) -> Result<Self> {
-        Ok(body.collect().await?)
+    async fn from_request(request: RequestPartsRef<'a>, body: Body) -> Result<Self> {
+        collect_configured(request, body).await
     }
 }
 
+/// Buffers the whole body, up to `BodyConfig::limit` bytes.
+#[async_trait::async_trait]
+impl<'a> FromRequest<'a> for Bytes {
+    async fn from_request(request: RequestPartsRef<'a>, body: Body) -> Result<Self> {
+        Ok(collect_configured(request, body).await?.into())
+    }
+}
+
+/// Buffers the whole body, up to `BodyConfig::limit` bytes. Invalid UTF-8 is rejected with `Error::BadUtf8`.
 #[async_trait::async_trait]
 impl<'a> FromRequest<'a> for String {
-    async fn from_request(_: RequestPartsRef<'a>, body: Body) -> Result<Self> {
-        String::from_utf8(body.collect().await?).map_err(|_| Error::BadUtf8)
+    async fn from_request(request: RequestPartsRef<'a>, body: Body) -> Result<Self> {
+        String::from_utf8(collect_configured(request, body).await?).map_err(|_| Error::BadUtf8)
     }
 }
 
@@ -1,4 +1,5 @@
 mod parts;
+pub(crate) use parts::urlencoded_de;
 pub use parts::*;
 
 mod from_request;
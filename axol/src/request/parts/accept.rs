@@ -0,0 +1,103 @@
+use axol_http::{mime::Mime, request::RequestPartsRef};
+
+use crate::{Error, FromRequestParts, Result};
+
+/// A media range of an `accept` header, i.e. `text/*;q=0.5`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRange {
+    pub mime: Mime,
+    pub quality: f32,
+}
+
+impl MediaRange {
+    /// `*/*` is least specific, then `type/*`, then `type/subtype`.
+    fn specificity(&self) -> u8 {
+        if self.mime.type_() == axol_http::mime::STAR {
+            0
+        } else if self.mime.subtype() == axol_http::mime::STAR {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Whether `mime` is in this media range. Parameters are not compared.
+    pub fn matches(&self, mime: &Mime) -> bool {
+        match self.specificity() {
+            0 => true,
+            1 => self.mime.type_() == mime.type_(),
+            _ => self.mime.type_() == mime.type_() && self.mime.subtype() == mime.subtype(),
+        }
+    }
+}
+
+/// The media ranges of the `accept` header, sorted by quality then specificity, most preferred first.
+/// Invalid media ranges are skipped. A request without an `accept` header accepts anything, as `*/*`.
+#[derive(Debug, Clone)]
+pub struct Accept(pub Vec<MediaRange>);
+
+impl Accept {
+    /// Selects the offered media type with the highest quality, per RFC 7231 section 5.3.2.
+    /// Each offered type takes the quality of the most specific media range matching it.
+    /// Ties go to the earliest offered type. Returns `None` if nothing offered is acceptable.
+    pub fn preferred<'b>(&self, offered: &[&'b str]) -> Option<&'b str> {
+        let mut best: Option<(&'b str, f32)> = None;
+        for &offer in offered {
+            let Ok(mime) = offer.parse::<Mime>() else {
+                continue;
+            };
+            let quality = self
+                .0
+                .iter()
+                .filter(|x| x.matches(&mime))
+                .max_by_key(|x| x.specificity())
+                .map(|x| x.quality)
+                .unwrap_or(0.0);
+            if quality <= 0.0 {
+                continue;
+            }
+            if best.map(|(_, q)| quality > q).unwrap_or(true) {
+                best = Some((offer, quality));
+            }
+        }
+        best.map(|(x, _)| x)
+    }
+
+    /// Same as `preferred`, but returns `Error::NotAcceptable` if nothing offered is acceptable.
+    pub fn negotiate<'b>(&self, offered: &[&'b str]) -> Result<&'b str> {
+        self.preferred(offered).ok_or(Error::NotAcceptable)
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for Accept {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        let mut ranges = vec![];
+        let mut present = false;
+        for value in request.headers.get_all("accept") {
+            present = true;
+            for item in value.split(',') {
+                let Ok(mime) = item.trim().parse::<Mime>() else {
+                    continue;
+                };
+                let quality = mime
+                    .get_param("q")
+                    .and_then(|x| x.as_str().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                ranges.push(MediaRange { mime, quality });
+            }
+        }
+        if !present {
+            ranges.push(MediaRange {
+                mime: axol_http::mime::STAR_STAR,
+                quality: 1.0,
+            });
+        }
+        ranges.sort_by(|a, b| {
+            b.quality
+                .total_cmp(&a.quality)
+                .then_with(|| b.specificity().cmp(&a.specificity()))
+        });
+        Ok(Accept(ranges))
+    }
+}
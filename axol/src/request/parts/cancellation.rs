@@ -0,0 +1,46 @@
+use std::ops::Deref;
+
+use anyhow::anyhow;
+use axol_http::request::RequestPartsRef;
+pub use tokio_util::sync::CancellationToken;
+
+use crate::{Error, FromRequestParts, Result};
+
+/// Cancelled when the server gives up on a request: the client disconnected before the response was produced
+/// or while a streaming response body was being sent, or a `Timeout`, `TimeoutWithBody` or `GrpcDeadlines` wrap ran out of time.
+///
+/// The handler future (or the response body) is dropped in either case, so this is mostly useful for work outliving it,
+/// i.e. spawned tasks or database queries that should be aborted as well:
+/// ```ignore
+/// async fn report(cancellation: Cancellation) -> Result<String> {
+///     let token = cancellation.0.clone();
+///     tokio::spawn(async move {
+///         tokio::select! {
+///             _ = token.cancelled() => rollback().await,
+///             _ = generate() => (),
+///         }
+///     });
+///     Ok("started".to_string())
+/// }
+/// ```
+/// The token is not cancelled once the whole response has been sent.
+#[derive(Debug, Clone)]
+pub struct Cancellation(pub CancellationToken);
+
+impl Deref for Cancellation {
+    type Target = CancellationToken;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for Cancellation {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        request
+            .extensions
+            .get_cloned::<Cancellation>()
+            .ok_or_else(|| Error::internal(anyhow!("missing Cancellation extension")))
+    }
+}
@@ -1,10 +1,16 @@
-use std::{net::SocketAddr, ops::Deref};
+use std::{
+    net::{IpAddr, SocketAddr},
+    ops::Deref,
+};
 
 use anyhow::anyhow;
 use axol_http::request::RequestPartsRef;
 
 use crate::{Error, FromRequestParts, Result};
 
+/// The address of the peer the request was received from. Extracting `SocketAddr` directly works as well.
+///
+/// Inserted by `Server` and `RouterService` for every request, so it is only missing for requests constructed by hand.
 #[derive(Debug, Clone, Copy)]
 pub struct ConnectInfo(pub SocketAddr);
 
@@ -19,10 +25,46 @@ impl Deref for ConnectInfo {
 #[async_trait::async_trait]
 impl<'a> FromRequestParts<'a> for ConnectInfo {
     async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
-        let info = request
-            .extensions
-            .get::<ConnectInfo>()
-            .ok_or_else(|| Error::internal(anyhow!("missing ConnectInfo extension")))?;
+        let info = request.extensions.get::<ConnectInfo>().ok_or_else(|| {
+            Error::internal(anyhow!(
+                "missing ConnectInfo extension, the request was not received through `Server` or `RouterService`"
+            ))
+        })?;
         Ok(*info)
     }
 }
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for SocketAddr {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        Ok(ConnectInfo::from_request_parts(request).await?.0)
+    }
+}
+
+/// The IP address of the client, for the common "who is calling" question.
+///
+/// This is the `ConnectInfo` address, which `RealIp` replaces with the client address reported by trusted proxies if registered,
+/// so register `RealIp` before extracting this behind a reverse proxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerAddr(pub IpAddr);
+
+impl Deref for PeerAddr {
+    type Target = IpAddr;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for PeerAddr {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        let address = ConnectInfo::from_request_parts(request).await?.0.ip();
+        // dual-stack sockets report IPv4 clients as `::ffff:a.b.c.d`
+        let address = match address {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(address),
+            address => address,
+        };
+        Ok(Self(address))
+    }
+}
@@ -0,0 +1,157 @@
+use std::{fmt, ops::Deref};
+
+use axol_http::{request::RequestPartsRef, uri::Authority};
+use url::Url;
+
+use crate::{Error, FromRequestParts, Result};
+
+/// Header TLS-terminating proxies report the original scheme in.
+pub(crate) const FORWARDED_PROTO: &str = "x-forwarded-proto";
+
+/// The scheme the client used to reach us.
+///
+/// `Https` if the connection was accepted via `TlsIncoming`, the request has an `https` URI, or `x-forwarded-proto` says so.
+/// Like with `HttpsRedirect`, the forwarded header is trusted unconditionally, so don't rely on this for security decisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Http,
+    Https,
+}
+
+impl Scheme {
+    /// Determines the scheme of `request`, reading the original scheme from `forwarded_proto_header` if present.
+    pub(crate) fn detect(request: RequestPartsRef<'_>, forwarded_proto_header: &str) -> Self {
+        #[cfg(feature = "tls")]
+        if request.extensions.get::<crate::TlsConnection>().is_some() {
+            return Scheme::Https;
+        }
+        let is_https = match request.headers.get(forwarded_proto_header) {
+            // the first entry is from the proxy closest to the client
+            Some(proto) => proto
+                .split(',')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .eq_ignore_ascii_case("https"),
+            None => request.uri.scheme_str() == Some("https"),
+        };
+        if is_https {
+            Scheme::Https
+        } else {
+            Scheme::Http
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scheme::Http => "http",
+            Scheme::Https => "https",
+        }
+    }
+
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Scheme::Http => 80,
+            Scheme::Https => 443,
+        }
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for Scheme {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        Ok(Scheme::detect(request, FORWARDED_PROTO))
+    }
+}
+
+/// The `host` header, or the URI authority for HTTP/2 `:authority` and absolute-form requests.
+pub(crate) fn request_host<'a>(request: RequestPartsRef<'a>) -> Option<&'a str> {
+    request
+        .headers
+        .get("host")
+        .or_else(|| request.uri.authority().map(|x| x.as_str()))
+}
+
+/// The host (and port, if the client sent one) the request was addressed to, i.e. to build absolute URLs with `Scheme`.
+///
+/// Fails with `400 Bad Request` if the request names no host or an invalid one. Extract `Option<Host>` to handle that yourself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Host(pub Authority);
+
+impl Host {
+    pub(crate) fn parse(request: RequestPartsRef<'_>) -> Result<Self> {
+        let host = request_host(request).ok_or_else(|| Error::bad_request("missing host"))?;
+        let authority: Authority = host
+            .parse()
+            .map_err(|_| Error::bad_request("invalid host"))?;
+        // userinfo has no business in a host header
+        if authority.as_str().contains('@') {
+            return Err(Error::bad_request("invalid host"));
+        }
+        Ok(Self(authority))
+    }
+
+    /// The host without the port.
+    pub fn hostname(&self) -> &str {
+        self.0.host()
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        self.0.port_u16()
+    }
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for Host {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        Host::parse(request)
+    }
+}
+
+/// Reconstructs the URL the client requested from its `Scheme`, `Host`, and the path and query as sent (still percent-encoded),
+/// i.e. for redirects, canonical links or OAuth callback URLs.
+///
+/// Behind a reverse proxy, this relies on the proxy passing through the `host` header and setting `x-forwarded-proto`.
+/// Fails with `400 Bad Request` if the host is missing or invalid.
+pub fn absolute_url(request: RequestPartsRef<'_>) -> Result<Url> {
+    let scheme = Scheme::detect(request, FORWARDED_PROTO);
+    let host = Host::parse(request)?;
+    let path_and_query = request
+        .uri
+        .path_and_query()
+        .map(|x| x.as_str())
+        .unwrap_or("/");
+    Url::parse(&format!("{scheme}://{host}{path_and_query}"))
+        .map_err(|_| Error::bad_request("invalid request url"))
+}
+
+/// Extractor for `absolute_url`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbsoluteUrl(pub Url);
+
+impl Deref for AbsoluteUrl {
+    type Target = Url;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for AbsoluteUrl {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        absolute_url(request).map(Self)
+    }
+}
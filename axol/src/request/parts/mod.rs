@@ -11,6 +11,8 @@ use crate::{Extension, Result};
 mod query;
 pub use query::*;
 
+pub(crate) mod urlencoded_de;
+
 mod path;
 mod path_de;
 pub use path::*;
@@ -18,6 +20,26 @@ pub use path::*;
 mod connect_info;
 pub use connect_info::*;
 
+mod cancellation;
+pub use cancellation::*;
+
+mod host;
+pub use host::*;
+
+#[cfg(feature = "tls")]
+mod tls;
+#[cfg(feature = "tls")]
+pub use tls::*;
+
+mod state;
+pub use state::*;
+
+mod accept;
+pub use accept::*;
+
+mod preconditions;
+pub use preconditions::*;
+
 #[async_trait::async_trait]
 pub trait FromRequestParts<'a>: Sized + Send + Sync + 'a {
     async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self>;
@@ -103,6 +125,63 @@ impl<'a> FromRequestParts<'a> for MatchedPath {
     }
 }
 
+/// Number of request path segments consumed by the prefix of a `Router::nest` or `Router::nest_service`.
+/// Set as an extension on the path nested at, so the innermost nest wins.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct NestDepth(pub(crate) usize);
+
+/// Splits `path` into the prefix consumed by the first `depth` segments, and the remainder.
+pub(crate) fn split_nested_path(path: &str, depth: usize) -> (String, String) {
+    let segments = path
+        .trim()
+        .split('/')
+        .filter(|x| !x.is_empty())
+        .map(|x| x.trim())
+        .collect::<Vec<_>>();
+    let depth = depth.min(segments.len());
+    let prefix = segments[..depth]
+        .iter()
+        .map(|x| format!("/{x}"))
+        .collect::<String>();
+    let mut remainder = format!("/{}", segments[depth..].join("/"));
+    if path.ends_with('/') && remainder.len() > 1 {
+        remainder.push('/');
+    }
+    (prefix, remainder)
+}
+
+/// The part of the request path below the prefix a `Router::nest_service` handler or `Router::nest` router is mounted at.
+/// i.e. `/b/c` for a request to `/a/b/c` with a service nested at `/a`.
+/// Always starts with a `/`, and is not percent-decoded.
+#[derive(Clone, Debug)]
+pub struct NestedPath(pub String);
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for NestedPath {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        if let Some(NestDepth(depth)) = request.extensions.get_cloned::<NestDepth>() {
+            return Ok(Self(split_nested_path(request.uri.path(), depth).1));
+        }
+        Ok(Extension::<Self>::from_request_parts(request).await?.0)
+    }
+}
+
+/// The part of the request path consumed by the prefix a `Router::nest_service` handler or `Router::nest` router is mounted at,
+/// i.e. `/users/42` for a request to `/users/42/posts` with a router nested at `/users/:id`.
+/// Prepend it to build links relative to the mount point. Empty if not nested, never ends with a `/`, and is not percent-decoded.
+#[derive(Clone, Debug)]
+pub struct NestedPrefix(pub String);
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for NestedPrefix {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        let Some(NestDepth(depth)) = request.extensions.get_cloned::<NestDepth>() else {
+            return Ok(Self(String::new()));
+        };
+        Ok(Self(split_nested_path(request.uri.path(), depth).0))
+    }
+}
+
 macro_rules! impl_from_request {
     (
         [$($ty:ident),*], $last:ident
@@ -9,6 +9,14 @@ use super::path_de::{self, PathDeserializationError};
 
 pub struct RawPathExt(pub Vec<(Arc<str>, String)>);
 
+/// The path variables before percent-decoding, see `EncodedPath`.
+pub struct EncodedPathExt(pub Vec<(Arc<str>, String)>);
+
+/// The path variables as `(name, value)` pairs, in route order.
+///
+/// Values are percent-decoded, like for `Path`, so an encoded slash (`%2F`) within a variable becomes a literal `/`.
+/// Routing happens before decoding, so such a value still matched a single `:variable` segment.
+/// Use `EncodedPath` if the distinction matters, i.e. before splitting a value on `/`.
 #[derive(Debug, Clone)]
 pub struct RawPath<'a>(pub &'a [(Arc<str>, String)]);
 
@@ -30,6 +38,29 @@ impl<'a> FromRequestParts<'a> for RawPath<'a> {
     }
 }
 
+/// The path variables as sent by the client, without percent-decoding, as `(name, value)` pairs in route order.
+#[derive(Debug, Clone)]
+pub struct EncodedPath(pub Vec<(Arc<str>, String)>);
+
+impl Deref for EncodedPath {
+    type Target = [(Arc<str>, String)];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for EncodedPath {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        match request.extensions.get::<EncodedPathExt>() {
+            Some(values) => Ok(Self(values.0.clone())),
+            None => Ok(Self(vec![])),
+        }
+    }
+}
+
+/// Deserializes the path variables, percent-decoded (see `RawPath` for the caveats), into a single value, a tuple, a struct or a map.
 #[derive(Debug, Clone)]
 pub struct Path<T>(pub T);
 
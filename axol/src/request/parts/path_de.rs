@@ -34,10 +34,12 @@ pub enum PathDeserializationError {
     /// Failed to parse the value at a specific index into the expected type.
     ///
     /// This variant is used when deserializing into sequence types, such as tuples.
-    #[error("Cannot parse value at index {index} with value `{value:?}` to a `{expected_type}`")]
+    #[error("Cannot parse `{key}` (index {index}) with value `{value:?}` to a `{expected_type}`")]
     ParseErrorAtIndex {
         /// The index at which the value was located.
         index: usize,
+        /// The name of the path variable at the index.
+        key: String,
         /// The value from the URI.
         value: String,
         /// The expected type of the value.
@@ -135,14 +137,14 @@ macro_rules! parse_single_value {
                     .expected(1));
             }
 
-            let value =
-                self.url_params[0]
-                    .1
-                    .parse()
-                    .map_err(|_| PathDeserializationError::ParseError {
-                        value: self.url_params[0].1.as_str().to_owned(),
-                        expected_type: $ty,
-                    })?;
+            let (key, value) = &self.url_params[0];
+            let value = value
+                .parse()
+                .map_err(|_| PathDeserializationError::ParseErrorAtKey {
+                    key: key.to_string(),
+                    value: value.clone(),
+                    expected_type: $ty,
+                })?;
             visitor.$visit_fn(value)
         }
     };
@@ -409,9 +411,10 @@ macro_rules! parse_value {
                             value: self.value.to_string(),
                             expected_type: $ty,
                         },
-                        KeyOrIdx::Idx { idx: index, key: _ } => {
+                        KeyOrIdx::Idx { idx: index, key } => {
                             PathDeserializationError::ParseErrorAtIndex {
                                 index,
+                                key: key.to_string(),
                                 value: self.value.to_string(),
                                 expected_type: $ty,
                             }
@@ -973,6 +976,7 @@ mod tests {
             (bool, u32),
             PathDeserializationError::ParseErrorAtIndex {
                 index: 1,
+                key: "b".to_owned(),
                 value: "true".to_owned(),
                 expected_type: "u32",
             }
@@ -980,11 +984,12 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_error_error() {
+    fn test_parse_single_value_error() {
         test_parse_error!(
             vec![("a", "false")],
             u32,
-            PathDeserializationError::ParseError {
+            PathDeserializationError::ParseErrorAtKey {
+                key: "a".to_owned(),
                 value: "false".to_owned(),
                 expected_type: "u32",
             }
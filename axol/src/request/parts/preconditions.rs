@@ -0,0 +1,194 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axol_http::{request::RequestPartsRef, typed_headers, Method, StatusCode};
+
+use crate::{Error, FromRequestParts, Result};
+
+/// Weak comparison, as required for `if-none-match`
+fn weak_eq(a: &str, b: &str) -> bool {
+    a.trim_start_matches("W/") == b.trim_start_matches("W/")
+}
+
+/// Strong comparison, as required for `if-match`
+fn strong_eq(a: &str, b: &str) -> bool {
+    !a.starts_with("W/") && !b.starts_with("W/") && a == b
+}
+
+fn tags_match(header: &str, etag: Option<&str>, eq: fn(&str, &str) -> bool) -> bool {
+    let Some(etag) = etag else {
+        return false;
+    };
+    header
+        .split(',')
+        .map(|x| x.trim())
+        .any(|x| x == "*" || eq(x, etag))
+}
+
+/// HTTP dates have a resolution of one second.
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or_default()
+}
+
+/// The `if-match` header, `None` if absent.
+#[derive(Debug, Clone, Default)]
+pub struct IfMatch(pub Option<String>);
+
+impl IfMatch {
+    /// Whether `etag`, the current entity tag of the resource (`None` if it doesn't exist), passes this precondition.
+    /// Uses strong comparison. Passes if the header is absent.
+    pub fn passes(&self, etag: Option<&str>) -> bool {
+        match &self.0 {
+            Some(header) => tags_match(header, etag, strong_eq),
+            None => true,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for IfMatch {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        let values = request.headers.get_all("if-match").collect::<Vec<_>>();
+        Ok(Self((!values.is_empty()).then(|| values.join(", "))))
+    }
+}
+
+/// The `if-none-match` header, `None` if absent.
+#[derive(Debug, Clone, Default)]
+pub struct IfNoneMatch(pub Option<String>);
+
+impl IfNoneMatch {
+    /// Whether `etag`, the current entity tag of the resource (`None` if it doesn't exist), passes this precondition.
+    /// Uses weak comparison. Passes if the header is absent.
+    pub fn passes(&self, etag: Option<&str>) -> bool {
+        match &self.0 {
+            Some(header) => !tags_match(header, etag, weak_eq),
+            None => true,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for IfNoneMatch {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        let values = request.headers.get_all("if-none-match").collect::<Vec<_>>();
+        Ok(Self((!values.is_empty()).then(|| values.join(", "))))
+    }
+}
+
+/// The `if-modified-since` header, `None` if absent or invalid.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IfModifiedSince(pub Option<SystemTime>);
+
+impl IfModifiedSince {
+    /// Whether a resource last modified at `last_modified` passes this precondition. Passes if the header is absent.
+    pub fn passes(&self, last_modified: SystemTime) -> bool {
+        match self.0 {
+            Some(since) => unix_seconds(last_modified) > unix_seconds(since),
+            None => true,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for IfModifiedSince {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        Ok(Self(
+            request
+                .headers
+                .get_typed::<typed_headers::IfModifiedSince>()
+                .map(SystemTime::from),
+        ))
+    }
+}
+
+/// The `if-unmodified-since` header, `None` if absent or invalid.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IfUnmodifiedSince(pub Option<SystemTime>);
+
+impl IfUnmodifiedSince {
+    /// Whether a resource last modified at `last_modified` passes this precondition. Passes if the header is absent.
+    pub fn passes(&self, last_modified: SystemTime) -> bool {
+        match self.0 {
+            Some(since) => unix_seconds(last_modified) <= unix_seconds(since),
+            None => true,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for IfUnmodifiedSince {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        Ok(Self(
+            request
+                .headers
+                .get_typed::<typed_headers::IfUnmodifiedSince>()
+                .map(SystemTime::from),
+        ))
+    }
+}
+
+/// All conditional request headers, evaluated together in the order of RFC 7232 section 6.
+///
+/// i.e. to guard a `PUT` against lost updates:
+/// ```ignore
+/// async fn update(preconditions: Preconditions, body: Json<Item>) -> Result<()> {
+///     let current = load_item().await?;
+///     preconditions.check(Some(&current.etag), None)?;
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Preconditions {
+    pub method: Method,
+    pub if_match: IfMatch,
+    pub if_none_match: IfNoneMatch,
+    pub if_modified_since: IfModifiedSince,
+    pub if_unmodified_since: IfUnmodifiedSince,
+}
+
+impl Preconditions {
+    /// Evaluates the preconditions against the current entity tag (`None` if the resource doesn't exist) and last modification time of the resource.
+    ///
+    /// Fails with `Error::PreconditionFailed`, or with a `304 Not Modified` status for `GET`/`HEAD` requests whose cached copy is still valid.
+    pub fn check(&self, etag: Option<&str>, last_modified: Option<SystemTime>) -> Result<()> {
+        if self.if_match.0.is_some() {
+            if !self.if_match.passes(etag) {
+                return Err(Error::PreconditionFailed);
+            }
+        } else if let Some(last_modified) = last_modified {
+            if !self.if_unmodified_since.passes(last_modified) {
+                return Err(Error::PreconditionFailed);
+            }
+        }
+        let safe = matches!(self.method, Method::Get | Method::Head);
+        if self.if_none_match.0.is_some() {
+            if !self.if_none_match.passes(etag) {
+                return Err(if safe {
+                    Error::Status(StatusCode::NotModified)
+                } else {
+                    Error::PreconditionFailed
+                });
+            }
+        } else if let Some(last_modified) = last_modified {
+            if safe && !self.if_modified_since.passes(last_modified) {
+                return Err(Error::Status(StatusCode::NotModified));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for Preconditions {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        Ok(Self {
+            method: request.method,
+            if_match: IfMatch::from_request_parts(request).await?,
+            if_none_match: IfNoneMatch::from_request_parts(request).await?,
+            if_modified_since: IfModifiedSince::from_request_parts(request).await?,
+            if_unmodified_since: IfUnmodifiedSince::from_request_parts(request).await?,
+        })
+    }
+}
@@ -5,10 +5,14 @@ use serde::Deserialize;
 
 use crate::{Error, FromRequestParts, Result};
 
+use super::urlencoded_de;
+
+/// The query string as sent by the client, without percent-decoding.
+/// Fails with `400 Bad Request` if the URI has no query string, so `Option<RawQuery>` distinguishes `/path` from `/path?`.
 #[derive(Debug, Clone)]
-pub struct RawQuery<'a>(pub &'a str);
+pub struct RawQuery(pub String);
 
-impl<'a> Deref for RawQuery<'a> {
+impl Deref for RawQuery {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -17,16 +21,42 @@ impl<'a> Deref for RawQuery<'a> {
 }
 
 #[async_trait::async_trait]
-impl<'a> FromRequestParts<'a> for RawQuery<'a> {
+impl<'a> FromRequestParts<'a> for RawQuery {
     async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
         request
             .uri
             .query()
-            .map(RawQuery)
+            .map(|x| RawQuery(x.to_string()))
             .ok_or_else(|| Error::bad_request("missing query string"))
     }
 }
 
+/// Request extension configuring the `Query` extractor, i.e. `router.extension("/", QueryConfig { last_value_wins: true })`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueryConfig {
+    /// If a key is repeated for a field that isn't a sequence (`?page=1&page=2` for `page: u32`), use the last value.
+    /// By default, this is rejected with `400 Bad Request`.
+    pub last_value_wins: bool,
+}
+
+/// Deserializes the query string. Repeated keys (`?tag=a&tag=b`) deserialize into sequences,
+/// and bracketed keys (`?filter[name]=x`) deserialize into nested structs or maps, i.e.:
+/// ```ignore
+/// #[derive(Deserialize)]
+/// struct Search {
+///     tag: Vec<String>,
+///     filter: Filter,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Filter {
+///     name: Option<String>,
+/// }
+/// ```
+/// A missing query string is treated as empty: structs whose fields are all `Option` or `#[serde(default)]` deserialize
+/// with those defaults, and `Query<Option<T>>` yields `None`.
+/// Repeated keys for fields that aren't sequences are rejected, see `QueryConfig`.
+/// On failure, `400 Bad Request` is returned naming the offending key.
 #[derive(Debug, Clone)]
 pub struct Query<T>(pub T);
 
@@ -41,9 +71,13 @@ impl<T> Deref for Query<T> {
 #[async_trait::async_trait]
 impl<'a, T: Deserialize<'a> + Send + Sync + 'a> FromRequestParts<'a> for Query<T> {
     async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        let config = request
+            .extensions
+            .get_cloned::<QueryConfig>()
+            .unwrap_or_default();
         let query = request.uri.query().unwrap_or_default();
-        Ok(Query(serde_urlencoded::from_str(query).map_err(|e| {
-            Error::bad_request(format!("Failed to deserialize query string: {e}"))
-        })?))
+        let value = urlencoded_de::from_bytes_with(query.as_bytes(), config.last_value_wins)
+            .map_err(|e| Error::bad_request(format!("Failed to deserialize query string: {e}")))?;
+        Ok(Query(value))
     }
 }
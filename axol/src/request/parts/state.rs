@@ -0,0 +1,40 @@
+use std::ops::Deref;
+
+use anyhow::anyhow;
+use axol_http::{request::RequestPartsRef, Extensions};
+
+use crate::{Error, FromRequestParts, Result};
+
+/// Holds the router state separately from the freeform request extensions.
+/// Since this type is private, it can't be removed or replaced by `Extensions::remove`/`Extensions::insert` outside of axol.
+pub(crate) struct StateSlot(pub(crate) Extensions);
+
+/// Extracts state registered with `Router::with_state`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct State<S>(pub S);
+
+impl<S> Deref for State<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a, S: Clone + Send + Sync + 'static> FromRequestParts<'a> for State<S> {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        request
+            .extensions
+            .get::<StateSlot>()
+            .and_then(|slot| slot.0.get::<S>())
+            .cloned()
+            .map(State)
+            .ok_or_else(|| {
+                Error::internal(anyhow!(
+                    "missing router state `{}`",
+                    std::any::type_name::<S>()
+                ))
+            })
+    }
+}
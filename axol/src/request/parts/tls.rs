@@ -0,0 +1,53 @@
+use std::ops::Deref;
+
+use anyhow::anyhow;
+use axol_http::request::RequestPartsRef;
+
+use crate::{Error, FromRequestParts, Result};
+
+/// Marks requests on connections accepted via `TlsIncoming`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlsConnection;
+
+/// The certificate chain presented by the client during a mutual TLS handshake, end-entity certificate first.
+///
+/// Only present on connections accepted via `TlsIncoming` whose `ServerConfig` requests client authentication.
+/// If client authentication is optional (i.e. `AllowAnyAnonymousOrAuthenticatedClient`) and the client presented no certificate,
+/// the extension is absent and extracting `ClientCertificate` fails with `401 Unauthorized`. Use `Option<ClientCertificate>` to allow anonymous clients.
+#[derive(Debug, Clone)]
+pub struct ClientCertificate(pub Vec<rustls::Certificate>);
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for ClientCertificate {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        request
+            .extensions
+            .get_cloned::<ClientCertificate>()
+            .ok_or(Error::Unauthorized)
+    }
+}
+
+/// The application protocol negotiated via TLS ALPN, i.e. `h2` or `http/1.1`.
+///
+/// Only present on connections accepted via `TlsIncoming` where the client and `ServerConfig::alpn_protocols` agreed on a protocol.
+/// Extracting `NegotiatedProtocol` otherwise fails, use `Option<NegotiatedProtocol>` for connections that may not use ALPN.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegotiatedProtocol(pub String);
+
+impl Deref for NegotiatedProtocol {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> FromRequestParts<'a> for NegotiatedProtocol {
+    async fn from_request_parts(request: RequestPartsRef<'a>) -> Result<Self> {
+        request
+            .extensions
+            .get_cloned::<NegotiatedProtocol>()
+            .ok_or_else(|| Error::internal(anyhow!("missing NegotiatedProtocol extension")))
+    }
+}
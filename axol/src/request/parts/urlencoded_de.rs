@@ -0,0 +1,535 @@
+//! Deserializer for `application/x-www-form-urlencoded` data (query strings and form bodies).
+//!
+//! Unlike `serde_urlencoded`, keys are grouped before deserializing:
+//! - `tag=a&tag=b` deserializes into a `Vec<String>` field `tag`. A single `tag=a` works for a `Vec` too.
+//! - `tag[]=a&tag[]=b` is the same as repeating `tag`.
+//! - `filter[name]=x&filter[age]=3` deserializes into a nested struct (or map) field `filter`, and nests arbitrarily deep.
+//! - `items[1]=b&items[0]=a` deserializes into a `Vec` ordered by index.
+//! - Empty values (`name=`) deserialize as empty strings, or `None` for `Option` fields.
+//! - An empty input deserializes into `None` for an `Option` root, and into structs if all their fields are optional or defaulted.
+//! - Repeated keys for a single value field are an error, unless `last_value_wins` is set.
+//!
+//! Keys and values are percent-decoded (and `+` is decoded to a space) before any of the above.
+//! Values that need no decoding are borrowed from the input, so `&str` fields work for them.
+
+use std::{borrow::Cow, fmt};
+
+use serde::{
+    de::{
+        self, value::StringDeserializer, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess,
+        Visitor,
+    },
+    forward_to_deserialize_any, Deserialize, Deserializer,
+};
+
+#[derive(Debug)]
+pub(crate) struct UrlEncodedError {
+    /// The offending key, if known
+    pub(crate) key: Option<String>,
+    pub(crate) message: String,
+}
+
+impl UrlEncodedError {
+    fn new(key: &Option<String>, message: impl fmt::Display) -> Self {
+        Self {
+            key: key.clone(),
+            message: message.to_string(),
+        }
+    }
+
+    fn with_key(mut self, key: &str) -> Self {
+        if self.key.is_none() {
+            self.key = Some(key.to_string());
+        }
+        self
+    }
+}
+
+impl fmt::Display for UrlEncodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "invalid value for key `{key}`: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for UrlEncodedError {}
+
+impl de::Error for UrlEncodedError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            key: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
+pub(crate) fn from_bytes<'de, T: Deserialize<'de>>(input: &'de [u8]) -> Result<T, UrlEncodedError> {
+    from_bytes_with(input, false)
+}
+
+/// Like `from_bytes`, but if `last_value_wins`, repeated keys for a single value field deserialize the last value.
+pub(crate) fn from_bytes_with<'de, T: Deserialize<'de>>(
+    input: &'de [u8],
+    last_value_wins: bool,
+) -> Result<T, UrlEncodedError> {
+    let mut root = Node::Map(vec![]);
+    for (key, value) in form_urlencoded::parse(input) {
+        let segments = split_key(&key);
+        root.insert(&key, &segments, value)?;
+    }
+    T::deserialize(NodeDeserializer {
+        node: root,
+        key: None,
+        last_value_wins,
+    })
+}
+
+enum Node<'de> {
+    Values(Vec<Cow<'de, str>>),
+    Map(Vec<(String, Node<'de>)>),
+}
+
+/// Splits `a[b][c]` into `["a", "b", "c"]`. Empty brackets are dropped, as they only mean "append".
+/// Keys which aren't well-formed bracket expressions are used verbatim.
+fn split_key(key: &str) -> Vec<&str> {
+    let Some(open) = key.find('[') else {
+        return vec![key];
+    };
+    if open == 0 || !key.ends_with(']') {
+        return vec![key];
+    }
+    let mut out = vec![&key[..open]];
+    let mut rest = &key[open..];
+    while !rest.is_empty() {
+        let Some(inner) = rest.strip_prefix('[') else {
+            return vec![key];
+        };
+        let Some(close) = inner.find(']') else {
+            return vec![key];
+        };
+        if close > 0 {
+            out.push(&inner[..close]);
+        }
+        rest = &inner[close + 1..];
+    }
+    out
+}
+
+impl<'de> Node<'de> {
+    fn insert(
+        &mut self,
+        full_key: &str,
+        segments: &[&str],
+        value: Cow<'de, str>,
+    ) -> Result<(), UrlEncodedError> {
+        let conflict = || UrlEncodedError {
+            key: Some(full_key.to_string()),
+            message: "key is used both as a value and with nested keys".to_string(),
+        };
+        let Some((first, rest)) = segments.split_first() else {
+            return match self {
+                Node::Values(values) => {
+                    values.push(value);
+                    Ok(())
+                }
+                Node::Map(_) => Err(conflict()),
+            };
+        };
+        let Node::Map(entries) = self else {
+            return Err(conflict());
+        };
+        let index = match entries.iter().position(|(key, _)| key == first) {
+            Some(index) => index,
+            None => {
+                let node = if rest.is_empty() {
+                    Node::Values(vec![])
+                } else {
+                    Node::Map(vec![])
+                };
+                entries.push((first.to_string(), node));
+                entries.len() - 1
+            }
+        };
+        entries[index].1.insert(full_key, rest, value)
+    }
+}
+
+struct NodeDeserializer<'de> {
+    node: Node<'de>,
+    key: Option<String>,
+    last_value_wins: bool,
+}
+
+impl<'de> NodeDeserializer<'de> {
+    fn single(self) -> Result<ValueDeserializer<'de>, UrlEncodedError> {
+        match self.node {
+            Node::Values(mut values)
+                if values.len() == 1 || (self.last_value_wins && !values.is_empty()) =>
+            {
+                Ok(ValueDeserializer {
+                    value: values.pop().unwrap(),
+                    key: self.key,
+                    last_value_wins: self.last_value_wins,
+                })
+            }
+            Node::Values(values) => Err(UrlEncodedError::new(
+                &self.key,
+                format!("expected a single value, found {}", values.len()),
+            )),
+            Node::Map(_) => Err(UrlEncodedError::new(
+                &self.key,
+                "expected a single value, found nested keys",
+            )),
+        }
+    }
+
+    fn into_seq(self) -> Result<SeqDeserializer<'de>, UrlEncodedError> {
+        let nodes: Vec<Node<'de>> = match self.node {
+            Node::Values(values) => values.into_iter().map(|x| Node::Values(vec![x])).collect(),
+            Node::Map(entries) => {
+                let mut indexed = Vec::with_capacity(entries.len());
+                for (key, node) in entries {
+                    let Ok(index) = key.parse::<usize>() else {
+                        return Err(UrlEncodedError::new(
+                            &self.key,
+                            format!("expected a sequence, found nested key `{key}`"),
+                        ));
+                    };
+                    indexed.push((index, node));
+                }
+                indexed.sort_by_key(|(index, _)| *index);
+                indexed.into_iter().map(|(_, node)| node).collect()
+            }
+        };
+        Ok(SeqDeserializer {
+            nodes: nodes.into_iter(),
+            key: self.key,
+            index: 0,
+            last_value_wins: self.last_value_wins,
+        })
+    }
+}
+
+macro_rules! forward_to_single {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.single()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for NodeDeserializer<'de> {
+    type Error = UrlEncodedError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if matches!(&self.node, Node::Values(values) if values.len() == 1) {
+            return self.single()?.deserialize_any(visitor);
+        }
+        match self.node {
+            Node::Map(entries) => visitor.visit_map(MapDeserializer {
+                entries: entries.into_iter(),
+                pending: None,
+                key: self.key,
+                last_value_wins: self.last_value_wins,
+            }),
+            Node::Values(_) => visitor.visit_seq(self.into_seq()?),
+        }
+    }
+
+    forward_to_single! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_unit
+        deserialize_identifier
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let empty = match &self.node {
+            Node::Values(values) => values.len() == 1 && values[0].is_empty(),
+            // only reachable for the root, i.e. a missing or empty query string
+            Node::Map(entries) => entries.is_empty(),
+        };
+        if empty {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(self.into_seq()?)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.node {
+            Node::Map(entries) => visitor.visit_map(MapDeserializer {
+                entries: entries.into_iter(),
+                pending: None,
+                key: self.key,
+                last_value_wins: self.last_value_wins,
+            }),
+            Node::Values(_) => Err(UrlEncodedError::new(
+                &self.key,
+                "expected nested keys (i.e. `key[field]=value`), found a value",
+            )),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct MapDeserializer<'de> {
+    entries: std::vec::IntoIter<(String, Node<'de>)>,
+    pending: Option<(String, Node<'de>)>,
+    key: Option<String>,
+    last_value_wins: bool,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+    type Error = UrlEncodedError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, node)) = self.entries.next() else {
+            return Ok(None);
+        };
+        let full_key = match &self.key {
+            Some(parent) => format!("{parent}[{key}]"),
+            None => key.clone(),
+        };
+        let out = seed.deserialize(ValueDeserializer {
+            value: Cow::Owned(key),
+            key: Some(full_key.clone()),
+            last_value_wins: self.last_value_wins,
+        })?;
+        self.pending = Some((full_key, node));
+        Ok(Some(out))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, node) = self
+            .pending
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(NodeDeserializer {
+            node,
+            key: Some(key.clone()),
+            last_value_wins: self.last_value_wins,
+        })
+        .map_err(|e| e.with_key(&key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct SeqDeserializer<'de> {
+    nodes: std::vec::IntoIter<Node<'de>>,
+    key: Option<String>,
+    index: usize,
+    last_value_wins: bool,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
+    type Error = UrlEncodedError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some(node) = self.nodes.next() else {
+            return Ok(None);
+        };
+        let key = self.key.as_ref().map(|x| format!("{x}[{}]", self.index));
+        self.index += 1;
+        seed.deserialize(NodeDeserializer {
+            node,
+            key,
+            last_value_wins: self.last_value_wins,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.nodes.len())
+    }
+}
+
+struct ValueDeserializer<'de> {
+    value: Cow<'de, str>,
+    key: Option<String>,
+    last_value_wins: bool,
+}
+
+macro_rules! parse_value {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.value.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(e) => Err(UrlEncodedError::new(
+                        &self.key,
+                        format!("cannot parse `{}`: {e}", self.value),
+                    )),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = UrlEncodedError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+            Cow::Owned(value) => visitor.visit_string(value),
+        }
+    }
+
+    parse_value! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_byte_buf(self.value.into_owned().into_bytes())
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.value.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(SeqDeserializer {
+            nodes: vec![Node::Values(vec![self.value])].into_iter(),
+            key: self.key,
+            index: 0,
+            last_value_wins: self.last_value_wins,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let deserializer: StringDeserializer<UrlEncodedError> =
+            self.value.into_owned().into_deserializer();
+        visitor
+            .visit_enum(deserializer)
+            .map_err(|e| match &self.key {
+                Some(key) => e.with_key(key),
+                None => e,
+            })
+    }
+
+    forward_to_deserialize_any! {
+        str string identifier tuple tuple_struct map struct ignored_any
+    }
+}
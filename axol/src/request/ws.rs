@@ -10,7 +10,7 @@ use axol_http::{
 };
 use futures_util::{
     sink::{Sink, SinkExt},
-    stream::{Stream, StreamExt},
+    stream::{SplitSink, SplitStream, Stream, StreamExt},
 };
 use hyper::upgrade::{OnUpgrade, Upgraded};
 pub use hyper::Error as HyperError;
@@ -21,7 +21,9 @@ use std::{
     pin::Pin,
     str::Utf8Error,
     task::{Context, Poll},
+    time::Duration,
 };
+use tokio::time::{Instant, Sleep};
 pub use tokio_tungstenite::tungstenite::Error as WsError;
 use tokio_tungstenite::{
     tungstenite::{
@@ -38,6 +40,11 @@ use tokio_tungstenite::{
 /// rejected.
 ///
 /// See the [module docs](self) for an example.
+///
+/// Compression (`permessage-deflate`) is not supported, and there is no option to enable it:
+/// tungstenite rejects any frame with the `RSV1` bit that compressed messages are sent with.
+/// Extensions offered in `Sec-WebSocket-Extensions` are ignored rather than rejected, as RFC 6455 allows,
+/// so clients offering compression fall back to an uncompressed connection.
 #[cfg_attr(docsrs, doc(cfg(feature = "ws")))]
 pub struct WebSocketUpgrade<F = DefaultOnFailedUpgrade> {
     config: WebSocketConfig,
@@ -47,6 +54,7 @@ pub struct WebSocketUpgrade<F = DefaultOnFailedUpgrade> {
     on_upgrade: OnUpgrade,
     on_failed_upgrade: F,
     sec_websocket_protocol: Option<String>,
+    keep_alive: Option<(Duration, Duration)>,
 }
 
 impl<F> std::fmt::Debug for WebSocketUpgrade<F> {
@@ -56,6 +64,7 @@ impl<F> std::fmt::Debug for WebSocketUpgrade<F> {
             .field("protocol", &self.protocol)
             .field("sec_websocket_key", &self.sec_websocket_key)
             .field("sec_websocket_protocol", &self.sec_websocket_protocol)
+            .field("keep_alive", &self.keep_alive)
             .finish_non_exhaustive()
     }
 }
@@ -85,6 +94,16 @@ impl<F> WebSocketUpgrade<F> {
         self
     }
 
+    /// Send a ping once no message has been received for `interval`, and fail the socket if no message
+    /// (usually the pong) is received within `timeout` of that ping. Disabled by default.
+    ///
+    /// Pings are only sent while the `WebSocket` is being read, i.e. while awaiting `recv`.
+    /// Once timed out, `recv` returns a `TimedOut` IO error, followed by `None`.
+    pub fn keep_alive(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.keep_alive = Some((interval, timeout));
+        self
+    }
+
     /// Set the known protocols.
     ///
     /// If the protocol name specified by `Sec-WebSocket-Protocol` header
@@ -174,6 +193,7 @@ impl<F> WebSocketUpgrade<F> {
             on_upgrade: self.on_upgrade,
             on_failed_upgrade: callback,
             sec_websocket_protocol: self.sec_websocket_protocol,
+            keep_alive: self.keep_alive,
         }
     }
 
@@ -191,6 +211,7 @@ impl<F> WebSocketUpgrade<F> {
         let on_failed_upgrade = self.on_failed_upgrade;
 
         let protocol = self.protocol.clone();
+        let keep_alive = self.keep_alive;
 
         tokio::spawn(async move {
             let upgraded = match on_upgrade.await {
@@ -207,6 +228,13 @@ impl<F> WebSocketUpgrade<F> {
             let socket = WebSocket {
                 inner: socket,
                 protocol,
+                keep_alive: keep_alive.map(|(interval, timeout)| KeepAlive {
+                    interval,
+                    timeout,
+                    sleep: Box::pin(tokio::time::sleep(interval)),
+                    awaiting_pong: false,
+                    expired: false,
+                }),
             };
             callback(socket).await;
         });
@@ -306,6 +334,7 @@ impl<'a> FromRequestParts<'a> for WebSocketUpgrade<DefaultOnFailedUpgrade> {
             Removed::Removed(x) => x,
         };
 
+        // `sec-websocket-extensions` is deliberately ignored, see the `WebSocketUpgrade` docs
         let sec_websocket_protocol = request
             .headers
             .get("sec-websocket-protocol")
@@ -318,6 +347,7 @@ impl<'a> FromRequestParts<'a> for WebSocketUpgrade<DefaultOnFailedUpgrade> {
             on_upgrade,
             sec_websocket_protocol,
             on_failed_upgrade: DefaultOnFailedUpgrade,
+            keep_alive: None,
         })
     }
 }
@@ -329,6 +359,18 @@ impl<'a> FromRequestParts<'a> for WebSocketUpgrade<DefaultOnFailedUpgrade> {
 pub struct WebSocket {
     inner: WebSocketStream<Upgraded>,
     protocol: Option<String>,
+    keep_alive: Option<KeepAlive>,
+}
+
+/// See `WebSocketUpgrade::keep_alive`
+#[derive(Debug)]
+struct KeepAlive {
+    interval: Duration,
+    timeout: Duration,
+    /// Fires when the next ping is due, or when the pending ping times out
+    sleep: Pin<Box<Sleep>>,
+    awaiting_pong: bool,
+    expired: bool,
 }
 
 impl WebSocket {
@@ -353,15 +395,78 @@ impl WebSocket {
     pub fn protocol(&self) -> Option<&str> {
         self.protocol.as_deref()
     }
+
+    /// Splits this socket into halves that can be used concurrently, i.e. receiving in one task while sending from another.
+    ///
+    /// Keep-alive pings (see `WebSocketUpgrade::keep_alive`) are still sent while the receiver is being read.
+    pub fn split(self) -> (WebSocketSender, WebSocketReceiver) {
+        let protocol = self.protocol.clone();
+        let (sink, stream) = StreamExt::split(self);
+        (
+            WebSocketSender {
+                inner: sink,
+                protocol: protocol.clone(),
+            },
+            WebSocketReceiver {
+                inner: stream,
+                protocol,
+            },
+        )
+    }
+
+    /// Sends keep-alive pings as they become due, failing if the previous ping timed out.
+    // returns the same `WsError` as the `Stream` impl it's polled from
+    #[allow(clippy::result_large_err)]
+    fn poll_keep_alive(&mut self, cx: &mut Context<'_>) -> Result<(), WsError> {
+        let Some(keep_alive) = &mut self.keep_alive else {
+            return Ok(());
+        };
+        while keep_alive.sleep.as_mut().poll(cx).is_ready() {
+            if keep_alive.awaiting_pong {
+                keep_alive.expired = true;
+                return Err(WsError::Io(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "websocket keep-alive timed out",
+                )));
+            }
+            let mut inner = Pin::new(&mut self.inner);
+            // if the send queue is full, skip this ping rather than blocking reads on it
+            let next = match inner.as_mut().poll_ready(cx) {
+                Poll::Ready(ready) => {
+                    ready?;
+                    inner.as_mut().start_send(ts::Message::Ping(vec![]))?;
+                    if let Poll::Ready(Err(e)) = inner.poll_flush(cx) {
+                        return Err(e);
+                    }
+                    keep_alive.awaiting_pong = true;
+                    keep_alive.timeout
+                }
+                Poll::Pending => keep_alive.interval,
+            };
+            keep_alive.sleep.as_mut().reset(Instant::now() + next);
+        }
+        Ok(())
+    }
 }
 
 impl Stream for WebSocket {
     type Item = Result<Message, WsError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.keep_alive.as_ref().is_some_and(|x| x.expired) {
+            return Poll::Ready(None);
+        }
+        if let Err(e) = self.poll_keep_alive(cx) {
+            return Poll::Ready(Some(Err(e)));
+        }
         loop {
             match futures_util::ready!(self.inner.poll_next_unpin(cx)) {
                 Some(Ok(msg)) => {
+                    if let Some(keep_alive) = &mut self.keep_alive {
+                        keep_alive.awaiting_pong = false;
+                        let deadline = Instant::now() + keep_alive.interval;
+                        keep_alive.sleep.as_mut().reset(deadline);
+                    }
                     if let Some(msg) = Message::from_tungstenite(msg) {
                         return Poll::Ready(Some(Ok(msg)));
                     }
@@ -393,6 +498,86 @@ impl Sink<Message> for WebSocket {
     }
 }
 
+/// The sending half of a `WebSocket`, see `WebSocket::split`.
+#[derive(Debug)]
+pub struct WebSocketSender {
+    inner: SplitSink<WebSocket, Message>,
+    protocol: Option<String>,
+}
+
+impl WebSocketSender {
+    /// Send a message.
+    pub async fn send(&mut self, msg: Message) -> Result<(), WsError> {
+        self.inner.send(msg).await
+    }
+
+    /// Gracefully close the WebSocket. The receiver yields any remaining messages, then `None`.
+    pub async fn close(mut self) -> Result<(), WsError> {
+        self.inner.close().await
+    }
+
+    /// Return the selected WebSocket subprotocol, if one has been chosen.
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    /// Recombines the halves returned by the same `WebSocket::split` call. Panics if they came from different sockets.
+    pub fn reunite(self, receiver: WebSocketReceiver) -> WebSocket {
+        self.inner
+            .reunite(receiver.inner)
+            .expect("attempted to reunite halves of different websockets")
+    }
+}
+
+impl Sink<Message> for WebSocketSender {
+    type Error = WsError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Message) -> Result<(), Self::Error> {
+        Pin::new(&mut self.inner).start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+/// The receiving half of a `WebSocket`, see `WebSocket::split`.
+#[derive(Debug)]
+pub struct WebSocketReceiver {
+    inner: SplitStream<WebSocket>,
+    protocol: Option<String>,
+}
+
+impl WebSocketReceiver {
+    /// Receive another message.
+    ///
+    /// Returns `None` if the stream has closed.
+    pub async fn recv(&mut self) -> Option<Result<Message, WsError>> {
+        self.inner.next().await
+    }
+
+    /// Return the selected WebSocket subprotocol, if one has been chosen.
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+}
+
+impl Stream for WebSocketReceiver {
+    type Item = Result<Message, WsError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
 /// Status code used to indicate why an endpoint is closing the WebSocket connection.
 pub type CloseCode = u16;
 
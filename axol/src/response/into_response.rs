@@ -1,4 +1,6 @@
-use crate::{IntoResponseParts, Result};
+use std::borrow::Cow;
+
+use crate::{Error, IntoResponseParts, PlainText, Result};
 use axol_http::{header::HeaderMap, response::Response, StatusCode};
 
 pub trait IntoResponse {
@@ -16,12 +18,10 @@ impl IntoResponse for () {
 
 //TODO fill out more
 fn x() {}
+/// Sent as `text/plain; charset=utf-8`.
 impl IntoResponse for &str {
     fn into_response(self) -> Result<Response> {
-        Ok(Response {
-            body: axol_http::Body::Bytes(self.as_bytes().to_vec()),
-            ..Default::default()
-        })
+        PlainText(self).into_response()
     }
 }
 
@@ -34,12 +34,17 @@ impl IntoResponse for &[u8] {
     }
 }
 
+/// Sent as `text/plain; charset=utf-8`.
 impl IntoResponse for String {
     fn into_response(self) -> Result<Response> {
-        Ok(Response {
-            body: axol_http::Body::Bytes(self.into_bytes()),
-            ..Default::default()
-        })
+        PlainText(self).into_response()
+    }
+}
+
+/// Sent as `text/plain; charset=utf-8`.
+impl IntoResponse for Cow<'static, str> {
+    fn into_response(self) -> Result<Response> {
+        PlainText(self.into_owned()).into_response()
     }
 }
 
@@ -76,9 +81,10 @@ impl IntoResponse for Response {
     }
 }
 
-impl<T: IntoResponse> IntoResponse for Result<T> {
+/// Any error convertible into `Error` can be returned, i.e. a domain error type implementing `From<MyError> for Error`, or `anyhow::Error`.
+impl<T: IntoResponse, E: Into<Error>> IntoResponse for Result<T, E> {
     fn into_response(self) -> Result<Response> {
-        self.and_then(|x| x.into_response())
+        self.map_err(Into::into).and_then(|x| x.into_response())
     }
 }
 
@@ -6,3 +6,6 @@ pub use into_response::*;
 
 mod typed_body;
 pub use typed_body::*;
+
+mod redirect;
+pub use redirect::*;
@@ -0,0 +1,42 @@
+use axol_http::{response::Response, Uri};
+
+use crate::{IntoResponse, RedirectMode, Result};
+
+/// A redirect returned on the success path of a handler, i.e. for post-redirect-get flows.
+/// Sets the redirect status code and the `location` header, same as `Error::redirect`.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Redirect {
+    mode: RedirectMode,
+    location: Uri,
+}
+
+impl Redirect {
+    pub fn new(mode: RedirectMode, uri: impl Into<Uri>) -> Self {
+        Self {
+            mode,
+            location: uri.into(),
+        }
+    }
+
+    /// `307 Temporary Redirect`
+    pub fn to(uri: impl Into<Uri>) -> Self {
+        Self::new(RedirectMode::TemporaryRedirect, uri)
+    }
+
+    /// `308 Permanent Redirect`
+    pub fn permanent(uri: impl Into<Uri>) -> Self {
+        Self::new(RedirectMode::PermanentRedirect, uri)
+    }
+
+    /// `303 See Other`, the client follows up with a `GET` request.
+    pub fn see_other(uri: impl Into<Uri>) -> Self {
+        Self::new(RedirectMode::SeeOther, uri)
+    }
+}
+
+impl IntoResponse for Redirect {
+    fn into_response(self) -> Result<Response> {
+        (self.mode, self.location).into_response()
+    }
+}
@@ -39,3 +39,13 @@ impl<T: Into<Vec<u8>>> IntoResponse for Html<T> {
         TypedBody(ContentType::html(), self.0.into()).into_response()
     }
 }
+
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct PlainText<T: Into<Vec<u8>>>(pub T);
+
+impl<T: Into<Vec<u8>>> IntoResponse for PlainText<T> {
+    fn into_response(self) -> Result<Response> {
+        TypedBody(ContentType::text_utf8(), self.0.into()).into_response()
+    }
+}
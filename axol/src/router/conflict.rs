@@ -0,0 +1,263 @@
+use std::fmt;
+
+use axol_http::Method;
+
+use super::{split_path_reverse, Router, Segment};
+use crate::HandlerExpansion;
+
+/// A collision between two routers, found by `Router::try_nest` or `Router::try_merge`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteConflict {
+    /// Both routers have a handler for `method` at `path`.
+    Method { path: String, method: Method },
+    /// Both routers have a fallback at `path`.
+    Fallback { path: String },
+    /// The routers name a variable or wildcard differently at the same level, i.e. `/api/:id` and `/api/:name`.
+    SegmentName { path: String, existing: String },
+    /// Both routers have a route of this name.
+    RouteName(String),
+}
+
+impl fmt::Display for RouteConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteConflict::Method { path, method } => {
+                write!(f, "duplicate handler for {method} {path}")
+            }
+            RouteConflict::Fallback { path } => write!(f, "duplicate fallback at {path}"),
+            RouteConflict::SegmentName { path, existing } => {
+                write!(f, "`{path}` conflicts with the existing `{existing}`")
+            }
+            RouteConflict::RouteName(name) => write!(f, "duplicate route name `{name}`"),
+        }
+    }
+}
+
+/// Returned by `Router::try_nest` and `Router::try_merge`, listing every conflict between the routers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub conflicts: Vec<RouteConflict>,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "conflicting routes: ")?;
+        for (i, conflict) in self.conflicts.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{conflict}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MergeConflict {}
+
+/// Returned by the `Router::try_*` registration methods instead of panicking or overwriting a handler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouterError {
+    /// The path conflicts with what is already registered on the router.
+    Conflict(RouteConflict),
+    /// A wildcard segment that isn't the last segment of its path, i.e. `/static/*path/more`.
+    WildcardNotLast { path: String },
+}
+
+impl fmt::Display for RouterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouterError::Conflict(conflict) => write!(f, "{conflict}"),
+            RouterError::WildcardNotLast { path } => {
+                write!(f, "wildcard segment in `{path}` must be the last segment")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouterError {}
+
+impl Router {
+    /// Like `nest`, but fails instead of overwriting handlers or fallbacks of this router, or panicking on mismatched variable names.
+    /// Nothing is nested if there is any conflict. Still panics if `path` has a wildcard segment.
+    pub fn try_nest(self, path: &str, router: Router) -> Result<Self, MergeConflict> {
+        let conflicts = self.nest_conflicts(path, &router);
+        if !conflicts.is_empty() {
+            return Err(MergeConflict { conflicts });
+        }
+        Ok(self.nest(path, router))
+    }
+
+    /// Same as `try_nest` with path = '/'
+    pub fn try_merge(self, router: Router) -> Result<Self, MergeConflict> {
+        self.try_nest("/", router)
+    }
+
+    fn nest_conflicts(&self, path: &str, router: &Router) -> Vec<RouteConflict> {
+        let mut conflicts = vec![];
+        let mut names = Default::default();
+        router.collect_named_routes(&mut vec![], &mut names);
+        for name in names.keys() {
+            if self.find_named_route(name).is_some() {
+                conflicts.push(RouteConflict::RouteName(name.to_string()));
+            }
+        }
+
+        match self.find_segments(split_path_reverse(path)) {
+            Ok((Some(target), mut prefix)) => {
+                target.collect_conflicts(router, &mut prefix, &mut conflicts)
+            }
+            // a new subtree can't conflict with anything
+            Ok((None, _)) => (),
+            Err(RouterError::Conflict(conflict)) => conflicts.push(conflict),
+            // `nest` panics on this, as documented
+            Err(RouterError::WildcardNotLast { .. }) => (),
+        }
+        conflicts
+    }
+
+    /// Walks `segments` (reversed, as from `split_path_reverse`) down the existing tree, failing where `resolve_segments_mut` would panic.
+    /// Returns the node at the end of the path if it already exists, and the path as a prefix, i.e. empty for the root.
+    fn find_segments(
+        &self,
+        mut segments: Vec<Segment>,
+    ) -> Result<(Option<&Router>, String), RouterError> {
+        let full_path = segments
+            .iter()
+            .rev()
+            .map(|x| format!("/{x}"))
+            .collect::<String>();
+        if segments
+            .iter()
+            .skip(1)
+            .any(|x| matches!(x, Segment::Wildcard(_)))
+        {
+            return Err(RouterError::WildcardNotLast { path: full_path });
+        }
+        let mut target = self;
+        let mut path = String::new();
+        while let Some(segment) = segments.pop() {
+            path = format!("{path}/{segment}");
+            match target.find_subpath(&segment) {
+                Ok(Some(subpath)) => target = subpath,
+                Ok(None) => return Ok((None, full_path)),
+                Err(existing) => {
+                    return Err(RouterError::Conflict(RouteConflict::SegmentName {
+                        path,
+                        existing: existing.to_string(),
+                    }))
+                }
+            }
+        }
+        Ok((Some(target), full_path))
+    }
+
+    /// Like `method`, but fails instead of panicking on an invalid path or overwriting an existing handler.
+    pub fn try_method<G: 'static>(
+        self,
+        path: &str,
+        method: Method,
+        route: impl HandlerExpansion<G>,
+    ) -> Result<Self, RouterError> {
+        if let (Some(target), path) = self.find_segments(split_path_reverse(path))? {
+            if target.methods.iter().any(|(x, _)| *x == method) {
+                return Err(RouterError::Conflict(RouteConflict::Method {
+                    path: if path.is_empty() {
+                        "/".to_string()
+                    } else {
+                        path
+                    },
+                    method,
+                }));
+            }
+        }
+        Ok(self.method(path, method, route))
+    }
+
+    pub fn try_get<G: 'static>(
+        self,
+        path: &str,
+        route: impl HandlerExpansion<G>,
+    ) -> Result<Self, RouterError> {
+        self.try_method(path, Method::Get, route)
+    }
+
+    pub fn try_post<G: 'static>(
+        self,
+        path: &str,
+        route: impl HandlerExpansion<G>,
+    ) -> Result<Self, RouterError> {
+        self.try_method(path, Method::Post, route)
+    }
+
+    pub fn try_put<G: 'static>(
+        self,
+        path: &str,
+        route: impl HandlerExpansion<G>,
+    ) -> Result<Self, RouterError> {
+        self.try_method(path, Method::Put, route)
+    }
+
+    pub fn try_delete<G: 'static>(
+        self,
+        path: &str,
+        route: impl HandlerExpansion<G>,
+    ) -> Result<Self, RouterError> {
+        self.try_method(path, Method::Delete, route)
+    }
+
+    pub fn try_patch<G: 'static>(
+        self,
+        path: &str,
+        route: impl HandlerExpansion<G>,
+    ) -> Result<Self, RouterError> {
+        self.try_method(path, Method::Patch, route)
+    }
+
+    /// Finds the subpath `segment` would be merged into. Fails with the existing segment if it is a variable or wildcard of another name.
+    fn find_subpath(&self, segment: &Segment) -> Result<Option<&Router>, &Segment> {
+        for subpath in &self.subpaths {
+            if &subpath.segment == segment {
+                return Ok(Some(subpath));
+            }
+            if matches!(
+                (&subpath.segment, segment),
+                (Segment::Variable(_), Segment::Variable(_))
+                    | (Segment::Wildcard(_), Segment::Wildcard(_))
+            ) {
+                return Err(&subpath.segment);
+            }
+        }
+        Ok(None)
+    }
+
+    fn collect_conflicts(&self, router: &Router, path: &mut String, out: &mut Vec<RouteConflict>) {
+        let display = if path.is_empty() { "/" } else { &**path };
+        for (method, _) in &router.methods {
+            if self.methods.iter().any(|(x, _)| x == method) {
+                out.push(RouteConflict::Method {
+                    path: display.to_string(),
+                    method: *method,
+                });
+            }
+        }
+        if self.fallback.is_some() && router.fallback.is_some() {
+            out.push(RouteConflict::Fallback {
+                path: display.to_string(),
+            });
+        }
+        for subpath in &router.subpaths {
+            let len = path.len();
+            path.push('/');
+            path.push_str(&subpath.segment.to_string());
+            match self.find_subpath(&subpath.segment) {
+                Ok(Some(target)) => target.collect_conflicts(subpath, path, out),
+                Ok(None) => (),
+                Err(existing) => out.push(RouteConflict::SegmentName {
+                    path: path.clone(),
+                    existing: existing.to_string(),
+                }),
+            }
+            path.truncate(len);
+        }
+    }
+}
@@ -1,19 +1,48 @@
-use std::{fmt, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, fmt, panic::AssertUnwindSafe, sync::Arc};
 
 use crate::{
-    EarlyResponseHook, EarlyResponseHookExpansion, Error, ErrorHook, ErrorHookExpansion, Handler,
-    HandlerExpansion, LateResponseHook, LateResponseHookExpansion, MatchedPath, Plugin,
-    RequestHook, RequestHookExpansion, Result, Wrap,
+    EarlyResponseHook, EarlyResponseHookExpansion, Error, ErrorHook, ErrorHookExpansion,
+    FromRequestParts, Handler, HandlerExpansion, IntoResponse, LateResponseHook,
+    LateResponseHookExpansion, MapRequest, MapRequestAsync, MatchedPath, NestDepth, NestedPath,
+    Plugin, RedirectMode, RequestHook, RequestHookExpansion, Result, RouterService, Wrap,
 };
-use axol_http::{response::Response, Extensions, Method};
-use log::warn;
+use anyhow::anyhow;
+use axol_http::{
+    request::{Request, RequestPartsRef},
+    response::Response,
+    Body, Extensions, Method, StatusCode, Uri,
+};
+use futures::future::BoxFuture;
+use log::{error, warn};
+use percent_encoding::{AsciiSet, CONTROLS};
+
+mod conflict;
+pub use conflict::*;
 
 type Route = Arc<dyn Handler>;
+type PanicHandler = Arc<dyn Fn(String) -> Response + Send + Sync>;
+
+/// Characters percent-encoded in path segments generated by `Router::url_for`.
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
 
 #[derive(PartialEq, Clone, Debug)]
 enum Segment {
     Literal(String),
     Variable(Arc<str>),
+    /// A variable only matching segments matching a regex, i.e. `:id(\d+)`.
+    Constrained(Arc<str>, SegmentConstraint),
+    Wildcard(Arc<str>),
 }
 
 impl fmt::Display for Segment {
@@ -21,8 +50,53 @@ impl fmt::Display for Segment {
         match self {
             Segment::Literal(x) => write!(f, "{x}"),
             Segment::Variable(x) => write!(f, ":{x}"),
+            Segment::Constrained(x, constraint) => write!(f, ":{x}({})", constraint.pattern),
+            Segment::Wildcard(x) => write!(f, "*{x}"),
+        }
+    }
+}
+
+/// The regex of a `Segment::Constrained`, matched against the whole percent-decoded segment.
+/// Requires the `path-constraints` feature.
+#[derive(Clone, Debug)]
+struct SegmentConstraint {
+    pattern: String,
+    #[cfg(feature = "path-constraints")]
+    regex: regex::Regex,
+}
+
+impl SegmentConstraint {
+    #[cfg(not(feature = "path-constraints"))]
+    fn new(pattern: &str) -> Self {
+        panic!("path segment constraint `{pattern}` requires the `path-constraints` feature")
+    }
+
+    #[cfg(not(feature = "path-constraints"))]
+    fn matches(&self, _segment: &str) -> bool {
+        unreachable!()
+    }
+
+    #[cfg(feature = "path-constraints")]
+    fn new(pattern: &str) -> Self {
+        let regex = regex::Regex::new(&format!("^(?:{pattern})$"))
+            .unwrap_or_else(|e| panic!("invalid path segment constraint `{pattern}`: {e}"));
+        Self {
+            pattern: pattern.to_string(),
+            regex,
         }
     }
+
+    #[cfg(feature = "path-constraints")]
+    fn matches(&self, segment: &str) -> bool {
+        self.regex
+            .is_match(&percent_encoding::percent_decode_str(segment).decode_utf8_lossy())
+    }
+}
+
+impl PartialEq for SegmentConstraint {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
 }
 
 impl Default for Segment {
@@ -31,6 +105,31 @@ impl Default for Segment {
     }
 }
 
+/// How the router treats a trailing `/` on request paths.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// `/foo` and `/foo/` resolve identically.
+    #[default]
+    Ignore,
+    /// `/foo/` is permanently redirected to `/foo`.
+    RedirectToNoSlash,
+    /// `/foo` is permanently redirected to `/foo/`.
+    RedirectToSlash,
+}
+
+impl TrailingSlash {
+    fn is_canonical(self, path: &str) -> bool {
+        match self {
+            TrailingSlash::Ignore => true,
+            _ if path == "/" || path.is_empty() => true,
+            TrailingSlash::RedirectToNoSlash => !path.ends_with('/'),
+            TrailingSlash::RedirectToSlash => path.ends_with('/'),
+        }
+    }
+}
+
+/// Builder methods taking a path panic on invalid or conflicting paths, as routes are usually fixed at startup.
+/// Use `try_method`, `try_nest` and `try_merge` when routes come from configuration.
 #[derive(Default, Clone)]
 pub struct Router {
     segment: Segment,
@@ -41,10 +140,19 @@ pub struct Router {
     early_response_hooks: Vec<Arc<dyn EarlyResponseHook>>,
     late_response_hooks: Vec<Arc<dyn LateResponseHook>>,
     error_hooks: Vec<Arc<dyn ErrorHook>>,
+    default_error_hook: Option<Arc<dyn ErrorHook>>,
     wraps: Vec<Arc<dyn Wrap>>,
     outer_wraps: Vec<Arc<dyn Wrap>>,
     fallback: Option<Route>,
+    global_fallback: Option<Route>,
+    no_inherit_fallback: bool,
     extensions: Extensions,
+    state: Extensions,
+    trailing_slash: TrailingSlash,
+    panic_handler: Option<PanicHandler>,
+    coalesce_headers: bool,
+    route_names: Vec<Arc<str>>,
+    named_routes: Arc<HashMap<Arc<str>, Vec<Segment>>>,
 }
 
 impl fmt::Debug for Router {
@@ -58,15 +166,62 @@ impl fmt::Debug for Router {
             .field("early_response_hooks", &self.early_response_hooks.len())
             .field("late_response_hooks", &self.late_response_hooks.len())
             .field("error_hooks", &self.error_hooks.len())
+            .field("default_error_hook", &self.default_error_hook.is_some())
             .field("wraps", &self.wraps.len())
             .field("fallback", &self.fallback.is_some())
+            .field("global_fallback", &self.global_fallback.is_some())
+            .field("no_inherit_fallback", &self.no_inherit_fallback)
             .field("extensions", &self.extensions)
+            .field("state", &self.state)
+            .field("trailing_slash", &self.trailing_slash)
+            .field("panic_handler", &self.panic_handler.is_some())
+            .field("coalesce_headers", &self.coalesce_headers)
+            .field("route_names", &self.route_names)
             .finish()
     }
 }
 
+/// Prints the route table sorted by path, one path per line.
+impl fmt::Display for Router {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut routes = self.routes();
+        routes.sort_by(|a, b| a.path.cmp(&b.path));
+        let methods = routes
+            .iter()
+            .map(|route| {
+                route
+                    .methods
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        let width = methods.iter().map(|x| x.len()).max().unwrap_or_default();
+        for (route, methods) in routes.iter().zip(methods) {
+            write!(f, "{methods:<width$} {}", route.path)?;
+            if route.has_fallback {
+                write!(f, " (fallback)")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 pub struct PathVariables(pub Vec<(Arc<str>, String)>);
 
+/// A path registered on a `Router`, as listed by `Router::routes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// The fully-resolved path pattern, i.e. `/api/:id/items`.
+    pub path: String,
+    /// Methods with a handler registered at this path.
+    pub methods: Vec<Method>,
+    /// Whether a fallback is registered at this path.
+    pub has_fallback: bool,
+}
+
 fn split_path_reverse(path: &str) -> Vec<Segment> {
     path.trim()
         .split('/')
@@ -74,8 +229,16 @@ fn split_path_reverse(path: &str) -> Vec<Segment> {
         .map(|x| x.trim())
         .rev()
         .map(|x| {
-            if x.starts_with(':') {
-                Segment::Variable(x[1..].to_string().into())
+            if let Some((name, pattern)) = x
+                .strip_prefix(':')
+                .and_then(|x| x.strip_suffix(')'))
+                .and_then(|x| x.split_once('('))
+            {
+                Segment::Constrained(name.into(), SegmentConstraint::new(pattern))
+            } else if let Some(name) = x.strip_prefix(':') {
+                Segment::Variable(name.to_string().into())
+            } else if let Some(name) = x.strip_prefix('*') {
+                Segment::Wildcard(name.to_string().into())
             } else {
                 Segment::Literal(x.to_string())
             }
@@ -95,25 +258,85 @@ async fn default_route() -> Result<Response> {
     Err(Error::NotFound)
 }
 
+async fn method_not_allowed_route() -> Result<Response> {
+    Err(Error::MethodNotAllowed)
+}
+
 lazy_static::lazy_static! {
     static ref DEFAULT_ROUTE: Arc<dyn Handler> = {
         let route: Box<dyn HandlerExpansion<()>> = Box::new(default_route);
         let handler: Arc<dyn Handler> = Arc::new(route);
         handler
     };
+    static ref METHOD_NOT_ALLOWED_ROUTE: Arc<dyn Handler> = {
+        let route: Box<dyn HandlerExpansion<()>> = Box::new(method_not_allowed_route);
+        let handler: Arc<dyn Handler> = Arc::new(route);
+        handler
+    };
+    static ref TRAILING_SLASH_ROUTE: Arc<dyn Handler> = Arc::new(TrailingSlashRedirect);
+}
+
+/// Redirects to the canonical form of the raw request path, according to the `TrailingSlash` extension.
+struct TrailingSlashRedirect;
+
+#[async_trait::async_trait]
+impl Handler for TrailingSlashRedirect {
+    async fn call<'a>(&self, request: RequestPartsRef<'a>, _body: Body) -> Result<Response> {
+        let mode = request
+            .extensions
+            .get::<TrailingSlash>()
+            .copied()
+            .unwrap_or_default();
+        // use the raw path so that percent-encoding is preserved
+        let path = request.uri.path().trim_end_matches('/');
+        let mut target = match mode {
+            TrailingSlash::RedirectToSlash => format!("{path}/"),
+            _ if path.is_empty() => "/".to_string(),
+            _ => path.to_string(),
+        };
+        if let Some(query) = request.uri.query() {
+            target.push('?');
+            target.push_str(query);
+        }
+        let target = Uri::try_from(target).map_err(Error::internal)?;
+        Err(Error::redirect(RedirectMode::PermanentRedirect, target))
+    }
+}
+
+/// Name of the wildcard segment `Router::nest_service` registers below its prefix.
+const NESTED_SERVICE_WILDCARD: &str = "nested_path";
+
+/// Inserts the `NestedPath` extension before calling a handler mounted with `Router::nest_service`.
+struct NestedService(Arc<dyn Handler>);
+
+#[async_trait::async_trait]
+impl Handler for NestedService {
+    async fn call<'a>(&self, request: RequestPartsRef<'a>, body: Body) -> Result<Response> {
+        let nested = NestedPath::from_request_parts(request).await?;
+        request.extensions.insert(nested);
+        self.0.call(request, body).await
+    }
 }
 
 pub struct ObservedRoute<'a> {
     pub route: &'a Route,
     pub extensions: Extensions,
+    pub state: Extensions,
     pub variables: PathVariables,
     //TODO: clean these up to not clone arcs
     pub request_hooks: Vec<Arc<dyn RequestHook>>,
     pub error_hooks: Vec<Arc<dyn ErrorHook>>,
+    /// Replaces `DefaultErrorHook` when no error hook returned a response, see `Router::default_error_hook`.
+    pub default_error_hook: Option<Arc<dyn ErrorHook>>,
     pub early_response_hooks: Vec<Arc<dyn EarlyResponseHook>>,
     pub late_response_hooks: Vec<Arc<dyn LateResponseHook>>,
     pub wraps: Vec<Arc<dyn Wrap>>,
     pub outer_wraps: Vec<Arc<dyn Wrap>>,
+    /// Methods registered at the matched path node, if the path matched a node with handlers but not for this method.
+    /// Used to populate the `Allow` header of `405 Method Not Allowed` responses.
+    pub allowed_methods: Vec<Method>,
+    /// Set once a miss passed through a node registered with `Router::no_inherit_fallback`, so ancestor and global fallbacks are skipped.
+    fallback_cut: bool,
 }
 
 impl<'a> ObservedRoute<'a> {
@@ -124,16 +347,54 @@ impl<'a> ObservedRoute<'a> {
             error_hooks: self.error_hooks.len(),
             early_response_hooks: self.early_response_hooks.len(),
             late_response_hooks: self.late_response_hooks.len(),
+            wraps: self.wraps.len(),
+            outer_wraps: self.outer_wraps.len(),
+            extensions: copy_extensions(&self.extensions),
+            state: copy_extensions(&self.state),
+            allowed_methods: self.allowed_methods.clone(),
         }
     }
 
-    fn reset(&mut self, check: ObservedRouteCheck) {
-        self.variables.0.truncate(check.variables);
-        self.request_hooks.truncate(check.request_hooks);
-        self.error_hooks.truncate(check.error_hooks);
-        self.early_response_hooks
-            .truncate(check.early_response_hooks);
-        self.late_response_hooks.truncate(check.late_response_hooks);
+    /// Undoes everything observed since `check`, returning it so it can be put back with `restore`.
+    fn rewind(&mut self, check: ObservedRouteCheck) -> ObservedRouteTail {
+        ObservedRouteTail {
+            variables: self.variables.0.split_off(check.variables),
+            request_hooks: self.request_hooks.split_off(check.request_hooks),
+            error_hooks: self.error_hooks.split_off(check.error_hooks),
+            early_response_hooks: self
+                .early_response_hooks
+                .split_off(check.early_response_hooks),
+            late_response_hooks: self
+                .late_response_hooks
+                .split_off(check.late_response_hooks),
+            wraps: self.wraps.split_off(check.wraps),
+            outer_wraps: self.outer_wraps.split_off(check.outer_wraps),
+            extensions: std::mem::replace(&mut self.extensions, check.extensions),
+            state: std::mem::replace(&mut self.state, check.state),
+            allowed_methods: std::mem::replace(&mut self.allowed_methods, check.allowed_methods),
+        }
+    }
+
+    fn restore(&mut self, tail: ObservedRouteTail) {
+        self.variables.0.extend(tail.variables);
+        self.request_hooks.extend(tail.request_hooks);
+        self.error_hooks.extend(tail.error_hooks);
+        self.early_response_hooks.extend(tail.early_response_hooks);
+        self.late_response_hooks.extend(tail.late_response_hooks);
+        self.wraps.extend(tail.wraps);
+        self.outer_wraps.extend(tail.outer_wraps);
+        self.extensions = tail.extensions;
+        self.state = tail.state;
+        self.allowed_methods = tail.allowed_methods;
+    }
+
+    /// Undoes a branch that didn't match. The first branch that matched the path but not the method is kept in `mismatch`,
+    /// to answer with its `405 Method Not Allowed` (and middleware) if no other branch matches.
+    fn backtrack(&mut self, check: ObservedRouteCheck, mismatch: &mut Option<ObservedRouteTail>) {
+        let tail = self.rewind(check);
+        if mismatch.is_none() && !tail.allowed_methods.is_empty() {
+            *mismatch = Some(tail);
+        }
     }
 }
 
@@ -143,6 +404,33 @@ struct ObservedRouteCheck {
     error_hooks: usize,
     early_response_hooks: usize,
     late_response_hooks: usize,
+    wraps: usize,
+    outer_wraps: usize,
+    /// `Extensions` can't be truncated, so these are copies taken at the check
+    extensions: Extensions,
+    state: Extensions,
+    allowed_methods: Vec<Method>,
+}
+
+/// What was observed in a branch after an `ObservedRouteCheck`.
+struct ObservedRouteTail {
+    variables: Vec<(Arc<str>, String)>,
+    request_hooks: Vec<Arc<dyn RequestHook>>,
+    error_hooks: Vec<Arc<dyn ErrorHook>>,
+    early_response_hooks: Vec<Arc<dyn EarlyResponseHook>>,
+    late_response_hooks: Vec<Arc<dyn LateResponseHook>>,
+    wraps: Vec<Arc<dyn Wrap>>,
+    outer_wraps: Vec<Arc<dyn Wrap>>,
+    extensions: Extensions,
+    state: Extensions,
+    allowed_methods: Vec<Method>,
+}
+
+/// Copies `extensions` into a new map, as clones of `Extensions` share their contents.
+fn copy_extensions(extensions: &Extensions) -> Extensions {
+    let out = Extensions::default();
+    out.extend(extensions);
+    out
 }
 
 impl Router {
@@ -154,24 +442,138 @@ impl Router {
         let mut out = ObservedRoute {
             route: &DEFAULT_ROUTE,
             extensions: Extensions::default(),
+            state: Extensions::default(),
             variables: PathVariables(vec![]),
             request_hooks: vec![],
             error_hooks: vec![],
+            default_error_hook: self.default_error_hook.clone(),
             early_response_hooks: vec![],
             late_response_hooks: vec![],
             wraps: vec![],
             outer_wraps: vec![],
+            allowed_methods: vec![],
+            fallback_cut: false,
         };
-        if let Some(route) = self.do_resolve_path(&mut out, method, &split_raw_path(path)) {
+        if let Some(route) = self.do_resolve_path(&mut out, method, path, &split_raw_path(path)) {
             out.route = &*route;
+            if !self.trailing_slash.is_canonical(path) {
+                out.route = &TRAILING_SLASH_ROUTE;
+                out.extensions.insert(self.trailing_slash);
+            }
+        } else if !out.allowed_methods.is_empty() {
+            out.route = &METHOD_NOT_ALLOWED_ROUTE;
+        } else if out.fallback_cut {
+            // keep the default 404
+        } else if let Some(route) = &self.global_fallback {
+            out.route = route;
         }
         out
     }
 
-    fn do_resolve_path<'a>(
+    /// Sets how trailing slashes on request paths are treated. Only the setting on the root router is used.
+    pub fn trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    /// Sets the function rendering the response when a handler or middleware panics, given the panic message.
+    /// Defaults to an empty `500 Internal Server Error`, which is also used if `handler` itself panics.
+    /// Only the setting on the root router is used.
+    pub fn panic_handler(
+        mut self,
+        handler: impl Fn(String) -> Response + Send + Sync + 'static,
+    ) -> Self {
+        self.panic_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Whether to combine multiple values of the same response header into a single comma-separated header line,
+    /// for clients that handle combined headers better. `set-cookie` is always sent as separate lines. Defaults to `false`.
+    /// Only the setting on the root router is used.
+    pub fn coalesce_headers(mut self, coalesce_headers: bool) -> Self {
+        self.coalesce_headers = coalesce_headers;
+        self
+    }
+
+    pub(crate) fn coalesces_headers(&self) -> bool {
+        self.coalesce_headers
+    }
+
+    /// Converts this router into a hyper/tower `Service`, for use outside of `Server`.
+    pub fn into_service(self) -> RouterService {
+        RouterService::new(self)
+    }
+
+    /// Creates an in-process client for testing this router without binding a socket.
+    #[cfg(feature = "test-util")]
+    pub fn test_client(self) -> crate::TestClient {
+        crate::TestClient::new(self)
+    }
+
+    /// Lists every path with a handler or fallback registered, in registration order.
+    /// Can be called at any point before the router is served.
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        let mut out = vec![];
+        self.collect_routes("", &mut out);
+        out
+    }
+
+    fn collect_routes(&self, parent: &str, out: &mut Vec<RouteInfo>) {
+        let path = match &self.segment {
+            Segment::Literal(x) if x.is_empty() => parent.to_string(),
+            segment => format!("{parent}/{segment}"),
+        };
+        if !self.methods.is_empty() || self.fallback.is_some() {
+            out.push(RouteInfo {
+                path: if path.is_empty() {
+                    "/".to_string()
+                } else {
+                    path.clone()
+                },
+                methods: self.methods.iter().map(|x| x.0).collect(),
+                has_fallback: self.fallback.is_some(),
+            });
+        }
+        for subpath in &self.subpaths {
+            subpath.collect_routes(&path, out);
+        }
+    }
+
+    pub(crate) fn panic_response(&self, message: String) -> Response {
+        let fallback = || StatusCode::InternalServerError.into_response().unwrap();
+        let Some(handler) = &self.panic_handler else {
+            return fallback();
+        };
+        std::panic::catch_unwind(AssertUnwindSafe(|| handler(message))).unwrap_or_else(|_| {
+            error!("panic during panic handler");
+            fallback()
+        })
+    }
+
+    fn do_resolve_path(
         &self,
         observed: &mut ObservedRoute<'_>,
         method: Method,
+        path: &str,
+        segments: &[&str],
+    ) -> Option<&Route> {
+        // a cut from a previously tried sibling subtree doesn't apply to this subtree's fallbacks
+        let outer_cut = std::mem::take(&mut observed.fallback_cut);
+        let route = self.resolve_node(observed, method, path, segments);
+        if route.is_none() && self.no_inherit_fallback {
+            observed.fallback_cut = true;
+        }
+        observed.fallback_cut |= outer_cut;
+        route
+    }
+
+    /// Resolves `segments` below this node, falling back to this node's fallback on a miss.
+    /// `segments` are slices of `path`, the raw request path.
+    fn resolve_node(
+        &self,
+        observed: &mut ObservedRoute<'_>,
+        method: Method,
+        path: &str,
         segments: &[&str],
     ) -> Option<&Route> {
         observed
@@ -191,6 +593,7 @@ impl Router {
             .outer_wraps
             .extend(self.outer_wraps.iter().cloned());
         observed.extensions.extend(&self.extensions);
+        observed.state.extend(&self.state);
         let Some(segment) = segments.first() else {
             observed.extensions.insert(MatchedPath(self.routed_path.clone()));
             if let Some((_, route)) = self.methods.iter().find(|x| x.0 == method) {
@@ -201,32 +604,48 @@ impl Router {
                     return Some(route);
                 }
             }
+            if self.fallback.is_none() {
+                observed.allowed_methods = self.allowed_methods();
+            }
             return self.fallback.as_ref();
         };
         // find existing segment
+        let mut mismatch = None;
+        let mut constrained_subpaths: Vec<&Router> = vec![];
         let mut variable_subpath: Option<&Router> = None;
+        let mut wildcard_subpath: Option<&Router> = None;
         for subpath in self.subpaths.iter() {
             match &subpath.segment {
                 Segment::Literal(literal) => {
                     if literal == segment {
                         let check = observed.check();
                         if let Some(route) =
-                            subpath.do_resolve_path(observed, method, &segments[1..])
+                            subpath.do_resolve_path(observed, method, path, &segments[1..])
                         {
                             return Some(route);
                         }
-                        observed.reset(check);
+                        observed.backtrack(check, &mut mismatch);
+                    }
+                }
+                Segment::Constrained(_, constraint) => {
+                    if constraint.matches(segment) {
+                        constrained_subpaths.push(subpath);
                     }
                 }
                 Segment::Variable(_) => {
                     variable_subpath = Some(subpath);
                     // we delay using the variable path in case there is a literal that supersedes it below
                 }
+                Segment::Wildcard(_) => {
+                    // wildcards are only considered once no literal or variable matched
+                    wildcard_subpath = Some(subpath);
+                }
             }
         }
-        if let Some(subpath) = variable_subpath {
+        // literals take precedence, then constrained variables in registration order, then the unconstrained variable, then the wildcard
+        for subpath in constrained_subpaths.into_iter().chain(variable_subpath) {
             let name = match &subpath.segment {
-                Segment::Variable(x) => x,
+                Segment::Variable(x) | Segment::Constrained(x, _) => x,
                 _ => unreachable!(),
             };
             let check = observed.check();
@@ -234,19 +653,53 @@ impl Router {
                 .variables
                 .0
                 .push((name.clone(), segment.to_string()));
-            if let Some(route) = subpath.do_resolve_path(observed, method, &segments[1..]) {
+            if let Some(route) = subpath.do_resolve_path(observed, method, path, &segments[1..]) {
+                return Some(route);
+            }
+            observed.backtrack(check, &mut mismatch);
+        }
+        if let Some(subpath) = wildcard_subpath {
+            let name = match &subpath.segment {
+                Segment::Wildcard(x) => x,
+                _ => unreachable!(),
+            };
+            // capture the raw rest of the path, keeping empty segments and a trailing slash
+            let offset = segment.as_ptr() as usize - path.as_ptr() as usize;
+            let rest = path.trim_end()[offset..].to_string();
+            let check = observed.check();
+            observed.variables.0.push((name.clone(), rest));
+            if let Some(route) = subpath.do_resolve_path(observed, method, path, &[]) {
                 return Some(route);
             }
-            observed.reset(check);
+            observed.backtrack(check, &mut mismatch);
         }
 
+        if let Some(mismatch) = mismatch {
+            // the path matched a node, so a method mismatch takes precedence over ancestor fallbacks
+            observed.restore(mismatch);
+            return None;
+        }
+        if observed.fallback_cut {
+            return None;
+        }
         self.fallback.as_ref()
     }
 
+    fn allowed_methods(&self) -> Vec<Method> {
+        let mut out = self.methods.iter().map(|x| x.0).collect::<Vec<_>>();
+        if out.contains(&Method::Get) && !out.contains(&Method::Head) {
+            out.push(Method::Head);
+        }
+        out
+    }
+
     fn resolve_segments_mut(&mut self, mut segments: Vec<Segment>) -> &mut Router {
         let Some(segment) = segments.pop() else {
             return self;
         };
+        if matches!(segment, Segment::Wildcard(_)) && !segments.is_empty() {
+            panic!("wildcard segment `{segment}` must be the last segment of a path. i.e. `/static/*path/more` is invalid");
+        }
         // find existing segment
         let mut subpath_index = None::<usize>;
         for (i, subpath) in self.subpaths.iter().enumerate() {
@@ -268,6 +721,16 @@ impl Router {
         {
             panic!("each routing level at the same superpath must use the same variable name. i.e. `/api/:var` and `/api/:variable` are invalid");
         }
+        if matches!(segment, Segment::Wildcard(_))
+            && self
+                .subpaths
+                .iter()
+                .filter(|x| matches!(x.segment, Segment::Wildcard(_)))
+                .count()
+                > 0
+        {
+            panic!("each routing level at the same superpath must use the same wildcard name. i.e. `/static/*path` and `/static/*file` are invalid");
+        }
         let mut subrouter = Router::new();
         subrouter.segment = segment;
         self.subpaths.push(subrouter);
@@ -277,19 +740,109 @@ impl Router {
             .resolve_segments_mut(segments)
     }
 
-    pub(crate) fn set_paths(&mut self, mut path: &str) {
+    pub(crate) fn set_paths(&mut self, path: &str) {
+        self.set_routed_paths(path);
+        let mut named_routes = HashMap::new();
+        self.collect_named_routes(&mut vec![], &mut named_routes);
+        self.named_routes = Arc::new(named_routes);
+    }
+
+    fn set_routed_paths(&mut self, mut path: &str) {
         while path.ends_with('/') {
             path = &path[..path.len() - 1];
         }
         self.routed_path = Arc::new(format!("{path}/{}", self.segment));
         for child in &mut self.subpaths {
-            child.set_paths(&self.routed_path);
+            child.set_routed_paths(&self.routed_path);
         }
     }
 
+    fn collect_named_routes(
+        &self,
+        prefix: &mut Vec<Segment>,
+        out: &mut HashMap<Arc<str>, Vec<Segment>>,
+    ) {
+        for name in &self.route_names {
+            out.insert(name.clone(), prefix.clone());
+        }
+        for subpath in &self.subpaths {
+            prefix.push(subpath.segment.clone());
+            subpath.collect_named_routes(prefix, out);
+            prefix.pop();
+        }
+    }
+
+    fn find_named_route(&self, name: &str) -> Option<Vec<Segment>> {
+        if self.route_names.iter().any(|x| &**x == name) {
+            return Some(vec![]);
+        }
+        for subpath in &self.subpaths {
+            if let Some(mut segments) = subpath.find_named_route(name) {
+                segments.insert(0, subpath.segment.clone());
+                return Some(segments);
+            }
+        }
+        None
+    }
+
+    /// Builds the path of the route registered as `name` with `Router::route_named`,
+    /// substituting `params` by name into its variable and wildcard segments.
+    /// Values are percent-encoded, except for the `/` separators of wildcard values.
+    pub fn url_for(&self, name: &str, params: &[(&str, &str)]) -> Result<String> {
+        let segments = match self.named_routes.get(name) {
+            Some(segments) => Cow::Borrowed(segments),
+            None => Cow::Owned(
+                self.find_named_route(name)
+                    .ok_or_else(|| Error::internal(anyhow!("no route named `{name}`")))?,
+            ),
+        };
+        if let Some((key, _)) = params.iter().find(|(key, _)| {
+            !segments.iter().any(|segment| {
+                matches!(segment, Segment::Variable(x) | Segment::Constrained(x, _) | Segment::Wildcard(x) if &**x == *key)
+            })
+        }) {
+            return Err(Error::internal(anyhow!(
+                "unexpected parameter `{key}` for route `{name}`"
+            )));
+        }
+        let mut out = String::new();
+        for segment in segments.iter() {
+            out.push('/');
+            let (Segment::Variable(key) | Segment::Constrained(key, _) | Segment::Wildcard(key)) =
+                segment
+            else {
+                out.push_str(&segment.to_string());
+                continue;
+            };
+            let Some((_, value)) = params.iter().find(|(x, _)| *x == &**key) else {
+                return Err(Error::internal(anyhow!(
+                    "missing parameter `{key}` for route `{name}`"
+                )));
+            };
+            if matches!(segment, Segment::Wildcard(_)) {
+                let value = value
+                    .split('/')
+                    .map(|x| percent_encoding::utf8_percent_encode(x, PATH_SEGMENT).to_string())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                out.push_str(&value);
+            } else {
+                out.extend(percent_encoding::utf8_percent_encode(value, PATH_SEGMENT));
+            }
+        }
+        if out.is_empty() {
+            out.push('/');
+        }
+        Ok(out)
+    }
+
     fn append_segment(&mut self, segments: Vec<Segment>, method: Method, route: Route) {
-        let target = self.resolve_segments_mut(segments);
-        if let Some(handler) = target
+        self.resolve_segments_mut(segments)
+            .append_method(method, route);
+    }
+
+    fn append_method(&mut self, method: Method, route: Route) {
+        if let Some(handler) = self
             .methods
             .iter_mut()
             .find(|(current_method, _)| current_method == &method)
@@ -297,10 +850,20 @@ impl Router {
             warn!("overwriting route for method {method}");
             handler.1 = route;
         } else {
-            target.methods.push((method, route));
+            self.methods.push((method, route));
         }
     }
 
+    /// Registers `route` for `method` at `path`, overwriting any existing handler for that method.
+    ///
+    /// A variable may be constrained by a regex matching the whole percent-decoded segment, i.e. `/users/:id(\d+)`.
+    /// Any number of constrained variables may share a level with each other and one unconstrained variable.
+    /// Literals are tried first, then constrained variables in registration order, then the unconstrained variable, then a wildcard.
+    ///
+    /// # Panics
+    /// If a wildcard isn't the last segment of `path`, a constraint isn't a valid regex, or an unconstrained variable or wildcard name
+    /// differs from one already registered at the same position, i.e. `/users/:id` and `/users/:user_id`.
+    /// See `try_method` for a non-panicking variant.
     pub fn method<G: 'static>(
         mut self,
         path: &str,
@@ -313,6 +876,27 @@ impl Router {
         self
     }
 
+    /// Same as `method`, additionally naming the route so its path can be built with `Router::url_for`.
+    ///
+    /// # Panics
+    /// If the route name is already in use, or in the same cases as `method`.
+    pub fn route_named<G: 'static>(
+        mut self,
+        name: &str,
+        path: &str,
+        method: Method,
+        route: impl HandlerExpansion<G>,
+    ) -> Self {
+        if self.find_named_route(name).is_some() {
+            panic!("route name `{name}` is already in use");
+        }
+        let route: Box<dyn HandlerExpansion<G>> = Box::new(route);
+        let target = self.resolve_segments_mut(split_path_reverse(path));
+        target.append_method(method, Arc::new(route));
+        target.route_names.push(name.into());
+        self
+    }
+
     pub fn get<G: 'static>(self, path: &str, route: impl HandlerExpansion<G>) -> Self {
         self.method(path, Method::Get, route)
     }
@@ -349,6 +933,10 @@ impl Router {
         self.method(path, Method::Trace, route)
     }
 
+    /// Sets the handler for requests at or below `path` that match no route.
+    /// A miss uses the fallback of the deepest node on the request's path that has one, so fallbacks are inherited by descendants,
+    /// including paths that don't exist below `path`. A method mismatch at a matched path returns `405 Method Not Allowed` instead.
+    /// See `no_inherit_fallback` to stop the inheritance.
     pub fn fallback<G: 'static>(mut self, path: &str, fallback: impl HandlerExpansion<G>) -> Self {
         let segments = split_path_reverse(path);
         let fallback: Box<dyn HandlerExpansion<G>> = Box::new(fallback);
@@ -363,6 +951,56 @@ impl Router {
         self
     }
 
+    /// Stops requests below `path` that match no route from using the fallbacks of `path`'s ancestors or the global fallback,
+    /// producing the default `404 Not Found` instead unless `path` or a descendant has its own fallback.
+    /// i.e. to keep `/api` misses out of a single page app's fallback at `/`.
+    pub fn no_inherit_fallback(mut self, path: &str) -> Self {
+        self.resolve_segments_mut(split_path_reverse(path))
+            .no_inherit_fallback = true;
+        self
+    }
+
+    /// Sets a catch-all handler used only when no path node or `fallback` matched the request at all,
+    /// i.e. to serve a single page app's `index.html` while `/api` keeps its own fallback.
+    /// Precedence is: fallback at the matched node, then fallbacks of its ancestors, then the global fallback.
+    /// A method mismatch at a matched path still returns `405 Method Not Allowed`.
+    /// Only the setting on the root router is used.
+    pub fn global_fallback<G: 'static>(mut self, fallback: impl HandlerExpansion<G>) -> Self {
+        let fallback: Box<dyn HandlerExpansion<G>> = Box::new(fallback);
+        let handler: Arc<dyn Handler> = Arc::new(fallback);
+        self.global_fallback = Some(handler);
+        self
+    }
+
+    /// Mounts `handler` at `path` and every path below it, for any method. i.e. for a reverse proxy.
+    /// The handler can read the rest of the request path below `path` from the `NestedPath` extension.
+    ///
+    /// The handler is registered as the fallback of `path` and of a `*nested_path` wildcard below it,
+    /// so routes registered below `path` still take precedence. It replaces any fallback already registered at `path`.
+    pub fn nest_service(mut self, path: &str, handler: impl Handler) -> Self {
+        let handler: Route = Arc::new(NestedService(Arc::new(handler)));
+        let mut segments = split_path_reverse(path);
+        let target = self.resolve_segments_mut(segments.clone());
+        if target.fallback.is_some() {
+            warn!("overwriting fallback at {path} with a nested service");
+        }
+        target.fallback = Some(handler.clone());
+        target.extensions.insert(NestDepth(segments.len()));
+        // segments are reversed, so the wildcard goes first to be the last segment
+        segments.insert(0, Segment::Wildcard(NESTED_SERVICE_WILDCARD.into()));
+        self.resolve_segments_mut(segments).fallback = Some(handler);
+        self
+    }
+
+    /// Registers state for the `State<S>` extractor. State is kept apart from the request `Extensions`, and is carried along by `nest`/`merge`.
+    pub fn with_state<S: Clone + Send + Sync + 'static>(mut self, state: S) -> Self {
+        // state is shared between clones of a router, so don't modify it in place
+        let copy = copy_extensions(&self.state);
+        copy.insert(state);
+        self.state = copy;
+        self
+    }
+
     pub fn extension<T: Send + Sync + 'static>(mut self, path: &str, extension: T) -> Self {
         let segments = split_path_reverse(path);
         let target = self.resolve_segments_mut(segments);
@@ -375,6 +1013,15 @@ impl Router {
         self.error_hook_direct(path, hook)
     }
 
+    /// Replaces `DefaultErrorHook` as the final error hook, used when no error hook at the matched path returned a response.
+    /// `Error::Internal` is still logged via `log::error` before `hook` is called.
+    /// If `hook` returns `None`, the error's default response is used. Only the setting on the root router is used.
+    pub fn default_error_hook(mut self, hook: impl ErrorHook) -> Self {
+        let hook: Arc<dyn ErrorHook> = Arc::new(hook);
+        self.default_error_hook = Some(hook);
+        self
+    }
+
     pub fn request_hook<G: 'static>(self, path: &str, hook: impl RequestHookExpansion<G>) -> Self {
         let hook: Box<dyn RequestHookExpansion<G>> = Box::new(hook);
         self.request_hook_direct(path, hook)
@@ -398,17 +1045,37 @@ impl Router {
         self.late_response_hook_direct(path, hook)
     }
 
-    pub fn wrap(mut self, path: &str, hook: impl Wrap) -> Self {
+    /// Wraps the handlers at `path` and below.
+    ///
+    /// Middleware runs in this order for each request: outer wraps, request hooks, wraps, the handler,
+    /// then early response hooks (or error hooks on errors) and late response hooks.
+    /// Middleware registered on a parent path runs before middleware on its child paths, and wraps on a parent path enclose those on child paths.
+    /// On the same path, middleware runs in registration order, so the first registered wrap is the outermost.
+    /// Routers added with `nest` or `merge` keep their middleware, running after the middleware already on the path they are nested at.
+    pub fn wrap(self, path: &str, hook: impl Wrap) -> Self {
+        self.wrap_direct(path, Arc::new(hook))
+    }
+
+    /// Same as `wrap`, for an already shared wrap, i.e. one registered on several paths.
+    pub fn wrap_direct(mut self, path: &str, hook: Arc<dyn Wrap>) -> Self {
         let segments = split_path_reverse(path);
-        let hook: Arc<dyn Wrap> = Arc::new(hook);
         let target = self.resolve_segments_mut(segments);
         target.wraps.push(hook);
         self
     }
 
-    pub fn outer_wrap(mut self, path: &str, hook: impl Wrap) -> Self {
+    /// Wraps everything after routing for requests to `path` and below: request hooks, wraps and the handler.
+    /// Use this for instrumentation that should cover the whole request, like `Trace` does.
+    ///
+    /// Outer wraps are ordered among themselves like `wrap`.
+    /// Early and late response hooks and error hooks still run outside of them.
+    pub fn outer_wrap(self, path: &str, hook: impl Wrap) -> Self {
+        self.outer_wrap_direct(path, Arc::new(hook))
+    }
+
+    /// Same as `outer_wrap`, for an already shared wrap.
+    pub fn outer_wrap_direct(mut self, path: &str, hook: Arc<dyn Wrap>) -> Self {
         let segments = split_path_reverse(path);
-        let hook: Arc<dyn Wrap> = Arc::new(hook);
         let target = self.resolve_segments_mut(segments);
         target.outer_wraps.push(hook);
         self
@@ -446,13 +1113,47 @@ impl Router {
         self
     }
 
+    /// Runs `map` on every request at `path` and below, i.e. to set a header or insert an extension.
+    /// Processing always continues afterwards, register a `RequestHook` to respond early instead.
+    pub fn map_request<F>(self, path: &str, map: F) -> Self
+    where
+        F: Fn(&mut Request) + Send + Sync + 'static,
+    {
+        self.request_hook_direct(path, MapRequest(map))
+    }
+
+    /// Async version of `map_request`:
+    /// ```ignore
+    /// router.map_request_async("/", |request| Box::pin(async move { ... }))
+    /// ```
+    pub fn map_request_async<F>(self, path: &str, map: F) -> Self
+    where
+        F: for<'a> Fn(&'a mut Request) -> BoxFuture<'a, ()> + Send + Sync + 'static,
+    {
+        self.request_hook_direct(path, MapRequestAsync(map))
+    }
+
     pub fn plugin(self, path: &str, hook: impl Plugin) -> Self {
         hook.apply(self, path)
     }
 
-    pub fn nest(mut self, path: &str, router: Router) -> Self {
+    /// Mounts `router` at `path`. Handlers below it can read the request path consumed by `path`
+    /// from the `NestedPrefix` extractor, and the rest from `NestedPath`.
+    pub fn nest(mut self, path: &str, mut router: Router) -> Self {
         let segments = split_path_reverse(path);
+        let depth = segments.len();
+        let mut names = HashMap::new();
+        router.collect_named_routes(&mut vec![], &mut names);
+        for name in names.keys() {
+            if self.find_named_route(name).is_some() {
+                warn!("route name `{name}` is already in use, `url_for` may build either route");
+            }
+        }
         let target = self.resolve_segments_mut(segments);
+        if depth > 0 {
+            router.offset_nest_depth(depth);
+            target.extensions.insert(NestDepth(depth));
+        }
         target.do_merge(router);
         self
     }
@@ -462,6 +1163,19 @@ impl Router {
         self.nest("/", router)
     }
 
+    /// Shifts the `NestDepth` of nests within this router, as it is nested `depth` segments below the root of another.
+    fn offset_nest_depth(&mut self, depth: usize) {
+        if let Some(NestDepth(inner)) = self.extensions.get_cloned::<NestDepth>() {
+            // extensions are shared between clones of a router, so don't modify them in place
+            let extensions = copy_extensions(&self.extensions);
+            extensions.insert(NestDepth(inner + depth));
+            self.extensions = extensions;
+        }
+        for subpath in &mut self.subpaths {
+            subpath.offset_nest_depth(depth);
+        }
+    }
+
     fn do_merge(&mut self, router: Router) {
         for (method, route) in router.methods {
             self.append_segment(vec![], method, route);
@@ -469,6 +1183,17 @@ impl Router {
         if let Some(fallback) = router.fallback {
             self.fallback = Some(fallback);
         }
+        self.no_inherit_fallback |= router.no_inherit_fallback;
+        self.request_hooks.extend(router.request_hooks);
+        self.early_response_hooks
+            .extend(router.early_response_hooks);
+        self.late_response_hooks.extend(router.late_response_hooks);
+        self.error_hooks.extend(router.error_hooks);
+        self.wraps.extend(router.wraps);
+        self.outer_wraps.extend(router.outer_wraps);
+        self.extensions.extend(&router.extensions);
+        self.state.extend(&router.state);
+        self.route_names.extend(router.route_names);
         for subpath in router.subpaths {
             let subtarget = self.resolve_segments_mut(vec![subpath.segment.clone()]);
             subtarget.do_merge(subpath);
@@ -0,0 +1,90 @@
+use std::{
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axol_http::body::{BodyComponent, BodyStream};
+use futures::Stream;
+use hyper::{Method, Uri};
+use log::info;
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+/// Held while a request is being handled. hyper drops the request future if the client disconnects before the response is produced,
+/// in which case this cancels the request's `Cancellation` and logs the request like nginx's `499 Client Closed Request`.
+pub(super) struct DisconnectGuard {
+    cancellation: CancellationToken,
+    method: Method,
+    uri: Uri,
+    address: SocketAddr,
+    armed: bool,
+}
+
+impl DisconnectGuard {
+    pub(super) fn new(
+        cancellation: CancellationToken,
+        method: Method,
+        uri: Uri,
+        address: SocketAddr,
+    ) -> Self {
+        Self {
+            cancellation,
+            method,
+            uri,
+            address,
+            armed: true,
+        }
+    }
+
+    /// Called once the response is produced, handing the token over to `CancelOnDrop`.
+    pub(super) fn disarm(mut self) -> CancellationToken {
+        self.armed = false;
+        self.cancellation.clone()
+    }
+}
+
+impl Drop for DisconnectGuard {
+    fn drop(&mut self) {
+        if self.armed {
+            info!(
+                "[{}] {} {} 499: client disconnected before the response was produced",
+                self.address, self.method, self.uri
+            );
+            self.cancellation.cancel();
+        }
+    }
+}
+
+/// Wraps a streaming response body, cancelling the request's `Cancellation` if the body is dropped before it ended,
+/// i.e. because the client disconnected mid-stream.
+pub(super) struct CancelOnDrop {
+    inner: BodyStream,
+    guard: Option<DropGuard>,
+}
+
+impl CancelOnDrop {
+    pub(super) fn new(inner: BodyStream, cancellation: CancellationToken) -> Self {
+        Self {
+            inner,
+            guard: Some(cancellation.drop_guard()),
+        }
+    }
+}
+
+impl Stream for CancelOnDrop {
+    type Item = Result<BodyComponent, anyhow::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let out = self.inner.as_mut().poll_next(cx);
+        if let Poll::Ready(None) = out {
+            if let Some(guard) = self.guard.take() {
+                guard.disarm();
+            }
+        }
+        out
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
@@ -1,18 +1,20 @@
 use std::borrow::Cow;
+use std::future::Future;
 use std::panic::AssertUnwindSafe;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::{convert::Infallible, net::SocketAddr, sync::Arc};
 
 use crate::{
-    ConnectInfo, DefaultErrorHook, Error, ErrorHook, Handler, ObservedRoute, OuterWrapState,
-    RawPathExt, RequestHook, Wrap, WrapTarget,
+    Cancellation, CancellationToken, ConnectInfo, DefaultErrorHook, EncodedPathExt, Error,
+    ErrorHook, Handler, ObservedRoute, OuterWrapState, RawPathExt, RequestHook, StateSlot, Wrap,
+    WrapTarget,
 };
-use crate::{IntoResponse, Result, WrapState};
+use crate::{Result, WrapState};
 use axol_http::body::{BodyComponent, BodyWrapper};
 use axol_http::header::HeaderMapConvertError;
 use axol_http::{request::Request, response::Response};
-use axol_http::{Body, StatusCode};
+use axol_http::{Body, Extensions, StatusCode};
 use derive_builder::Builder;
 use futures::{FutureExt, Stream};
 use hyper::body::HttpBody;
@@ -23,7 +25,7 @@ pub use hyper::Error as HyperError;
 use hyper::{
     server::conn::AddrStream, Body as HyperBody, Request as HyperRequest, Response as HyperResponse,
 };
-use log::error;
+use log::{error, warn};
 use pin_project_lite::pin_project;
 use tokio::io::{AsyncRead, AsyncWrite};
 #[cfg(feature = "trace")]
@@ -36,11 +38,89 @@ mod tls_acceptor;
 #[cfg(feature = "tls")]
 pub use tls_acceptor::*;
 
+mod disconnect;
+use disconnect::{CancelOnDrop, DisconnectGuard};
+
+#[cfg(unix)]
+mod unix;
+#[cfg(unix)]
+pub use unix::*;
+
 #[derive(Builder)]
 #[builder(pattern = "owned")]
 pub struct Server<I> {
     incoming: I,
     router: Router,
+    #[builder(default, private)]
+    http: HttpOptions,
+}
+
+/// Protocol options forwarded to the hyper `Builder`, see the typed setters on `ServerBuilder`.
+#[derive(Default, Clone, Copy, Debug)]
+struct HttpOptions {
+    http1_only: bool,
+    http2_only: bool,
+    http2_adaptive_window: Option<bool>,
+    http2_max_concurrent_streams: Option<u32>,
+}
+
+impl HttpOptions {
+    fn apply<I>(self, mut builder: Builder<I>) -> Builder<I> {
+        if self.http1_only {
+            builder = builder.http1_only(true);
+        }
+        if self.http2_only {
+            builder = builder.http2_only(true);
+        }
+        if let Some(enabled) = self.http2_adaptive_window {
+            builder = builder.http2_adaptive_window(enabled);
+        }
+        if let Some(max) = self.http2_max_concurrent_streams {
+            builder = builder.http2_max_concurrent_streams(max);
+        }
+        builder
+    }
+}
+
+impl<I> ServerBuilder<I> {
+    /// Only accept HTTP/1 connections.
+    pub fn http1_only(self, enabled: bool) -> Self {
+        let http = self.http.unwrap_or_default();
+        self.http(HttpOptions {
+            http1_only: enabled,
+            ..http
+        })
+    }
+
+    /// Only accept HTTP/2 connections.
+    ///
+    /// Without TLS, this serves HTTP/2 cleartext (h2c) with prior knowledge only: there is no `Upgrade: h2c` handshake,
+    /// so clients must already know to speak HTTP/2, i.e. `curl --http2-prior-knowledge`. HTTP/1 clients will fail to connect.
+    pub fn http2_only(self, enabled: bool) -> Self {
+        let http = self.http.unwrap_or_default();
+        self.http(HttpOptions {
+            http2_only: enabled,
+            ..http
+        })
+    }
+
+    /// Whether to use an adaptive flow control window for HTTP/2 connections. Defaults to hyper's default.
+    pub fn http2_adaptive_window(self, enabled: bool) -> Self {
+        let http = self.http.unwrap_or_default();
+        self.http(HttpOptions {
+            http2_adaptive_window: Some(enabled),
+            ..http
+        })
+    }
+
+    /// Sets the maximum number of concurrent streams per HTTP/2 connection. Defaults to hyper's default.
+    pub fn http2_max_concurrent_streams(self, max: u32) -> Self {
+        let http = self.http.unwrap_or_default();
+        self.http(HttpOptions {
+            http2_max_concurrent_streams: Some(max),
+            ..http
+        })
+    }
 }
 
 impl ServerBuilder<AddrIncoming> {
@@ -75,6 +155,7 @@ impl ServerBuilder<TlsIncoming> {
                 .start(),
             ),
             router: self.router,
+            http: self.http,
         })
     }
 }
@@ -126,7 +207,8 @@ impl<I: Accept> Server<I> {
 }
 
 pin_project! {
-    struct BodyInputStream {
+    /// Adapts a hyper body to a `Body::Stream` stream.
+    pub(crate) struct BodyInputStream {
         #[pin]
         body: HyperBody,
         data_ended: bool,
@@ -134,6 +216,16 @@ pin_project! {
     }
 }
 
+impl BodyInputStream {
+    pub(crate) fn new(body: HyperBody) -> Self {
+        Self {
+            body,
+            data_ended: false,
+            trailers_ended: false,
+        }
+    }
+}
+
 impl Stream for BodyInputStream {
     type Item = Result<BodyComponent, anyhow::Error>;
 
@@ -176,6 +268,11 @@ impl Stream for BodyInputStream {
 
 pub trait RemoteSocket {
     fn remote_addr(&self) -> SocketAddr;
+
+    /// Connection-level data inserted into the extensions of every request on this connection, i.e. TLS session details.
+    fn connection_extensions(&self) -> Extensions {
+        Extensions::default()
+    }
 }
 
 impl RemoteSocket for AddrStream {
@@ -199,18 +296,45 @@ pub(crate) async fn inner_handler(
         }
     }
     let state = WrapState {
-        wraps,
+        wraps: outermost_last(wraps),
         target: WrapTarget::Handler(&*handler),
         request,
     };
     state.next().await
 }
 
-impl<I: Accept + 'static> Server<I>
-where
-    I::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
-    I::Conn: AsyncRead + AsyncWrite + RemoteSocket + Unpin + Send + 'static,
-{
+/// `WrapState::next` pops wraps from the back, so the first (outermost) wrap has to be last.
+fn outermost_last(mut wraps: Vec<Arc<dyn Wrap>>) -> Vec<Arc<dyn Wrap>> {
+    wraps.reverse();
+    wraps
+}
+
+/// A hyper (and thereby tower) `Service` dispatching requests through a `Router`, see `Router::into_service`.
+///
+/// Used to embed a router in an existing hyper or tower stack, or to call it without binding a port.
+#[derive(Clone, Debug)]
+pub struct RouterService {
+    router: Arc<Router>,
+    address: SocketAddr,
+    connection: Extensions,
+}
+
+impl RouterService {
+    pub(crate) fn new(mut router: Router) -> Self {
+        router.set_paths("");
+        Self {
+            router: Arc::new(router),
+            address: SocketAddr::from(([0, 0, 0, 0], 0)),
+            connection: Extensions::default(),
+        }
+    }
+
+    /// Sets the peer address exposed as `ConnectInfo`. Defaults to `0.0.0.0:0`.
+    pub fn connect_info(mut self, address: SocketAddr) -> Self {
+        self.address = address;
+        self
+    }
+
     async fn request_phase(
         request_hooks: Vec<Arc<dyn RequestHook>>,
         wraps: Vec<Arc<dyn Wrap>>,
@@ -225,7 +349,7 @@ where
         };
 
         let state = WrapState {
-            wraps: outer_wraps,
+            wraps: outermost_last(outer_wraps),
             target: WrapTarget::Phase(outer_wrap_state),
             request,
         };
@@ -246,11 +370,24 @@ where
                 }
             }
         }
-        DefaultErrorHook
-            .handle_error(request.parts(), &mut error)
-            .await
-            .unwrap()
-            .unwrap()
+        let Some(hook) = &observed.default_error_hook else {
+            return DefaultErrorHook
+                .handle_error(request.parts(), &mut error)
+                .await
+                .unwrap()
+                .unwrap();
+        };
+        if let Some(e) = error.as_internal() {
+            log::error!("internal error: {e:#}");
+        }
+        match hook.handle_error(request.parts(), &mut error).await {
+            Ok(Some(x)) => return x,
+            Err(Error::SkipMiddleware) | Ok(None) => (),
+            Err(e) => {
+                log::error!("default error hook failure: {e}");
+            }
+        }
+        error.into_response()
     }
 
     async fn handle_early_response(
@@ -285,7 +422,9 @@ where
     async fn do_handle_axol_response(
         router: Arc<Router>,
         address: SocketAddr,
+        connection: Extensions,
         request: HyperRequest<HyperBody>,
+        cancellation: CancellationToken,
     ) -> Result<Response> {
         let (parts, body) = request.into_parts();
         let mut request = Request {
@@ -301,15 +440,15 @@ where
                 .map_err(|e: HeaderMapConvertError| Error::unprocessable_entity(e.to_string()))?,
             extensions: parts.extensions.into(),
             body: Body::Stream {
-                size_hint: Some(<HyperBody as HttpBody>::size_hint(&body).lower() as usize),
-                stream: Box::pin(BodyInputStream {
-                    body,
-                    data_ended: false,
-                    trailers_ended: false,
-                }),
+                // only an exact hint, the hint may become the `content-length` of a response streaming this body
+                size_hint: <HyperBody as HttpBody>::size_hint(&body)
+                    .exact()
+                    .map(|x| x as usize),
+                stream: Box::pin(BodyInputStream::new(body)),
             },
         };
         let mut observed = router.resolve_path(request.method, request.uri.path());
+        let encoded_variables = observed.variables.0.clone();
         for (_, value) in observed.variables.0.iter_mut() {
             let decoded = percent_encoding::percent_decode_str(value)
                 .decode_utf8()
@@ -320,10 +459,16 @@ where
         }
         //TODO: make this extension gathering more efficient
         request.extensions.extend(&observed.extensions);
+        request
+            .extensions
+            .insert(StateSlot(std::mem::take(&mut observed.state)));
         request
             .extensions
             .insert(RawPathExt(std::mem::take(&mut observed.variables.0)));
+        request.extensions.insert(EncodedPathExt(encoded_variables));
         request.extensions.insert(ConnectInfo(address));
+        request.extensions.extend(&connection);
+        request.extensions.insert(Cancellation(cancellation));
 
         #[cfg(feature = "tracing")]
         let remote = address;
@@ -333,22 +478,48 @@ where
         let wraps = std::mem::take(&mut observed.wraps);
         let outer_wraps = std::mem::take(&mut observed.outer_wraps);
         let request_hooks = std::mem::take(&mut observed.request_hooks);
+        let allowed_methods = std::mem::take(&mut observed.allowed_methods);
+        // hyper sends `100 Continue` once the body is first read, so only other expectations need rejecting here.
+        // they are rejected after routing, so the route's error hooks see them
+        let expectation_failed = request.version >= axol_http::Version::HTTP_11
+            && request
+                .headers
+                .get("expect")
+                .is_some_and(|x| !x.trim().eq_ignore_ascii_case("100-continue"));
 
         // we are not passing any interior mutability or mutability into the catch_unwind.
         // (that isn't dropped inside if a panic occurs)
         // TODO: this might not be a good idea, analyze how this could interact with application code
         let late_response = AssertUnwindSafe(async move {
-            let mut late_response = match Self::request_phase(
-                request_hooks,
-                wraps,
-                outer_wraps,
-                observed.route.clone(),
-                &mut request,
-            )
-            .await
-            {
+            let response = if expectation_failed {
+                Err(Error::ExpectationFailed)
+            } else {
+                Self::request_phase(
+                    request_hooks,
+                    wraps,
+                    outer_wraps,
+                    observed.route.clone(),
+                    &mut request,
+                )
+                .await
+            };
+            let mut late_response = match response {
                 Ok(x) => Self::handle_early_response(&observed, &mut request, x).await,
-                Err(error) => Self::handle_error(&observed, &mut request, error).await,
+                Err(error) => {
+                    let mut response = Self::handle_error(&observed, &mut request, error).await;
+                    if response.status == StatusCode::MethodNotAllowed
+                        && !allowed_methods.is_empty()
+                        && !response.headers.contains_key("allow")
+                    {
+                        let allow = allowed_methods
+                            .iter()
+                            .map(|x| x.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        response.headers.insert("allow", allow);
+                    }
+                    response
+                }
             };
             Self::handle_late_response(&observed, &mut request, &mut late_response).await;
             late_response
@@ -372,7 +543,7 @@ where
                     .or_else(|e| e.downcast::<&'static str>().map(|x| x.to_string()))
                     .unwrap_or_else(|e| format!("{e:?}"));
                 error!("panic during handler/middlware: {display}");
-                StatusCode::InternalServerError.into_response().unwrap()
+                router.panic_response(display)
             }
         };
 
@@ -382,18 +553,49 @@ where
     async fn do_handle(
         router: Arc<Router>,
         address: SocketAddr,
+        connection: Extensions,
         request: HyperRequest<HyperBody>,
     ) -> Result<HyperResponse<BodyWrapper>, Infallible> {
         let is_head = request.method() == axol_http::http::Method::HEAD;
-        let mut response = match Self::do_handle_axol_response(router, address, request).await {
-            Ok(x) => x,
-            Err(e) => e.into_response(),
-        };
+        let coalesce_headers = router.coalesces_headers();
+        let cancellation = CancellationToken::new();
+        let guard = DisconnectGuard::new(
+            cancellation.clone(),
+            request.method().clone(),
+            request.uri().clone(),
+            address,
+        );
+        let mut response =
+            match Self::do_handle_axol_response(router, address, connection, request, cancellation)
+                .await
+            {
+                Ok(x) => x,
+                Err(e) => e.into_response(),
+            };
+        let cancellation = guard.disarm();
 
+        // for HEAD, the GET handler has already run, so this reports the length of what it would have sent
+        Self::set_content_length(&mut response, is_head);
         if is_head {
-            std::mem::take(&mut response.body);
+            // an empty buffered body would have an exact size hint of 0, contradicting `content-length`
+            response.body = Body::Stream {
+                size_hint: None,
+                stream: Box::pin(futures::stream::empty()),
+            };
         }
 
+        if coalesce_headers {
+            response.headers.coalesce();
+        }
+
+        response.body = match std::mem::take(&mut response.body) {
+            Body::Stream { size_hint, stream } => Body::Stream {
+                size_hint,
+                stream: Box::pin(CancelOnDrop::new(stream, cancellation)),
+            },
+            body => body,
+        };
+
         let status: axol_http::http::StatusCode = response.status.into();
         let mut builder = HyperResponse::builder()
             .status(status)
@@ -406,24 +608,89 @@ where
             .expect("body conversion failed"))
     }
 
+    /// Sets `content-length` from the body's known length if the handler didn't, so HTTP/1.1 responses aren't needlessly chunked
+    /// and HEAD responses match GET. A wrong `content-length` for a buffered body breaks framing, so it is corrected.
+    fn set_content_length(response: &mut Response, is_head: bool) {
+        if response.status.is_informational()
+            || matches!(
+                response.status,
+                StatusCode::NoContent | StatusCode::NotModified
+            )
+            || response.headers.contains_key("transfer-encoding")
+        {
+            return;
+        }
+        let Some(length) = response.body.size_hint() else {
+            return;
+        };
+        let current = response
+            .headers
+            .get("content-length")
+            .map(|x| x.trim().parse::<usize>().ok());
+        match current {
+            Some(Some(current)) if current == length => (),
+            // HEAD handlers may set the length of a body they don't send
+            Some(_) if is_head && length == 0 => (),
+            Some(_) if !matches!(response.body, Body::Bytes(_)) => (),
+            Some(current) => {
+                warn!("correcting content-length {current:?} to the body length {length}");
+                response
+                    .headers
+                    .insert("content-length", length.to_string());
+            }
+            None => {
+                response
+                    .headers
+                    .insert("content-length", length.to_string());
+            }
+        }
+    }
+}
+
+impl hyper::service::Service<HyperRequest<HyperBody>> for RouterService {
+    type Response = HyperResponse<BodyWrapper>;
+
+    type Error = Infallible;
+
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: HyperRequest<HyperBody>) -> Self::Future {
+        Box::pin(Self::do_handle(
+            self.router.clone(),
+            self.address,
+            self.connection.clone(),
+            request,
+        ))
+    }
+}
+
+impl<I: Accept + 'static> Server<I>
+where
+    I::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    I::Conn: AsyncRead + AsyncWrite + RemoteSocket + Unpin + Send + 'static,
+{
     pub async fn serve(self) -> Result<(), hyper::Error> {
         self.serve_custom(|x| x).await
     }
 
     pub async fn serve_custom(
-        mut self,
+        self,
         customize: impl FnOnce(Builder<I>) -> Builder<I>,
     ) -> Result<(), hyper::Error> {
-        self.router.set_paths("");
-        let router = Arc::new(self.router);
+        let service = RouterService::new(self.router);
         let service = hyper::service::make_service_fn(move |conn: &I::Conn| {
-            let addr = conn.remote_addr();
-            let router = router.clone();
-            let service =
-                hyper::service::service_fn(move |req| Self::do_handle(router.clone(), addr, req));
+            let service = RouterService {
+                address: conn.remote_addr(),
+                connection: conn.connection_extensions(),
+                ..service.clone()
+            };
             async move { Ok::<_, Infallible>(service) }
         });
-        let mut builder = hyper::Server::builder(self.incoming);
+        let mut builder = self.http.apply(hyper::Server::builder(self.incoming));
         builder = customize(builder);
         builder.serve(service).await
     }
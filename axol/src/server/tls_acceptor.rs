@@ -7,6 +7,7 @@ use std::{
 };
 
 use anyhow::Result;
+use axol_http::Extensions;
 use futures::Stream;
 use hyper::server::{
     accept::Accept,
@@ -18,6 +19,9 @@ use tokio::sync::{mpsc, watch};
 use tokio_rustls::{server::TlsStream, LazyConfigAcceptor};
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
 
+use super::RemoteSocket;
+use crate::{ClientCertificate, NegotiatedProtocol, TlsConnection};
+
 pub struct TlsIncoming {
     incoming: StreamWrapper,
     tls_config: watch::Receiver<Option<Arc<ServerConfig>>>,
@@ -43,6 +47,44 @@ impl<S: Stream<Item = Result<TlsStream<AddrStream>, std::io::Error>>> Accept for
     }
 }
 
+impl RemoteSocket for TlsStream<AddrStream> {
+    fn remote_addr(&self) -> SocketAddr {
+        self.get_ref().0.remote_addr()
+    }
+
+    fn connection_extensions(&self) -> Extensions {
+        let extensions = Extensions::default();
+        extensions.insert(TlsConnection);
+        let session = self.get_ref().1;
+        if let Some(certificates) = session.peer_certificates() {
+            extensions.insert(ClientCertificate(certificates.to_vec()));
+        }
+        if let Some(protocol) = session.alpn_protocol() {
+            extensions.insert(NegotiatedProtocol(
+                String::from_utf8_lossy(protocol).into_owned(),
+            ));
+        }
+        extensions
+    }
+}
+
+/// Handle for replacing the TLS configuration of a running `TlsIncoming`, i.e. to rotate certificates after an ACME renewal.
+///
+/// Only handshakes started after a reload use the new configuration, established connections are unaffected.
+#[derive(Clone)]
+pub struct TlsReloader(Arc<watch::Sender<Option<Arc<ServerConfig>>>>);
+
+impl TlsReloader {
+    pub fn reload(&self, tls_config: ServerConfig) {
+        self.0.send_replace(Some(Arc::new(tls_config)));
+    }
+
+    /// Removes the TLS configuration, so new connections are dropped until the next `reload`.
+    pub fn unload(&self) {
+        self.0.send_replace(None);
+    }
+}
+
 struct StreamWrapper(AddrIncoming);
 
 impl Stream for StreamWrapper {
@@ -84,6 +126,18 @@ impl TlsIncoming {
         )
     }
 
+    /// Same as `new`, returning a `TlsReloader` to replace `tls_config` later on.
+    pub fn new_reloadable(
+        listen: SocketAddr,
+        nodelay: bool,
+        keepalive: Option<Duration>,
+        tls_config: Option<ServerConfig>,
+    ) -> Result<(Self, TlsReloader), hyper::Error> {
+        let (sender, receiver) = watch::channel(tls_config.map(Arc::new));
+        let incoming = Self::new(listen, nodelay, keepalive, receiver)?;
+        Ok((incoming, TlsReloader(Arc::new(sender))))
+    }
+
     pub fn start(
         mut self,
     ) -> AcceptWrapper<impl Stream<Item = Result<TlsStream<AddrStream>, std::io::Error>>> {
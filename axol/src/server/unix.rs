@@ -0,0 +1,60 @@
+use std::{
+    net::SocketAddr,
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use hyper::server::accept::Accept;
+use tokio::net::{UnixListener, UnixStream};
+
+use super::{RemoteSocket, Server, ServerBuilder};
+
+/// Accepts connections on a Unix domain socket, i.e. behind a reverse proxy on the same host.
+pub struct UnixIncoming {
+    listener: UnixListener,
+}
+
+impl UnixIncoming {
+    /// Binds to `path`. Fails if the path already exists, so stale sockets from a previous run must be removed first.
+    pub fn bind(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            listener: UnixListener::bind(path)?,
+        })
+    }
+}
+
+impl Accept for UnixIncoming {
+    type Conn = UnixStream;
+
+    type Error = std::io::Error;
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+        self.listener
+            .poll_accept(cx)
+            .map(|x| Some(x.map(|(stream, _)| stream)))
+    }
+}
+
+/// Unix domain socket peers have no IP address, so `ConnectInfo` is always `0.0.0.0:0`.
+impl RemoteSocket for UnixStream {
+    fn remote_addr(&self) -> SocketAddr {
+        SocketAddr::from(([0, 0, 0, 0], 0))
+    }
+}
+
+impl ServerBuilder<UnixIncoming> {
+    pub fn bind_unix(mut self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        self.incoming = Some(UnixIncoming::bind(path)?);
+        Ok(self)
+    }
+}
+
+impl Server<UnixIncoming> {
+    pub fn bind_unix(path: impl AsRef<Path>) -> std::io::Result<ServerBuilder<UnixIncoming>> {
+        ServerBuilder::default().bind_unix(path)
+    }
+}
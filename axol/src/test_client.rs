@@ -0,0 +1,104 @@
+use axol_http::{header::HeaderMap, request::Request, Body, Method, StatusCode};
+use hyper::{service::Service, Body as HyperBody, Request as HyperRequest};
+use serde::de::DeserializeOwned;
+
+use crate::{Router, RouterService};
+
+/// In-process client dispatching requests straight through a `Router` without binding a socket, for tests.
+/// See `Router::test_client`.
+///
+/// Requests never leave the process, so they are deterministic and can run concurrently.
+/// Failures outside of the router itself (i.e. a streaming body erroring) panic.
+///
+/// ```ignore
+/// let client = Router::new().get("/", index).test_client();
+/// let response = client.get("/").await;
+/// assert_eq!(response.status, StatusCode::Ok);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TestClient {
+    service: RouterService,
+}
+
+/// A response received by a `TestClient`, with the body collected.
+#[derive(Clone, Debug)]
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl TestResponse {
+    /// The body as text, replacing invalid UTF-8.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Deserializes the body as JSON, panicking if it isn't valid.
+    pub fn json<T: DeserializeOwned>(&self) -> T {
+        serde_json::from_slice(&self.body).expect("response body is not valid JSON")
+    }
+}
+
+impl TestClient {
+    pub(crate) fn new(router: Router) -> Self {
+        Self {
+            service: router.into_service(),
+        }
+    }
+
+    pub async fn get(&self, path: &str) -> TestResponse {
+        self.send(Method::Get, path, Body::empty()).await
+    }
+
+    pub async fn post(&self, path: &str, body: impl Into<Body>) -> TestResponse {
+        self.send(Method::Post, path, body.into()).await
+    }
+
+    pub async fn put(&self, path: &str, body: impl Into<Body>) -> TestResponse {
+        self.send(Method::Put, path, body.into()).await
+    }
+
+    pub async fn delete(&self, path: &str) -> TestResponse {
+        self.send(Method::Delete, path, Body::empty()).await
+    }
+
+    /// Sends a request without headers.
+    pub async fn send(&self, method: Method, path: &str, body: Body) -> TestResponse {
+        let request = Request {
+            method,
+            uri: path.parse().expect("invalid request path"),
+            body,
+            ..Default::default()
+        };
+        self.request(request).await
+    }
+
+    /// Sends `request` as is. Request extensions are not forwarded.
+    pub async fn request(&self, request: Request) -> TestResponse {
+        let method: axol_http::http::Method = request.method.into();
+        let mut builder = HyperRequest::builder()
+            .method(method)
+            .uri(request.uri)
+            .version(request.version);
+        *builder.headers_mut().unwrap() = request.headers.into();
+        let body = request.body.collect().await.expect("request body failed");
+        let request = builder
+            .body(HyperBody::from(body))
+            .expect("invalid request");
+
+        let response = self.service.clone().call(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body)
+            .await
+            .expect("response body failed");
+        TestResponse {
+            status: parts.status.into(),
+            headers: parts
+                .headers
+                .try_into()
+                .expect("response headers are not UTF-8"),
+            body: body.to_vec(),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use axol::{AbsoluteUrl, Router};
+
+mod common;
+use common::*;
+
+async fn url(AbsoluteUrl(url): AbsoluteUrl) -> String {
+    url.to_string()
+}
+
+#[tokio::test]
+async fn absolute_url() {
+    let handle = spawn_router(Router::new().get("/callback/:provider", url)).await;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!(
+            "http://{}/callback/github?code=a%20b&state=1",
+            *TEST_ADDRESS
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.text().await.unwrap(),
+        format!(
+            "http://{}/callback/github?code=a%20b&state=1",
+            *TEST_ADDRESS
+        )
+    );
+
+    let response = client
+        .get(format!("http://{}/callback/github", *TEST_ADDRESS))
+        .header("host", "example.com:8443")
+        .header("x-forwarded-proto", "https")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.text().await.unwrap(),
+        "https://example.com:8443/callback/github"
+    );
+
+    handle.abort();
+}
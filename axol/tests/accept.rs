@@ -0,0 +1,40 @@
+use axol::{Accept, Result, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn report(accept: Accept) -> Result<String> {
+    let preferred = accept.negotiate(&["application/json", "text/html", "text/csv"])?;
+    Ok(preferred.to_string())
+}
+
+async fn get_with(accept: Option<&str>) -> reqwest::Response {
+    let mut request = reqwest::Client::new().get(format!("http://{}/report", *TEST_ADDRESS));
+    if let Some(accept) = accept {
+        request = request.header("accept", accept);
+    }
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn accept_tests() {
+    let handle = spawn_router(Router::new().get("/report", report)).await;
+
+    let response = get_with(None).await;
+    assert_eq!(response.text().await.unwrap(), "application/json");
+
+    let response = get_with(Some("text/html,application/xhtml+xml,*/*;q=0.8")).await;
+    assert_eq!(response.text().await.unwrap(), "text/html");
+
+    let response = get_with(Some("text/*;q=0.5, text/csv, application/json;q=0.4")).await;
+    assert_eq!(response.text().await.unwrap(), "text/csv");
+
+    let response = get_with(Some("*/*;q=0.1, text/html;q=0")).await;
+    assert_eq!(response.text().await.unwrap(), "application/json");
+
+    let response = get_with(Some("image/png")).await;
+    assert_eq!(StatusCode::NotAcceptable, response.status().into());
+
+    handle.abort();
+}
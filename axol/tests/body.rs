@@ -0,0 +1,105 @@
+use axol::{BodyConfig, ContentLengthLimit, Router};
+use axol_http::StatusCode;
+use bytes::Bytes;
+
+mod common;
+use common::*;
+
+async fn echo(body: String) -> String {
+    body
+}
+
+async fn length(body: Bytes) -> String {
+    body.len().to_string()
+}
+
+async fn large(ContentLengthLimit(body): ContentLengthLimit<Vec<u8>, 64>) -> String {
+    body.len().to_string()
+}
+
+#[tokio::test]
+async fn body_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .post("/echo", echo)
+            .post("/length", length)
+            .post("/large", large)
+            .extension("/", BodyConfig { limit: 16 }),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{}/echo", *TEST_ADDRESS))
+        .body("hello")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "hello");
+
+    let response = client
+        .post(format!("http://{}/echo", *TEST_ADDRESS))
+        .body(vec![0xffu8, 0xfe])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UnprocessableEntity, response.status().into());
+
+    let response = client
+        .post(format!("http://{}/length", *TEST_ADDRESS))
+        .body(vec![0u8; 16])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "16");
+
+    let response = client
+        .post(format!("http://{}/length", *TEST_ADDRESS))
+        .body(vec![0u8; 17])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PayloadTooLarge, response.status().into());
+
+    let response = client
+        .post(format!("http://{}/large", *TEST_ADDRESS))
+        .body(vec![0u8; 16])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "16");
+
+    // the body buffered by `ContentLengthLimit` is still held to `BodyConfig`
+    let response = client
+        .post(format!("http://{}/large", *TEST_ADDRESS))
+        .body(vec![0u8; 32])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PayloadTooLarge, response.status().into());
+
+    // a `content-length` over the limit is rejected before the body is read
+    let response = client
+        .post(format!("http://{}/large", *TEST_ADDRESS))
+        .body(vec![0u8; 65])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PayloadTooLarge, response.status().into());
+
+    // unless the client waits for `100 Continue`, which it won't get
+    let response = client
+        .post(format!("http://{}/large", *TEST_ADDRESS))
+        .header("expect", "100-continue")
+        .body(vec![0u8; 65])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::ExpectationFailed, response.status().into());
+
+    handle.abort();
+}
@@ -0,0 +1,59 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use axol::{Cancellation, CancellationToken, Router, Timeout};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+static TIMED_OUT: AtomicBool = AtomicBool::new(false);
+static COMPLETED: Mutex<Option<CancellationToken>> = Mutex::new(None);
+
+async fn slow(cancellation: Cancellation) -> &'static str {
+    let token = cancellation.0.clone();
+    tokio::spawn(async move {
+        token.cancelled().await;
+        TIMED_OUT.store(true, Ordering::SeqCst);
+    });
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    "done"
+}
+
+async fn fast(cancellation: Cancellation) -> String {
+    *COMPLETED.lock().unwrap() = Some(cancellation.0.clone());
+    cancellation.is_cancelled().to_string()
+}
+
+#[tokio::test]
+async fn cancellation() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/slow", slow)
+            .wrap("/slow", Timeout(Duration::from_millis(100)))
+            .get("/fast", fast),
+    )
+    .await;
+
+    let response = reqwest::get(format!("http://{}/slow", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::RequestTimeout, response.status().into());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(TIMED_OUT.load(Ordering::SeqCst));
+
+    // producing a response doesn't cancel the token
+    let response = reqwest::get(format!("http://{}/fast", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "false");
+    let token = COMPLETED.lock().unwrap().take().unwrap();
+    assert!(!token.is_cancelled());
+
+    handle.abort();
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use axol::{ClientCertificate, Router, Server, TlsIncoming};
+use rustls::{
+    server::AllowAnyAnonymousOrAuthenticatedClient, Certificate, ClientConfig, PrivateKey,
+    RootCertStore, ServerConfig, ServerName,
+};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+
+mod common;
+use common::*;
+
+fn self_signed() -> (Certificate, PrivateKey) {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    (
+        Certificate(cert.serialize_der().unwrap()),
+        PrivateKey(cert.serialize_private_key_der()),
+    )
+}
+
+async fn peer(certificate: Option<ClientCertificate>) -> String {
+    match certificate {
+        Some(ClientCertificate(chain)) => format!("{} bytes", chain[0].0.len()),
+        None => "anonymous".to_string(),
+    }
+}
+
+async fn request(config: ClientConfig) -> String {
+    let tcp = TcpStream::connect(*TEST_ADDRESS).await.unwrap();
+    let stream = TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), tcp)
+        .await
+        .unwrap();
+    let (mut sender, connection) = hyper::client::conn::handshake(stream).await.unwrap();
+    tokio::spawn(connection);
+    let request = hyper::Request::get("/peer")
+        .header("host", "localhost")
+        .body(hyper::Body::empty())
+        .unwrap();
+    let response = sender.send_request(request).await.unwrap();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn client_certificate_tests() {
+    let (server_cert, server_key) = self_signed();
+    let (client_cert, client_key) = self_signed();
+
+    let mut client_roots = RootCertStore::empty();
+    client_roots.add(&client_cert).unwrap();
+    let server_config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(AllowAnyAnonymousOrAuthenticatedClient::new(client_roots))
+        .with_single_cert(vec![server_cert.clone()], server_key)
+        .unwrap();
+    let (incoming, _reloader) =
+        TlsIncoming::new_reloadable(*TEST_ADDRESS, true, None, Some(server_config)).unwrap();
+    let handle = tokio::spawn(
+        Server::builder()
+            .incoming(incoming.start())
+            .router(Router::new().get("/peer", peer))
+            .serve(),
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    let mut server_roots = RootCertStore::empty();
+    server_roots.add(&server_cert).unwrap();
+
+    let anonymous = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(server_roots.clone())
+        .with_no_client_auth();
+    assert_eq!(request(anonymous).await, "anonymous");
+
+    let authenticated = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(server_roots)
+        .with_single_cert(vec![client_cert.clone()], client_key)
+        .unwrap();
+    assert_eq!(
+        request(authenticated).await,
+        format!("{} bytes", client_cert.0.len())
+    );
+
+    handle.abort();
+}
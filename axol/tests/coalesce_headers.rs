@@ -0,0 +1,42 @@
+use axol::Router;
+use axol_http::response::Response;
+
+mod common;
+use common::*;
+
+async fn multi_header() -> Response {
+    let mut response = Response::default();
+    response.headers.append("vary", "accept");
+    response.headers.append("set-cookie", "a=1");
+    response.headers.append("vary", "origin");
+    response.headers.append("set-cookie", "b=2");
+    response
+}
+
+#[tokio::test]
+async fn coalesce_headers_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/multi", multi_header)
+            .coalesce_headers(true),
+    )
+    .await;
+
+    let response = reqwest::get(format!("http://{}/multi", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    let vary = response
+        .headers()
+        .get_all("vary")
+        .iter()
+        .collect::<Vec<_>>();
+    assert_eq!(vary, vec!["accept, origin"]);
+    let cookies = response
+        .headers()
+        .get_all("set-cookie")
+        .iter()
+        .collect::<Vec<_>>();
+    assert_eq!(cookies, vec!["a=1", "b=2"]);
+
+    handle.abort();
+}
@@ -1,3 +1,6 @@
+// not every test binary uses every helper
+#![allow(dead_code)]
+
 use std::{net::SocketAddr, time::Duration};
 
 use axol::Router;
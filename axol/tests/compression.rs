@@ -0,0 +1,105 @@
+#![cfg(feature = "compression")]
+
+use std::io::Write;
+
+use axol::{
+    compression::{Compression, RequestDecompression},
+    Json, Router,
+};
+use axol_http::StatusCode;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+
+mod common;
+use common::*;
+
+#[derive(Serialize, Deserialize)]
+struct Payload {
+    message: String,
+}
+
+async fn echo(Json(payload): Json<Payload>) -> String {
+    payload.message
+}
+
+async fn large() -> String {
+    "a".repeat(4096)
+}
+
+async fn large_vary() -> ([(&'static str, &'static str); 1], String) {
+    ([("vary", "Accept-Encoding")], "a".repeat(4096))
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[tokio::test]
+async fn compression_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .post("/echo", echo)
+            .get("/large", large)
+            .get("/large_vary", large_vary)
+            .plugin("/", RequestDecompression::new())
+            .plugin("/large", Compression::new())
+            .plugin("/large_vary", Compression::new()),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let body = serde_json::to_vec(&Payload {
+        message: "compressed hello".to_string(),
+    })
+    .unwrap();
+    let response = client
+        .post(format!("http://{}/echo", *TEST_ADDRESS))
+        .header("content-type", "application/json")
+        .header("content-encoding", "gzip")
+        .body(gzip(&body))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "compressed hello");
+
+    let response = client
+        .post(format!("http://{}/echo", *TEST_ADDRESS))
+        .header("content-type", "application/json")
+        .header("content-encoding", "gzip")
+        .body(&b"not gzip"[..])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BadRequest, response.status().into());
+
+    let response = client
+        .get(format!("http://{}/large", *TEST_ADDRESS))
+        .header("accept-encoding", "deflate;q=0.5, gzip")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+    assert_eq!(response.headers()["vary"], "accept-encoding");
+    let compressed = response.bytes().await.unwrap();
+    let mut decoder = flate2::write::GzDecoder::new(vec![]);
+    decoder.write_all(&compressed).unwrap();
+    assert_eq!(decoder.finish().unwrap(), "a".repeat(4096).as_bytes());
+
+    // an existing `vary: accept-encoding` isn't repeated
+    let response = client
+        .get(format!("http://{}/large_vary", *TEST_ADDRESS))
+        .header("accept-encoding", "gzip")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    let vary: Vec<_> = response.headers().get_all("vary").iter().collect();
+    assert_eq!(vary, ["Accept-Encoding"]);
+
+    handle.abort();
+}
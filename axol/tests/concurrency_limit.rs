@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use axol::{ConcurrencyLimit, Router, Timeout};
+use axol_http::StatusCode;
+use tokio::{sync::Semaphore, task::JoinHandle};
+
+mod common;
+use common::*;
+
+/// Handlers wait for a permit here, so the test controls when they finish.
+static GATE: Semaphore = Semaphore::const_new(0);
+
+async fn hold() -> &'static str {
+    GATE.acquire().await.unwrap().forget();
+    "done"
+}
+
+fn spawn_get(path: &'static str) -> JoinHandle<StatusCode> {
+    tokio::spawn(async move {
+        reqwest::get(format!("http://{}{path}", *TEST_ADDRESS))
+            .await
+            .unwrap()
+            .status()
+            .into()
+    })
+}
+
+async fn wait_for_in_flight(limit: &ConcurrencyLimit, in_flight: usize) {
+    while limit.in_flight() != in_flight {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[tokio::test]
+async fn concurrency_limit_tests() {
+    let queue = ConcurrencyLimit::new(1);
+    let reject = ConcurrencyLimit::new(1).reject_when_saturated(true);
+    let timed = ConcurrencyLimit::new(1);
+    let handle = spawn_router(
+        Router::new()
+            .get("/queue", hold)
+            .wrap("/queue", queue.clone())
+            .get("/reject", hold)
+            .wrap("/reject", reject.clone())
+            .get("/timed", hold)
+            .wrap("/timed", Timeout(Duration::from_millis(200)))
+            .wrap("/timed", timed.clone()),
+    )
+    .await;
+
+    // requests past the limit wait for a permit
+    let first = spawn_get("/queue");
+    wait_for_in_flight(&queue, 1).await;
+    let second = spawn_get("/queue");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!second.is_finished());
+    assert_eq!(queue.in_flight(), 1);
+    GATE.add_permits(2);
+    assert_eq!(first.await.unwrap(), StatusCode::Ok);
+    assert_eq!(second.await.unwrap(), StatusCode::Ok);
+    assert_eq!(queue.in_flight(), 0);
+
+    // or are rejected right away
+    let first = spawn_get("/reject");
+    wait_for_in_flight(&reject, 1).await;
+    assert_eq!(
+        spawn_get("/reject").await.unwrap(),
+        StatusCode::ServiceUnavailable
+    );
+    GATE.add_permits(1);
+    assert_eq!(first.await.unwrap(), StatusCode::Ok);
+    assert_eq!(reject.in_flight(), 0);
+
+    // a `Timeout` registered before the limit also covers time spent queued, and timing out releases the permit
+    let first = spawn_get("/timed");
+    wait_for_in_flight(&timed, 1).await;
+    let second = spawn_get("/timed");
+    assert_eq!(first.await.unwrap(), StatusCode::RequestTimeout);
+    assert_eq!(second.await.unwrap(), StatusCode::RequestTimeout);
+    assert_eq!(timed.in_flight(), 0);
+    GATE.add_permits(1);
+    assert_eq!(spawn_get("/timed").await.unwrap(), StatusCode::Ok);
+
+    handle.abort();
+}
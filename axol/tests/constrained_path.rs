@@ -0,0 +1,87 @@
+#![cfg(feature = "path-constraints")]
+
+use axol::{Extension, Path, Result, Router, Wrap, WrapState};
+use axol_http::response::Response;
+
+mod common;
+use common::*;
+
+async fn by_id(Path(id): Path<u64>) -> String {
+    format!("id {id}")
+}
+
+async fn by_uuid(Path(uuid): Path<String>) -> String {
+    format!("uuid {uuid}")
+}
+
+async fn by_slug(Path(slug): Path<String>) -> String {
+    format!("slug {slug}")
+}
+
+async fn me() -> &'static str {
+    "me"
+}
+
+#[derive(Clone)]
+struct Marker;
+
+struct Stamp;
+
+#[async_trait::async_trait]
+impl Wrap for Stamp {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let mut response = state.next().await?;
+        response.headers.append("x-stamp", "numeric");
+        Ok(response)
+    }
+}
+
+async fn post_by_slug(Path(slug): Path<String>, marker: Option<Extension<Marker>>) -> String {
+    format!("slug {slug} {}", marker.is_some())
+}
+
+#[tokio::test]
+async fn constrained_path() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/users/:slug", by_slug)
+            .get("/users/:id(\\d+)", by_id)
+            .get(
+                "/users/:uuid([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})",
+                by_uuid,
+            )
+            .get("/users/me", me)
+            // `/posts/42` enters the numeric branch, finds no handler there, and falls back to the slug
+            .get("/posts/:id(\\d+)/edit", me)
+            .wrap("/posts/:id(\\d+)", Stamp)
+            .outer_wrap("/posts/:id(\\d+)", Stamp)
+            .extension("/posts/:id(\\d+)", Marker)
+            .get("/posts/:slug", post_by_slug),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let get = |path: &'static str| client.get(format!("http://{}{path}", *TEST_ADDRESS)).send();
+    let response = get("/users/42").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "id 42");
+    let response = get("/users/42abc").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "slug 42abc");
+    let response = get("/users/67e55044-10b1-426f-9247-bb680e5fe0c8")
+        .await
+        .unwrap();
+    assert_eq!(
+        response.text().await.unwrap(),
+        "uuid 67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+    let response = get("/users/me").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "me");
+
+    // middleware of a constrained branch that was backtracked out of doesn't apply
+    let response = get("/posts/42").await.unwrap();
+    assert!(response.headers().get("x-stamp").is_none());
+    assert_eq!(response.text().await.unwrap(), "slug 42 false");
+    let response = get("/posts/42/edit").await.unwrap();
+    assert_eq!(response.headers().get_all("x-stamp").iter().count(), 2);
+
+    handle.abort();
+}
@@ -0,0 +1,104 @@
+use axol::Router;
+use axol_http::{body::BodyComponent, response::Response, Body};
+use tokio::net::TcpStream;
+
+mod common;
+use common::*;
+
+async fn bytes() -> &'static str {
+    "hello"
+}
+
+async fn wrong_length() -> Response {
+    let mut response = Response::default();
+    response.headers.insert("content-length", "100");
+    response.body = Body::Bytes(b"hello".to_vec());
+    response
+}
+
+fn stream(size_hint: Option<usize>) -> Response {
+    Response {
+        body: Body::Stream {
+            size_hint,
+            stream: Box::pin(futures::stream::iter([
+                Ok(BodyComponent::Data("hel".into())),
+                Ok(BodyComponent::Data("lo".into())),
+            ])),
+        },
+        ..Default::default()
+    }
+}
+
+async fn sized_stream() -> Response {
+    stream(Some(5))
+}
+
+async fn unsized_stream() -> Response {
+    stream(None)
+}
+
+async fn echo(body: Body) -> Response {
+    Response {
+        body,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn content_length() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/bytes", bytes)
+            .get("/wrong", wrong_length)
+            .get("/sized", sized_stream)
+            .get("/unsized", unsized_stream)
+            .post("/echo", echo),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    for path in ["/bytes", "/wrong", "/sized"] {
+        let response = client
+            .get(format!("http://{}{path}", *TEST_ADDRESS))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["content-length"], "5", "{path}");
+        assert!(!response.headers().contains_key("transfer-encoding"));
+        assert_eq!(response.text().await.unwrap(), "hello");
+
+        let response = client
+            .head(format!("http://{}{path}", *TEST_ADDRESS))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["content-length"], "5", "{path}");
+    }
+
+    let response = client
+        .get(format!("http://{}/unsized", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert!(!response.headers().contains_key("content-length"));
+    assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    assert_eq!(response.text().await.unwrap(), "hello");
+
+    // a chunked request body has no known length, so neither does a response streaming it back
+    let stream = TcpStream::connect(*TEST_ADDRESS).await.unwrap();
+    let (mut sender, connection) = hyper::client::conn::handshake(stream).await.unwrap();
+    tokio::spawn(connection);
+    let request = hyper::Request::post("/echo")
+        .header("host", "localhost")
+        .body(hyper::Body::wrap_stream(futures::stream::iter([
+            Ok::<_, std::io::Error>("hel"),
+            Ok("lo"),
+        ])))
+        .unwrap();
+    let response = sender.send_request(request).await.unwrap();
+    assert!(!response.headers().contains_key("content-length"));
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(&body[..], b"hello");
+
+    handle.abort();
+}
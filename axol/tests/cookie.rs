@@ -0,0 +1,73 @@
+#![cfg(feature = "cookie")]
+
+use axol::{Cookie, CookieJar, Router};
+
+mod common;
+use common::*;
+
+async fn login(jar: CookieJar) -> (CookieJar, &'static str) {
+    let cookie = Cookie::build("session", "abc").path("/").finish();
+    (jar.add(cookie), "logged in")
+}
+
+async fn logout(jar: CookieJar) -> (CookieJar, &'static str) {
+    (
+        jar.remove_with(Cookie::build("session", "").path("/")),
+        "logged out",
+    )
+}
+
+async fn forget(jar: CookieJar) -> (CookieJar, &'static str) {
+    (jar.remove(Cookie::named("theme")), "forgotten")
+}
+
+#[tokio::test]
+async fn cookie_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/login", login)
+            .get("/logout", logout)
+            .get("/forget", forget),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/login", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["set-cookie"], "session=abc; Path=/");
+
+    let response = client
+        .get(format!("http://{}/logout", *TEST_ADDRESS))
+        .header("cookie", "session=abc; theme=dark")
+        .send()
+        .await
+        .unwrap();
+    let removal = response.headers()["set-cookie"].to_str().unwrap();
+    assert!(removal.starts_with("session=;"));
+    assert!(removal.contains("Path=/"));
+    assert!(removal.contains("Max-Age=0"));
+    assert!(removal.contains("Expires="));
+
+    let response = client
+        .get(format!("http://{}/forget", *TEST_ADDRESS))
+        .header("cookie", "session=abc; theme=dark")
+        .send()
+        .await
+        .unwrap();
+    let removal = response.headers()["set-cookie"].to_str().unwrap();
+    assert!(removal.starts_with("theme=;"));
+    assert!(removal.contains("Max-Age=0"));
+
+    // nothing to remove if the request didn't carry the cookie
+    let response = client
+        .get(format!("http://{}/logout", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.headers().get("set-cookie").is_none());
+
+    handle.abort();
+}
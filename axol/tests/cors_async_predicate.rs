@@ -0,0 +1,74 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use axol::{
+    cors::{AllowOrigin, Cors},
+    Router,
+};
+use futures::FutureExt;
+
+mod common;
+use common::*;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn cors_async_predicate() {
+    // stands in for a database of tenant origins
+    let tenants: Arc<HashSet<String>> = Arc::new(["https://tenant.example".to_string()].into());
+    let handle = spawn_router(Router::new().get("/", hello).plugin(
+        "/",
+        Cors::new().allow_origin(AllowOrigin::async_predicate(move |origin, _| {
+            let tenants = tenants.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                tenants.contains(origin)
+            }
+            .boxed()
+        })),
+    ))
+    .await;
+    let client = reqwest::Client::new();
+
+    for (origin, allowed) in [
+        ("https://tenant.example", true),
+        ("https://other.example", false),
+    ] {
+        let response = client
+            .get(format!("http://{}/", *TEST_ADDRESS))
+            .header("origin", origin)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response
+                .headers()
+                .get("access-control-allow-origin")
+                .map(|x| x.to_str().unwrap()),
+            allowed.then_some(origin),
+            "{origin}"
+        );
+
+        let response = client
+            .request(
+                reqwest::Method::OPTIONS,
+                format!("http://{}/", *TEST_ADDRESS),
+            )
+            .header("origin", origin)
+            .header("access-control-request-method", "GET")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response
+                .headers()
+                .get("access-control-allow-origin")
+                .map(|x| x.to_str().unwrap()),
+            allowed.then_some(origin),
+            "{origin}"
+        );
+    }
+
+    handle.abort();
+}
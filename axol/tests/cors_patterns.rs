@@ -0,0 +1,50 @@
+#![cfg(feature = "cors-regex")]
+
+use axol::{
+    cors::{AllowOrigin, Cors},
+    Router,
+};
+use regex::Regex;
+
+mod common;
+use common::*;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn cors_patterns() {
+    let handle = spawn_router(Router::new().get("/", hello).plugin(
+        "/",
+        Cors::new().allow_origin(AllowOrigin::patterns([
+            Regex::new(r"https://[a-z0-9-]+\.example\.com").unwrap(),
+        ])),
+    ))
+    .await;
+    let client = reqwest::Client::new();
+
+    for (origin, allowed) in [
+        ("https://app.example.com", true),
+        ("https://example.com", false),
+        ("https://app.example.com.evil.com", false),
+        ("http://app.example.com", false),
+    ] {
+        let response = client
+            .get(format!("http://{}/", *TEST_ADDRESS))
+            .header("origin", origin)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response
+                .headers()
+                .get("access-control-allow-origin")
+                .map(|x| x.to_str().unwrap()),
+            allowed.then_some(origin),
+            "{origin}"
+        );
+    }
+
+    handle.abort();
+}
@@ -0,0 +1,75 @@
+use axol::{
+    cors::{AllowOrigin, Cors},
+    Router,
+};
+
+mod common;
+use common::*;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+fn vary_values(response: &reqwest::Response) -> Vec<String> {
+    response
+        .headers()
+        .get_all("vary")
+        .iter()
+        .flat_map(|x| x.to_str().unwrap().split(','))
+        .map(|x| x.trim().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn cors_vary() {
+    let predicate = || AllowOrigin::predicate(|origin, _| origin.ends_with(".example.com"));
+    let handle = spawn_router(
+        Router::new()
+            .get("/predicate", hello)
+            .get("/custom", hello)
+            .get("/const", hello)
+            .plugin("/predicate", Cors::new().allow_origin(predicate()))
+            .plugin(
+                "/custom",
+                Cors::new()
+                    .allow_origin(predicate())
+                    .vary(["accept-encoding"]),
+            )
+            .plugin(
+                "/const",
+                Cors::new()
+                    .allow_origin("https://app.example.com")
+                    .vary(Vec::<String>::new()),
+            ),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    for path in ["predicate", "custom"] {
+        // also for disallowed origins, the response is still specific to the origin
+        for origin in ["https://app.example.com", "https://evil.com"] {
+            let response = client
+                .get(format!("http://{}/{path}", *TEST_ADDRESS))
+                .header("origin", origin)
+                .send()
+                .await
+                .unwrap();
+            let vary = vary_values(&response);
+            assert_eq!(
+                vary.iter().filter(|x| *x == "origin").count(),
+                1,
+                "{path}: {vary:?}"
+            );
+        }
+    }
+
+    let response = client
+        .get(format!("http://{}/const", *TEST_ADDRESS))
+        .header("origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert!(vary_values(&response).is_empty());
+
+    handle.abort();
+}
@@ -0,0 +1,68 @@
+use axol::{Csv, Router};
+use axol_http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+mod common;
+use common::*;
+
+#[derive(Serialize, Deserialize)]
+struct Row {
+    name: String,
+    count: u32,
+}
+
+async fn double(Csv { records, .. }: Csv<Vec<Row>>) -> Csv<Vec<Row>> {
+    let records = records
+        .into_iter()
+        .map(|x| Row {
+            name: x.name,
+            count: x.count * 2,
+        })
+        .collect();
+    Csv::new(records).attachment("doubled.csv")
+}
+
+#[tokio::test]
+async fn csv_tests() {
+    let handle = spawn_router(Router::new().post("/double", double)).await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{}/double", *TEST_ADDRESS))
+        .header("content-type", "text/csv")
+        .body("name,count\na,1\nb,2\n")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"doubled.csv\""
+    );
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/csv"));
+    assert_eq!(response.text().await.unwrap(), "name,count\na,2\nb,4\n");
+
+    let response = client
+        .post(format!("http://{}/double", *TEST_ADDRESS))
+        .header("content-type", "text/csv")
+        .body("name,count\na,x\n")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UnprocessableEntity, response.status().into());
+
+    let response = client
+        .post(format!("http://{}/double", *TEST_ADDRESS))
+        .header("content-type", "application/json")
+        .body("[]")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UnsupportedMediaType, response.status().into());
+
+    handle.abort();
+}
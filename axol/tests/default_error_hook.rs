@@ -0,0 +1,56 @@
+use axol::{Error, ErrorHook, IntoResponse, Json, Result, Router};
+use axol_http::{request::RequestPartsRef, response::Response, StatusCode};
+use serde_json::{json, Value};
+
+mod common;
+use common::*;
+
+async fn fails() -> Result<()> {
+    Err(Error::internal(anyhow::anyhow!("database unavailable")))
+}
+
+struct JsonErrors;
+
+#[async_trait::async_trait]
+impl ErrorHook for JsonErrors {
+    async fn handle_error<'a>(
+        &self,
+        _request: RequestPartsRef<'a>,
+        error: &mut Error,
+    ) -> Result<Option<Response>> {
+        let status = error.status().unwrap_or(StatusCode::InternalServerError);
+        (status, Json(json!({ "status": status.as_u16() })))
+            .into_response()
+            .map(Some)
+    }
+}
+
+#[tokio::test]
+async fn default_error_hook_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/fails", fails)
+            .default_error_hook(JsonErrors),
+    )
+    .await;
+
+    let response = reqwest::get(format!("http://{}/missing", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+    assert_eq!(
+        response.json::<Value>().await.unwrap(),
+        json!({ "status": 404 })
+    );
+
+    let response = reqwest::get(format!("http://{}/fails", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::InternalServerError, response.status().into());
+    assert_eq!(
+        response.json::<Value>().await.unwrap(),
+        json!({ "status": 500 })
+    );
+
+    handle.abort();
+}
@@ -0,0 +1,43 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use axol::{Cancellation, Router};
+
+mod common;
+use common::*;
+
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+async fn slow(cancellation: Cancellation) -> &'static str {
+    let token = cancellation.0.clone();
+    tokio::spawn(async move {
+        token.cancelled().await;
+        CANCELLED.store(true, Ordering::SeqCst);
+    });
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    "done"
+}
+
+#[tokio::test]
+async fn client_disconnect() {
+    let handle = spawn_router(Router::new().get("/slow", slow)).await;
+
+    // the client gives up and closes the connection while the handler is still running
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    assert!(client
+        .get(format!("http://{}/slow", *TEST_ADDRESS))
+        .send()
+        .await
+        .is_err());
+    drop(client);
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(CANCELLED.load(Ordering::SeqCst));
+
+    handle.abort();
+}
@@ -0,0 +1,75 @@
+use axol::{Error, Path, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+enum AccountError {
+    NotFound,
+    Locked,
+}
+
+impl From<AccountError> for Error {
+    fn from(value: AccountError) -> Self {
+        match value {
+            AccountError::NotFound => Error::NotFound,
+            AccountError::Locked => Error::Forbidden,
+        }
+    }
+}
+
+fn find_account(id: &str) -> Result<String, AccountError> {
+    match id {
+        "1" => Ok("alice".to_string()),
+        "2" => Err(AccountError::Locked),
+        _ => Err(AccountError::NotFound),
+    }
+}
+
+async fn account(Path(id): Path<String>) -> Result<String, AccountError> {
+    let name = find_account(&id)?;
+    Ok(format!("account {name}"))
+}
+
+async fn parse(Path(value): Path<String>) -> anyhow::Result<String> {
+    let value: u32 = value.parse()?;
+    Ok(format!("parsed {value}"))
+}
+
+#[tokio::test]
+async fn domain_error_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/account/:id", account)
+            .get("/parse/:value", parse),
+    )
+    .await;
+
+    let response = reqwest::get(format!("http://{}/account/1", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "account alice");
+
+    let response = reqwest::get(format!("http://{}/account/2", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Forbidden, response.status().into());
+
+    let response = reqwest::get(format!("http://{}/account/3", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+
+    let response = reqwest::get(format!("http://{}/parse/12", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "parsed 12");
+
+    let response = reqwest::get(format!("http://{}/parse/x", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::InternalServerError, response.status().into());
+
+    handle.abort();
+}
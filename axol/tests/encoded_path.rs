@@ -0,0 +1,29 @@
+use axol::{EncodedPath, Path, Router};
+
+mod common;
+use common::*;
+
+async fn name(Path(name): Path<String>, EncodedPath(encoded): EncodedPath) -> String {
+    format!("{name} {}", encoded[0].1)
+}
+
+#[tokio::test]
+async fn encoded_path() {
+    let handle = spawn_router(Router::new().get("/files/:name", name)).await;
+
+    let get = |path: &'static str| async move {
+        reqwest::get(format!("http://{}{path}", *TEST_ADDRESS))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap()
+    };
+
+    // an encoded slash stays within the variable, but is decoded for `Path`
+    assert_eq!(get("/files/a%2Fb").await, "a/b a%2Fb");
+    assert_eq!(get("/files/a%20b").await, "a b a%20b");
+    assert_eq!(get("/files/plain").await, "plain plain");
+
+    handle.abort();
+}
@@ -0,0 +1,27 @@
+use std::error::Error as _;
+
+use anyhow::Context;
+use axol::Error;
+
+#[test]
+fn internal_error_source() {
+    let inner = Err::<(), _>(std::io::Error::other("disk full"))
+        .context("failed to save upload")
+        .unwrap_err();
+    let error = Error::internal(inner);
+
+    assert_eq!(error.to_string(), "failed to save upload: disk full");
+    let internal = error.as_internal().unwrap();
+    assert_eq!(internal.chain().count(), 2);
+    assert_eq!(internal.root_cause().to_string(), "disk full");
+
+    let source = error.source().unwrap();
+    assert_eq!(source.to_string(), "failed to save upload");
+    assert_eq!(source.source().unwrap().to_string(), "disk full");
+
+    let error = error.with_detail("upload failed");
+    assert!(error.as_internal().is_some());
+
+    assert!(Error::NotFound.as_internal().is_none());
+    assert!(Error::NotFound.source().is_none());
+}
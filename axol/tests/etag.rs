@@ -0,0 +1,47 @@
+#![cfg(feature = "etag")]
+
+use axol::{ETag, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn index() -> &'static str {
+    "cacheable"
+}
+
+#[tokio::test]
+async fn etag_tests() {
+    let handle = spawn_router(Router::new().get("/", index).plugin("/", ETag)).await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    let etag = response.headers()["etag"].clone();
+    assert_eq!(response.text().await.unwrap(), "cacheable");
+
+    let response = client
+        .get(format!("http://{}/", *TEST_ADDRESS))
+        .header("if-none-match", etag.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotModified, response.status().into());
+    assert_eq!(response.headers()["etag"], etag);
+    assert!(response.bytes().await.unwrap().is_empty());
+
+    let response = client
+        .get(format!("http://{}/", *TEST_ADDRESS))
+        .header("if-none-match", "\"other\"")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+
+    handle.abort();
+}
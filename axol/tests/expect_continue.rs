@@ -0,0 +1,71 @@
+use axol::{ContentLengthLimit, Error, ErrorHook, IntoResponse, Result, Router};
+use axol_http::{request::RequestPartsRef, response::Response, StatusCode};
+
+mod common;
+use common::*;
+
+async fn upload(ContentLengthLimit(body): ContentLengthLimit<Vec<u8>, 8>) -> String {
+    body.len().to_string()
+}
+
+struct ExplainExpectation;
+
+#[async_trait::async_trait]
+impl ErrorHook for ExplainExpectation {
+    async fn handle_error<'a>(
+        &self,
+        _request: RequestPartsRef<'a>,
+        error: &mut Error,
+    ) -> Result<Option<Response>> {
+        if !matches!(error, Error::ExpectationFailed) {
+            return Ok(None);
+        }
+        (
+            StatusCode::ExpectationFailed,
+            "only 100-continue is supported",
+        )
+            .into_response()
+            .map(Some)
+    }
+}
+
+#[tokio::test]
+async fn expect_continue() {
+    let handle = spawn_router(
+        Router::new()
+            .post("/upload", upload)
+            .error_hook_direct("/upload", ExplainExpectation),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let post = |expect: Option<&'static str>, body: &'static str| {
+        let mut request = client
+            .post(format!("http://{}/upload", *TEST_ADDRESS))
+            .body(body);
+        if let Some(expect) = expect {
+            request = request.header("expect", expect);
+        }
+        request.send()
+    };
+
+    let response = post(Some("100-continue"), "hello").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "5");
+
+    let response = post(Some("100-continue"), "far too large").await.unwrap();
+    assert_eq!(StatusCode::ExpectationFailed, response.status().into());
+
+    let response = post(None, "far too large").await.unwrap();
+    assert_eq!(StatusCode::PayloadTooLarge, response.status().into());
+
+    // unsupported expectations go through the route's error hooks
+    let response = post(Some("something-else"), "hello").await.unwrap();
+    assert_eq!(StatusCode::ExpectationFailed, response.status().into());
+    assert_eq!(
+        response.text().await.unwrap(),
+        "only 100-continue is supported"
+    );
+
+    handle.abort();
+}
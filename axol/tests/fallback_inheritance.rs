@@ -0,0 +1,74 @@
+use axol::Router;
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn shell() -> &'static str {
+    "shell"
+}
+
+async fn users_fallback() -> &'static str {
+    "users fallback"
+}
+
+async fn docs_fallback() -> &'static str {
+    "docs fallback"
+}
+
+async fn user() -> &'static str {
+    "user"
+}
+
+#[tokio::test]
+async fn fallback_inheritance() {
+    let handle = spawn_router(
+        Router::new()
+            .fallback("/", shell)
+            .get("/api/users/:id", user)
+            .get("/api/health", user)
+            .fallback("/api/users", users_fallback)
+            .no_inherit_fallback("/api")
+            .get("/app/settings", user)
+            .post("/docs/:id(\\d+)/raw", user)
+            .fallback("/docs/:name", docs_fallback),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let get = |path: &'static str| client.get(format!("http://{}{path}", *TEST_ADDRESS)).send();
+
+    // ancestor fallbacks are inherited, even for paths that don't exist
+    let response = get("/app/missing/deeper").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "shell");
+    let response = get("/unknown").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "shell");
+
+    // the deepest fallback wins
+    let response = get("/api/users/1/missing").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "users fallback");
+
+    // `/api` doesn't inherit the shell
+    let response = get("/api/missing").await.unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+    let response = get("/api").await.unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+
+    // method mismatches still take precedence over fallbacks
+    let response = client
+        .post(format!("http://{}/app/settings", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::MethodNotAllowed, response.status().into());
+    assert_eq!(response.headers()["allow"], "GET, HEAD");
+
+    // a method mismatch in a branch that was backtracked out of doesn't hide other branches
+    let response = get("/docs/5/raw").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "docs fallback");
+
+    let response = get("/api/health").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "user");
+
+    handle.abort();
+}
@@ -0,0 +1,57 @@
+use axol::{Form, FormConfig, Router};
+use axol_http::StatusCode;
+use serde::Deserialize;
+
+mod common;
+use common::*;
+
+#[derive(Deserialize)]
+struct Numbers {
+    a: Vec<u32>,
+}
+
+async fn sum(Form(numbers): Form<Numbers>) -> String {
+    numbers.a.iter().sum::<u32>().to_string()
+}
+
+#[tokio::test]
+async fn form_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .post("/sum", sum)
+            .extension("/", FormConfig { limit: 64 }),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{}/sum", *TEST_ADDRESS))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body("a=1&a=2")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "3");
+
+    let response = client
+        .post(format!("http://{}/sum", *TEST_ADDRESS))
+        .header("content-type", "text/plain")
+        .body("a=1&a=2")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UnsupportedMediaType, response.status().into());
+
+    let response = client
+        .post(format!("http://{}/sum", *TEST_ADDRESS))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body("a=1&".repeat(64))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PayloadTooLarge, response.status().into());
+
+    handle.abort();
+}
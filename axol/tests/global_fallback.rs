@@ -0,0 +1,61 @@
+use axol::{Error, Result, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn index() -> &'static str {
+    "index"
+}
+
+async fn api_not_found() -> Result<&'static str> {
+    Err(Error::NotFound)
+}
+
+async fn user() -> &'static str {
+    "user"
+}
+
+#[tokio::test]
+async fn global_fallback_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/api/user", user)
+            .fallback("/api", api_not_found)
+            .global_fallback(index),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/some/page", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "index");
+
+    let response = client
+        .get(format!("http://{}/api/missing", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+
+    let response = client
+        .get(format!("http://{}/api/user", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "user");
+
+    let response = client
+        .post(format!("http://{}/api/user", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::MethodNotAllowed, response.status().into());
+
+    handle.abort();
+}
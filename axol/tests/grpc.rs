@@ -0,0 +1,99 @@
+#![cfg(feature = "grpc")]
+
+use std::io::{Read, Write};
+
+use axol::{
+    grpc::{AcceptEncoding, Encoding, Grpc},
+    Router, Typed,
+};
+use flate2::{read::GzDecoder, write::GzEncoder};
+use prost::Message;
+
+mod common;
+use common::*;
+
+async fn echo(accept: Option<Typed<AcceptEncoding>>, request: Grpc<String>) -> Grpc<String> {
+    Grpc {
+        encoding: Encoding::negotiate(accept.as_ref().map(|x| &x.0)),
+        content_type: request.content_type,
+        body: request.body,
+    }
+}
+
+fn frame(compressed: bool, message: &[u8]) -> Vec<u8> {
+    let mut out = vec![compressed as u8];
+    out.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    out.extend_from_slice(message);
+    out
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[tokio::test]
+async fn grpc_encoding() {
+    let handle = spawn_router(Router::new().post("/test.Echo/Echo", echo)).await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/test.Echo/Echo", *TEST_ADDRESS);
+    let message = "hello".repeat(10).encode_to_vec();
+
+    // gzip request, gzip response
+    let response = client
+        .post(&url)
+        .header("content-type", "application/grpc+proto")
+        .header("grpc-encoding", "gzip")
+        .header("grpc-accept-encoding", "snappy,gzip")
+        .body(frame(true, &gzip(&message)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["grpc-encoding"], "gzip");
+    assert_eq!(
+        response.headers()["grpc-accept-encoding"],
+        "gzip,deflate,identity"
+    );
+    let body = response.bytes().await.unwrap();
+    assert_eq!(body[0], 1);
+    assert_eq!(
+        u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize,
+        body.len() - 5
+    );
+    let mut decompressed = vec![];
+    GzDecoder::new(&body[5..])
+        .read_to_end(&mut decompressed)
+        .unwrap();
+    assert_eq!(
+        String::decode(&decompressed[..]).unwrap(),
+        "hello".repeat(10)
+    );
+
+    // uncompressed message with a grpc-encoding, identity response
+    let response = client
+        .post(&url)
+        .header("content-type", "application/grpc")
+        .header("grpc-encoding", "gzip")
+        .body(frame(false, &message))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["grpc-encoding"], "identity");
+    let body = response.bytes().await.unwrap();
+    assert_eq!(&body[..], &frame(false, &message)[..]);
+
+    // unsupported encoding
+    let response = client
+        .post(&url)
+        .header("content-type", "application/grpc")
+        .header("grpc-encoding", "snappy")
+        .body(frame(true, &message))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.headers().get("grpc-encoding").is_none());
+    assert!(response.bytes().await.unwrap().is_empty());
+
+    handle.abort();
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "grpc")]
+
+use std::time::{Duration, Instant};
+
+use axol::{
+    grpc::{GrpcDeadline, GrpcDeadlines},
+    Router,
+};
+
+mod common;
+use common::*;
+
+async fn slow() -> String {
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    "done".to_string()
+}
+
+async fn remaining(deadline: Option<GrpcDeadline>) -> String {
+    match deadline {
+        Some(deadline) => deadline.remaining().as_millis().to_string(),
+        None => "none".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn grpc_deadline() {
+    let handle = spawn_router(
+        Router::new()
+            .post("/test.Service/Slow", slow)
+            .post("/test.Service/Remaining", remaining)
+            .wrap("/", GrpcDeadlines::default()),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    let start = Instant::now();
+    let response = client
+        .post(format!("http://{}/test.Service/Slow", *TEST_ADDRESS))
+        .header("grpc-timeout", "50m")
+        .send()
+        .await
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_millis(400));
+    assert_eq!(response.headers()["content-type"], "application/grpc+proto");
+    assert!(response.bytes().await.unwrap().is_empty());
+
+    let response = client
+        .post(format!("http://{}/test.Service/Slow", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "done");
+
+    let remaining: u64 = client
+        .post(format!("http://{}/test.Service/Remaining", *TEST_ADDRESS))
+        .header("grpc-timeout", "2S")
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(remaining > 1000 && remaining <= 2000);
+
+    let response = client
+        .post(format!("http://{}/test.Service/Remaining", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "none");
+
+    handle.abort();
+}
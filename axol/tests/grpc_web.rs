@@ -0,0 +1,81 @@
+#![cfg(feature = "grpc")]
+
+use axol::{
+    grpc::{Grpc, GrpcWeb, Status},
+    Error, Result, Router,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use prost::Message;
+
+mod common;
+use common::*;
+
+async fn echo(request: Grpc<String>) -> Grpc<String> {
+    request
+}
+
+async fn missing() -> Result<Grpc<String>> {
+    Err(Error::Grpc(Status::NotFound))
+}
+
+fn frame(flag: u8, message: &[u8]) -> Vec<u8> {
+    let mut out = vec![flag];
+    out.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    out.extend_from_slice(message);
+    out
+}
+
+#[tokio::test]
+async fn grpc_web() {
+    let handle = spawn_router(
+        Router::new()
+            .post("/test.Echo/Echo", echo)
+            .post("/test.Echo/Missing", missing)
+            .wrap("/", GrpcWeb),
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let message = "hello".to_string().encode_to_vec();
+
+    let response = client
+        .post(format!("http://{}/test.Echo/Echo", *TEST_ADDRESS))
+        .header("content-type", "application/grpc-web+proto")
+        .body(frame(0, &message))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/grpc-web+proto"
+    );
+    let mut expected = frame(0, &message);
+    expected.extend(frame(0x80, b"grpc-status:0\r\n"));
+    assert_eq!(&response.bytes().await.unwrap()[..], &expected[..]);
+
+    let response = client
+        .post(format!("http://{}/test.Echo/Echo", *TEST_ADDRESS))
+        .header("content-type", "application/grpc-web-text")
+        .body(STANDARD.encode(frame(0, &message)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/grpc-web-text+proto"
+    );
+    let body = STANDARD.decode(response.bytes().await.unwrap()).unwrap();
+    assert_eq!(body, expected);
+
+    let response = client
+        .post(format!("http://{}/test.Echo/Missing", *TEST_ADDRESS))
+        .header("content-type", "application/grpc-web")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        &response.bytes().await.unwrap()[..],
+        &frame(0x80, b"grpc-status:5\r\n")[..]
+    );
+
+    handle.abort();
+}
@@ -0,0 +1,36 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use axol::Router;
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+#[tokio::test]
+async fn health() {
+    let ready = Arc::new(AtomicBool::new(false));
+    let check = ready.clone();
+    let handle = spawn_router(Router::new().with_health("/healthz", "/readyz", move || {
+        let ready = check.load(Ordering::SeqCst);
+        Box::pin(async move { ready })
+    }))
+    .await;
+
+    let get = |path: &'static str| async move {
+        let response = reqwest::get(format!("http://{}{path}", *TEST_ADDRESS))
+            .await
+            .unwrap();
+        StatusCode::from(response.status())
+    };
+
+    assert_eq!(get("/healthz").await, StatusCode::Ok);
+    assert_eq!(get("/readyz").await, StatusCode::ServiceUnavailable);
+    ready.store(true, Ordering::SeqCst);
+    assert_eq!(get("/readyz").await, StatusCode::Ok);
+    assert_eq!(get("/healthz").await, StatusCode::Ok);
+
+    handle.abort();
+}
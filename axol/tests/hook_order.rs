@@ -0,0 +1,69 @@
+use axol::{Error, Result, Router};
+use axol_http::{header::HeaderMap, response::Response, StatusCode};
+
+mod common;
+use common::*;
+
+async fn gate(headers: HeaderMap) -> Result<Option<(StatusCode, &'static str)>> {
+    if headers.contains_key("x-fail") {
+        return Err(Error::Unauthorized);
+    }
+    if headers.contains_key("x-short") {
+        return Ok(Some((StatusCode::Accepted, "short-circuited")));
+    }
+    Ok(None)
+}
+
+async fn stamp(mut response: Response) -> Response {
+    response.headers.insert("x-late", "1");
+    response
+}
+
+async fn handler() -> &'static str {
+    "handled"
+}
+
+#[tokio::test]
+async fn hook_order_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/", handler)
+            .request_hook("/", gate)
+            .late_response_hook("/", stamp),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.headers()["x-late"], "1");
+    assert_eq!(response.text().await.unwrap(), "handled");
+
+    // responses produced by request hooks still go through late response hooks
+    let response = client
+        .get(format!("http://{}/", *TEST_ADDRESS))
+        .header("x-short", "1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Accepted, response.status().into());
+    assert_eq!(response.headers()["x-late"], "1");
+    assert_eq!(response.text().await.unwrap(), "short-circuited");
+
+    // as do errors returned by request hooks
+    let response = client
+        .get(format!("http://{}/", *TEST_ADDRESS))
+        .header("x-fail", "1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Unauthorized, response.status().into());
+    assert_eq!(response.headers()["x-late"], "1");
+
+    handle.abort();
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use axol::{Router, Server};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn http2_prior_knowledge_tests() {
+    let server = Server::bind(*TEST_ADDRESS)
+        .unwrap()
+        .router(Router::new().get("/hello", hello))
+        .http2_only(true)
+        .http2_adaptive_window(true)
+        .http2_max_concurrent_streams(16);
+    let handle = tokio::spawn(server.serve());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let client = reqwest::Client::builder()
+        .http2_prior_knowledge()
+        .build()
+        .unwrap();
+    let response = client
+        .get(format!("http://{}/hello", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.version(), reqwest::Version::HTTP_2);
+    assert_eq!(&response.bytes().await.unwrap(), &b"hello"[..]);
+
+    // no upgrade handshake, so HTTP/1 clients are rejected
+    assert!(reqwest::get(format!("http://{}/hello", *TEST_ADDRESS))
+        .await
+        .is_err());
+
+    handle.abort();
+}
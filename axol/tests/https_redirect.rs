@@ -0,0 +1,58 @@
+use axol::{HttpsRedirect, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn https_redirect() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/page", hello)
+            .get("/health", hello)
+            .request_hook_direct("/", HttpsRedirect::new().skip("/health")),
+    )
+    .await;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let response = client
+        .get(format!("http://{}/page?a=1", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PermanentRedirect, response.status().into());
+    assert_eq!(response.headers()["location"], "https://127.0.0.1/page?a=1");
+
+    let response = client
+        .get(format!("http://{}/page", *TEST_ADDRESS))
+        .header("x-forwarded-proto", "https")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "hello");
+
+    let response = client
+        .get(format!("http://{}/page", *TEST_ADDRESS))
+        .header("x-forwarded-proto", "http")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PermanentRedirect, response.status().into());
+
+    let response = client
+        .get(format!("http://{}/health", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+
+    handle.abort();
+}
@@ -0,0 +1,67 @@
+use axol::{Json, JsonConfig, Router};
+use axol_http::StatusCode;
+use serde::Deserialize;
+use serde_json::Value;
+
+mod common;
+use common::*;
+
+#[derive(Deserialize)]
+struct Greeting {
+    name: String,
+}
+
+async fn greet(Json(greeting): Json<Greeting>) -> String {
+    format!("hello {}", greeting.name)
+}
+
+#[tokio::test]
+async fn json_config_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .post("/lenient", greet)
+            .post("/strict", greet)
+            .extension("/strict", JsonConfig::strict()),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let post = |path: &'static str, content_type: &'static str, body: &'static str| {
+        client
+            .post(format!("http://{}{path}", *TEST_ADDRESS))
+            .header("content-type", content_type)
+            .body(body)
+            .send()
+    };
+
+    let response = post("/strict", "application/json", r#"{"name":"axol"}"#)
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "hello axol");
+
+    // unknown fields are only rejected in strict mode
+    let body = r#"{"name":"axol","admin":true}"#;
+    let response = post("/lenient", "application/json", body).await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "hello axol");
+
+    let response = post("/strict", "application/json", body).await.unwrap();
+    assert_eq!(StatusCode::UnprocessableEntity, response.status().into());
+    let rejection = response.json::<Value>().await.unwrap();
+    assert_eq!(rejection["path"], "admin");
+
+    // `+json` structured syntax suffixes are json
+    let response = post("/strict", "application/vnd.foo+json", r#"{"name":"axol"}"#)
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "hello axol");
+
+    let response = post("/strict", "text/plain", r#"{"name":"axol"}"#)
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UnsupportedMediaType, response.status().into());
+
+    handle.abort();
+}
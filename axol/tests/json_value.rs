@@ -0,0 +1,44 @@
+use axol::{Error, Result, Router};
+use axol_http::StatusCode;
+use serde_json::{json, Value};
+
+mod common;
+use common::*;
+
+async fn value() -> Value {
+    json!({ "name": "axol", "tags": ["http", "web"] })
+}
+
+async fn fallible_value() -> Result<Value> {
+    Err(Error::Forbidden)
+}
+
+#[tokio::test]
+async fn json_value_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/value", value)
+            .get("/fallible", fallible_value),
+    )
+    .await;
+
+    let response = reqwest::get(format!("http://{}/value", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(
+        response.json::<Value>().await.unwrap(),
+        json!({ "name": "axol", "tags": ["http", "web"] })
+    );
+
+    let response = reqwest::get(format!("http://{}/fallible", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Forbidden, response.status().into());
+
+    handle.abort();
+}
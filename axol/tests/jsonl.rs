@@ -0,0 +1,33 @@
+use axol::{Jsonl, Result, Router};
+use axol_http::StatusCode;
+use futures::Stream;
+use serde::Serialize;
+
+mod common;
+use common::*;
+
+#[derive(Serialize)]
+struct Row {
+    id: u32,
+}
+
+async fn export() -> Jsonl<impl Stream<Item = Result<Row>>> {
+    Jsonl::new(futures::stream::iter((1..=3).map(|id| Ok(Row { id }))))
+}
+
+#[tokio::test]
+async fn jsonl_tests() {
+    let handle = spawn_router(Router::new().get("/export", export)).await;
+
+    let response = reqwest::get(format!("http://{}/export", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    assert_eq!(
+        response.text().await.unwrap(),
+        "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n"
+    );
+
+    handle.abort();
+}
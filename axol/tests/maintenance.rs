@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use axol::{MaintenanceMode, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn maintenance_mode() {
+    let maintenance = MaintenanceMode::new()
+        .retry_after(Duration::from_secs(120))
+        .allow("/healthz");
+    let handle = maintenance.handle();
+    let server = spawn_router(
+        Router::new()
+            .get("/page", hello)
+            .get("/healthz", hello)
+            .request_hook_direct("/", maintenance),
+    )
+    .await;
+
+    let get = |path: &'static str| async move {
+        reqwest::get(format!("http://{}{path}", *TEST_ADDRESS))
+            .await
+            .unwrap()
+    };
+
+    assert_eq!(StatusCode::Ok, get("/page").await.status().into());
+
+    handle.enable();
+    let response = get("/page").await;
+    assert_eq!(StatusCode::ServiceUnavailable, response.status().into());
+    assert_eq!(response.headers()["retry-after"], "120");
+    assert_eq!(StatusCode::Ok, get("/healthz").await.status().into());
+
+    handle.disable();
+    assert_eq!(StatusCode::Ok, get("/page").await.status().into());
+
+    server.abort();
+}
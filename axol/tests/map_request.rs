@@ -0,0 +1,49 @@
+use axol::{Extension, Router};
+use axol_http::header::HeaderMap;
+
+mod common;
+use common::*;
+
+#[derive(Clone)]
+struct Tenant(String);
+
+async fn show(headers: HeaderMap, Extension(tenant): Extension<Tenant>) -> String {
+    format!(
+        "{} {}",
+        headers.get("x-mapped").unwrap_or_default(),
+        tenant.0
+    )
+}
+
+#[tokio::test]
+async fn map_request() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/show", show)
+            .map_request("/", |request| {
+                request.headers.insert("x-mapped", "yes");
+            })
+            .map_request_async("/", |request| {
+                Box::pin(async move {
+                    let tenant = request
+                        .headers
+                        .get("x-tenant")
+                        .unwrap_or("none")
+                        .to_string();
+                    request.extensions.insert(Tenant(tenant));
+                })
+            }),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("http://{}/show", *TEST_ADDRESS))
+        .header("x-tenant", "acme")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "yes acme");
+
+    handle.abort();
+}
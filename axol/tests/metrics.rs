@@ -0,0 +1,121 @@
+#![cfg(feature = "metrics")]
+
+use std::sync::OnceLock;
+
+use axol::{Error, Metrics, Path, Result, Router};
+use axol_http::StatusCode;
+use metrics_util::{
+    debugging::{DebugValue, DebuggingRecorder, Snapshotter},
+    CompositeKey,
+};
+
+mod common;
+use common::*;
+
+static SNAPSHOTTER: OnceLock<Snapshotter> = OnceLock::new();
+
+// taking a snapshot drains histograms, so each test step takes a single snapshot
+fn snapshot() -> Vec<(CompositeKey, DebugValue)> {
+    SNAPSHOTTER
+        .get()
+        .unwrap()
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|x| (x.0, x.3))
+        .collect()
+}
+
+fn metric<'a>(
+    snapshot: &'a [(CompositeKey, DebugValue)],
+    name: &str,
+    route: &str,
+    status: Option<&str>,
+) -> Option<&'a DebugValue> {
+    snapshot
+        .iter()
+        .find(|(key, _)| {
+            let key = key.key();
+            let label = |label: &str| {
+                key.labels()
+                    .find(|x| x.key() == label)
+                    .map(|x| x.value().to_string())
+            };
+            key.name() == name
+                && label("method").as_deref() == Some("GET")
+                && label("route").as_deref() == Some(route)
+                && label("status").as_deref() == status
+        })
+        .map(|x| &x.1)
+}
+
+async fn in_flight() -> String {
+    match metric(&snapshot(), "axol_requests_in_flight", "/in-flight", None) {
+        Some(DebugValue::Gauge(value)) => value.0.to_string(),
+        value => panic!("unexpected gauge {value:?}"),
+    }
+}
+
+async fn user(Path(id): Path<u32>) -> Result<String> {
+    if id == 0 {
+        return Err(Error::NotFound);
+    }
+    Ok(format!("user {id}"))
+}
+
+#[tokio::test]
+async fn metrics_tests() {
+    let recorder = DebuggingRecorder::new();
+    SNAPSHOTTER.set(recorder.snapshotter()).ok().unwrap();
+    recorder.install().unwrap();
+
+    let handle = spawn_router(
+        Router::new()
+            .get("/in-flight", in_flight)
+            .get("/users/:id", user)
+            .wrap("/", Metrics::default()),
+    )
+    .await;
+
+    let get = |path: &'static str| reqwest::get(format!("http://{}{path}", *TEST_ADDRESS));
+
+    // the handler runs while its own request is in flight
+    let response = get("/in-flight").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "1");
+    assert_eq!(
+        metric(&snapshot(), "axol_requests_in_flight", "/in-flight", None),
+        Some(&DebugValue::Gauge(0.0.into()))
+    );
+
+    let response = get("/users/1").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    let response = get("/users/2").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    let response = get("/users/0").await.unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+
+    // labeled by the route pattern and status class
+    let snapshot = snapshot();
+    assert_eq!(
+        metric(&snapshot, "axol_requests_total", "/users/:id", Some("2xx")),
+        Some(&DebugValue::Counter(2))
+    );
+    assert_eq!(
+        metric(&snapshot, "axol_requests_total", "/users/:id", Some("4xx")),
+        Some(&DebugValue::Counter(1))
+    );
+    match metric(
+        &snapshot,
+        "axol_request_duration_seconds",
+        "/users/:id",
+        Some("2xx"),
+    ) {
+        Some(DebugValue::Histogram(values)) => {
+            assert_eq!(values.len(), 2);
+            assert!(values.iter().all(|x| x.0 >= 0.0));
+        }
+        value => panic!("unexpected histogram {value:?}"),
+    }
+
+    handle.abort();
+}
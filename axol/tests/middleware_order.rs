@@ -0,0 +1,107 @@
+use std::sync::{Arc, Mutex};
+
+use axol::{LateResponseHook, RequestHook, Result, Router, Wrap, WrapState};
+use axol_http::{
+    request::{Request, RequestPartsRef},
+    response::Response,
+};
+
+mod common;
+use common::*;
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+struct Record(&'static str, Log);
+
+impl Record {
+    fn push(&self, event: String) {
+        self.1.lock().unwrap().push(event);
+    }
+}
+
+#[async_trait::async_trait]
+impl Wrap for Record {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        self.push(format!("{}>", self.0));
+        let out = state.next().await;
+        self.push(format!("<{}", self.0));
+        out
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHook for Record {
+    async fn handle_request(&self, _request: &mut Request) -> Result<Option<Response>> {
+        self.push(format!("request {}", self.0));
+        Ok(None)
+    }
+}
+
+#[async_trait::async_trait]
+impl LateResponseHook for Record {
+    async fn handle_response<'a>(&self, _request: RequestPartsRef<'a>, _response: &mut Response) {
+        self.push(format!("late {}", self.0));
+    }
+}
+
+#[tokio::test]
+async fn middleware_order() {
+    let log = Log::default();
+    let record = |name| Record(name, log.clone());
+
+    let handler_log = log.clone();
+    let child = Router::new()
+        .get("/leaf", move || {
+            let log = handler_log.clone();
+            async move {
+                log.lock().unwrap().push("handler".to_string());
+                "ok"
+            }
+        })
+        .wrap("/", record("child"))
+        .wrap("/leaf", record("leaf"))
+        .request_hook_direct("/", record("child"))
+        .late_response_hook_direct("/", record("child"));
+
+    let handle = spawn_router(
+        Router::new()
+            .wrap("/", record("root1"))
+            .wrap("/", record("root2"))
+            .outer_wrap("/", record("outer"))
+            .request_hook_direct("/", record("root"))
+            .late_response_hook_direct("/", record("root"))
+            .wrap("/api", record("api"))
+            .nest("/api", child),
+    )
+    .await;
+
+    let response = reqwest::get(format!("http://{}/api/leaf", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "ok");
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "outer>",
+            "request root",
+            "request child",
+            "root1>",
+            "root2>",
+            "api>",
+            "child>",
+            "leaf>",
+            "handler",
+            "<leaf",
+            "<child",
+            "<api",
+            "<root2",
+            "<root1",
+            "<outer",
+            "late root",
+            "late child",
+        ]
+    );
+
+    handle.abort();
+}
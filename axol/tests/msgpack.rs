@@ -0,0 +1,72 @@
+use axol::{MsgPack, Router};
+use axol_http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+mod common;
+use common::*;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+    label: Option<String>,
+}
+
+async fn flip(MsgPack(point): MsgPack<Point>) -> MsgPack<Point> {
+    MsgPack(Point {
+        x: point.y,
+        y: point.x,
+        label: point.label,
+    })
+}
+
+#[tokio::test]
+async fn msgpack_tests() {
+    let handle = spawn_router(Router::new().post("/flip", flip)).await;
+
+    let client = reqwest::Client::new();
+
+    let point = Point {
+        x: 1,
+        y: -2,
+        label: Some("a".to_string()),
+    };
+    let response = client
+        .post(format!("http://{}/flip", *TEST_ADDRESS))
+        .header("content-type", "application/msgpack")
+        .body(rmp_serde::to_vec_named(&point).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.headers()["content-type"], "application/msgpack");
+    let flipped: Point = rmp_serde::from_slice(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!(
+        flipped,
+        Point {
+            x: -2,
+            y: 1,
+            label: Some("a".to_string()),
+        }
+    );
+
+    let response = client
+        .post(format!("http://{}/flip", *TEST_ADDRESS))
+        .header("content-type", "application/msgpack")
+        .body(rmp_serde::to_vec_named(&("not", "a", "point")).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UnprocessableEntity, response.status().into());
+
+    let response = client
+        .post(format!("http://{}/flip", *TEST_ADDRESS))
+        .header("content-type", "application/json")
+        .body("{}")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::UnsupportedMediaType, response.status().into());
+
+    handle.abort();
+}
@@ -0,0 +1,68 @@
+use axol::{Multipart, MultipartConfig, MultipartValue, Result, Router};
+use axol_http::StatusCode;
+use reqwest::multipart::{Form, Part};
+
+mod common;
+use common::*;
+
+async fn describe(multipart: Multipart) -> Result<String> {
+    let map = multipart.into_map().await?;
+    match (map.get("title"), map.get("upload")) {
+        (
+            Some(MultipartValue::Text(title)),
+            Some(MultipartValue::File {
+                filename, bytes, ..
+            }),
+        ) => Ok(format!("{title} {filename} {}", bytes.len())),
+        _ => Ok("missing fields".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn multipart_map_tests() {
+    let handle = spawn_router(Router::new().post("/mp", describe).extension(
+        "/",
+        MultipartConfig {
+            field_limit: 16,
+            total_limit: 24,
+        },
+    ))
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let form = Form::new().text("title", "notes").part(
+        "upload",
+        Part::bytes(&b"test message"[..]).file_name("test.txt"),
+    );
+    let response = client
+        .post(format!("http://{}/mp", *TEST_ADDRESS))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "notes test.txt 12");
+
+    let form = Form::new().part("upload", Part::bytes(vec![0u8; 17]).file_name("big.bin"));
+    let response = client
+        .post(format!("http://{}/mp", *TEST_ADDRESS))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PayloadTooLarge, response.status().into());
+
+    let form = Form::new()
+        .text("title", "0123456789")
+        .part("upload", Part::bytes(vec![0u8; 16]).file_name("total.bin"));
+    let response = client
+        .post(format!("http://{}/mp", *TEST_ADDRESS))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PayloadTooLarge, response.status().into());
+
+    handle.abort();
+}
@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use axol::{Multipart, Result, Router};
+use axol_http::StatusCode;
+use reqwest::multipart::{Form, Part};
+
+mod common;
+use common::*;
+
+fn upload_path() -> PathBuf {
+    std::env::temp_dir().join(format!("axol-multipart-save-{}", std::process::id()))
+}
+
+async fn save(mut multipart: Multipart) -> Result<String> {
+    let field = multipart.next_field().await?.unwrap();
+    let written = field.save_to(upload_path(), Some(16)).await?;
+    Ok(written.to_string())
+}
+
+#[tokio::test]
+async fn multipart_save_tests() {
+    let handle = spawn_router(Router::new().post("/mp", save)).await;
+
+    let client = reqwest::Client::new();
+
+    let form = Form::new().part(
+        "upload",
+        Part::bytes(&b"test message"[..]).file_name("test.txt"),
+    );
+    let response = client
+        .post(format!("http://{}/mp", *TEST_ADDRESS))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "12");
+    assert_eq!(std::fs::read(upload_path()).unwrap(), b"test message");
+
+    let form = Form::new().part("upload", Part::bytes(vec![0u8; 17]).file_name("big.bin"));
+    let response = client
+        .post(format!("http://{}/mp", *TEST_ADDRESS))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PayloadTooLarge, response.status().into());
+    assert!(!upload_path().exists());
+
+    handle.abort();
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use axol::{NegotiatedProtocol, Router, Server, TlsIncoming};
+use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore, ServerConfig, ServerName};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+
+mod common;
+use common::*;
+
+async fn protocol(protocol: Option<NegotiatedProtocol>) -> String {
+    match protocol {
+        Some(protocol) => protocol.0,
+        None => "none".to_string(),
+    }
+}
+
+async fn request(roots: &RootCertStore, alpn: &[&[u8]]) -> String {
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots.clone())
+        .with_no_client_auth();
+    config.alpn_protocols = alpn.iter().map(|x| x.to_vec()).collect();
+    let tcp = TcpStream::connect(*TEST_ADDRESS).await.unwrap();
+    let stream = TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), tcp)
+        .await
+        .unwrap();
+    let (mut sender, connection) = hyper::client::conn::handshake(stream).await.unwrap();
+    tokio::spawn(connection);
+    let request = hyper::Request::get("/protocol")
+        .header("host", "localhost")
+        .body(hyper::Body::empty())
+        .unwrap();
+    let response = sender.send_request(request).await.unwrap();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn negotiated_protocol_tests() {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert_der = Certificate(cert.serialize_der().unwrap());
+    let mut server_config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![cert_der.clone()],
+            PrivateKey(cert.serialize_private_key_der()),
+        )
+        .unwrap();
+    server_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    let (incoming, _reloader) =
+        TlsIncoming::new_reloadable(*TEST_ADDRESS, true, None, Some(server_config)).unwrap();
+    let handle = tokio::spawn(
+        Server::builder()
+            .incoming(incoming.start())
+            .router(Router::new().get("/protocol", protocol))
+            .serve(),
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    let mut roots = RootCertStore::empty();
+    roots.add(&cert_der).unwrap();
+
+    assert_eq!(request(&roots, &[b"http/1.1"]).await, "http/1.1");
+    assert_eq!(request(&roots, &[]).await, "none");
+
+    handle.abort();
+}
@@ -0,0 +1,67 @@
+use axol::{Handler, NestedPath, Result, Router};
+use axol_http::{request::RequestPartsRef, response::Response, Body, StatusCode};
+
+mod common;
+use common::*;
+
+struct Echo;
+
+#[async_trait::async_trait]
+impl Handler for Echo {
+    async fn call<'a>(&self, request: RequestPartsRef<'a>, _body: Body) -> Result<Response> {
+        let nested = request.extensions.get::<NestedPath>().unwrap();
+        Ok(Response {
+            body: Body::Bytes(format!("{} {}", request.method, nested.0).into_bytes()),
+            ..Default::default()
+        })
+    }
+}
+
+async fn own() -> &'static str {
+    "own"
+}
+
+#[tokio::test]
+async fn nest_service_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .nest_service("/svc", Echo)
+            .get("/svc/own", own),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/svc/a/b%20c", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "GET /a/b%20c");
+
+    let response = client
+        .post(format!("http://{}/svc", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "POST /");
+
+    let response = client
+        .get(format!("http://{}/svc/own", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "own");
+
+    let response = client
+        .get(format!("http://{}/other", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+
+    handle.abort();
+}
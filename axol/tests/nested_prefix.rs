@@ -0,0 +1,60 @@
+use axol::{FromRequestParts, Handler, NestedPath, NestedPrefix, Result, Router};
+use axol_http::{request::RequestPartsRef, response::Response, Body};
+
+mod common;
+use common::*;
+
+async fn links(NestedPrefix(prefix): NestedPrefix, NestedPath(path): NestedPath) -> String {
+    format!("{prefix} {path}")
+}
+
+async fn prefix_only(NestedPrefix(prefix): NestedPrefix) -> String {
+    format!("[{prefix}]")
+}
+
+struct Echo;
+
+#[async_trait::async_trait]
+impl Handler for Echo {
+    async fn call<'a>(&self, request: RequestPartsRef<'a>, _body: Body) -> Result<Response> {
+        let NestedPrefix(prefix) = NestedPrefix::from_request_parts(request).await?;
+        Ok(Response {
+            body: Body::Bytes(prefix.into_bytes()),
+            ..Default::default()
+        })
+    }
+}
+
+#[tokio::test]
+async fn nested_prefix() {
+    let posts = Router::new().get("/posts/:post", links);
+    let api = Router::new().nest("/v1/users/:id", posts.clone());
+    let handle = spawn_router(
+        Router::new()
+            .get("/top", prefix_only)
+            .nest("/users/:id", posts)
+            .nest("/api", api)
+            .nest_service("/svc/:tenant", Echo),
+    )
+    .await;
+
+    let get = |path: &'static str| async move {
+        reqwest::get(format!("http://{}{path}", *TEST_ADDRESS))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap()
+    };
+
+    assert_eq!(get("/top").await, "[]");
+    assert_eq!(get("/users/42/posts/7").await, "/users/42 /posts/7");
+    // the innermost nest wins, offset by the nests around it
+    assert_eq!(
+        get("/api/v1/users/42/posts/7").await,
+        "/api/v1/users/42 /posts/7"
+    );
+    assert_eq!(get("/svc/acme/a/b").await, "/svc/acme");
+
+    handle.abort();
+}
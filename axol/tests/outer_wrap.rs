@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use axol::{Result, Router, Wrap, WrapState};
+use axol_http::{header::HeaderMap, response::Response, StatusCode};
+
+mod common;
+use common::*;
+
+struct Stamp(&'static str);
+
+#[async_trait::async_trait]
+impl Wrap for Stamp {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let mut response = state.next().await?;
+        response.headers.append("x-stamp", self.0);
+        Ok(response)
+    }
+}
+
+async fn deny(headers: HeaderMap) -> Result<Option<StatusCode>> {
+    Ok(headers
+        .contains_key("x-deny")
+        .then_some(StatusCode::Forbidden))
+}
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn outer_wrap() {
+    let shared: Arc<dyn Wrap> = Arc::new(Stamp("shared"));
+    let handle = spawn_router(
+        Router::new()
+            .get("/a", hello)
+            .get("/b", hello)
+            .outer_wrap("/", Stamp("outer"))
+            .wrap("/", Stamp("inner"))
+            .request_hook("/", deny)
+            .wrap_direct("/a", shared.clone())
+            .outer_wrap_direct("/b", shared),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    let stamps = |response: &reqwest::Response| {
+        response
+            .headers()
+            .get_all("x-stamp")
+            .iter()
+            .map(|x| x.to_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let response = client
+        .get(format!("http://{}/a", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stamps(&response), ["shared", "inner", "outer"]);
+
+    let response = client
+        .get(format!("http://{}/b", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stamps(&response), ["inner", "shared", "outer"]);
+
+    // responses from request hooks only pass through outer wraps
+    let response = client
+        .get(format!("http://{}/a", *TEST_ADDRESS))
+        .header("x-deny", "1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Forbidden, response.status().into());
+    assert_eq!(stamps(&response), ["outer"]);
+
+    handle.abort();
+}
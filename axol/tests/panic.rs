@@ -0,0 +1,35 @@
+use axol::Router;
+use axol_http::{response::Response, Body, StatusCode};
+
+mod common;
+use common::*;
+
+async fn explode() -> &'static str {
+    panic!("kaboom")
+}
+
+fn render_panic(message: String) -> Response {
+    Response {
+        status: StatusCode::ServiceUnavailable,
+        body: Body::Bytes(format!("sorry: {message}").into_bytes()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn panic_handler_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/explode", explode)
+            .panic_handler(render_panic),
+    )
+    .await;
+
+    let response = reqwest::get(format!("http://{}/explode", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::ServiceUnavailable, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "sorry: kaboom");
+
+    handle.abort();
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use axol::{Path, Router};
+use axol_http::StatusCode;
+use serde::Deserialize;
+
+mod common;
+use common::*;
+
+async fn tuple(Path((uid, pid)): Path<(u32, String)>) -> String {
+    format!("{uid} {pid}")
+}
+
+async fn map(Path(variables): Path<HashMap<String, String>>) -> String {
+    format!("{} {}", variables["uid"], variables["pid"])
+}
+
+#[derive(Deserialize)]
+struct Post {
+    uid: u32,
+    pid: u64,
+}
+
+async fn structured(Path(post): Path<Post>) -> String {
+    format!("{} {}", post.uid, post.pid)
+}
+
+#[tokio::test]
+async fn path_extractor() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/tuple/users/:uid/posts/:pid", tuple)
+            .get("/map/users/:uid/posts/:pid", map)
+            .get("/struct/users/:uid/posts/:pid", structured),
+    )
+    .await;
+
+    let get = |path: &'static str| async move {
+        let response = reqwest::get(format!("http://{}{path}", *TEST_ADDRESS))
+            .await
+            .unwrap();
+        let status: StatusCode = response.status().into();
+        (status, response.text().await.unwrap())
+    };
+
+    assert_eq!(
+        get("/tuple/users/1/posts/first").await,
+        (StatusCode::Ok, "1 first".to_string())
+    );
+    assert_eq!(
+        get("/map/users/1/posts/first").await,
+        (StatusCode::Ok, "1 first".to_string())
+    );
+    assert_eq!(
+        get("/struct/users/1/posts/2").await,
+        (StatusCode::Ok, "1 2".to_string())
+    );
+
+    let (status, body) = get("/tuple/users/x/posts/first").await;
+    assert_eq!(status, StatusCode::BadRequest);
+    assert!(body.contains("`uid`") && body.contains("u32"), "{body}");
+
+    let (status, body) = get("/struct/users/1/posts/x").await;
+    assert_eq!(status, StatusCode::BadRequest);
+    assert!(body.contains("`pid`") && body.contains("u64"), "{body}");
+
+    handle.abort();
+}
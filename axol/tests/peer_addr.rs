@@ -0,0 +1,39 @@
+use std::net::SocketAddr;
+
+use axol::{PeerAddr, RealIp, Router};
+
+mod common;
+use common::*;
+
+async fn peer(address: SocketAddr, PeerAddr(ip): PeerAddr) -> String {
+    format!("{} {ip}", address.ip())
+}
+
+#[tokio::test]
+async fn peer_addr() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/direct", peer)
+            .get("/proxied", peer)
+            .request_hook_direct(
+                "/proxied",
+                RealIp::new("x-forwarded-for").trust_proxy("127.0.0.0/8".parse().unwrap()),
+            ),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let get = |path: &'static str| {
+        client
+            .get(format!("http://{}{path}", *TEST_ADDRESS))
+            .header("x-forwarded-for", "203.0.113.7")
+            .send()
+    };
+
+    let response = get("/direct").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "127.0.0.1 127.0.0.1");
+    let response = get("/proxied").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "203.0.113.7 203.0.113.7");
+
+    handle.abort();
+}
@@ -0,0 +1,101 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axol::{Preconditions, Result, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+const ETAG: &str = "\"v2\"";
+
+fn last_modified() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+}
+
+async fn read(preconditions: Preconditions) -> Result<&'static str> {
+    preconditions.check(Some(ETAG), Some(last_modified()))?;
+    Ok("document")
+}
+
+async fn update(preconditions: Preconditions) -> Result<&'static str> {
+    preconditions.check(Some(ETAG), Some(last_modified()))?;
+    Ok("updated")
+}
+
+#[tokio::test]
+async fn preconditions_tests() {
+    let handle = spawn_router(Router::new().get("/doc", read).put("/doc", update)).await;
+    let url = format!("http://{}/doc", *TEST_ADDRESS);
+    let client = reqwest::Client::new();
+
+    let response = client.put(&url).send().await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+
+    let response = client
+        .put(&url)
+        .header("if-match", "\"v1\"")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PreconditionFailed, response.status().into());
+
+    let response = client
+        .put(&url)
+        .header("if-match", "\"v1\", \"v2\"")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "updated");
+
+    // if-match uses strong comparison
+    let response = client
+        .put(&url)
+        .header("if-match", "W/\"v2\"")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PreconditionFailed, response.status().into());
+
+    let response = client
+        .put(&url)
+        .header("if-unmodified-since", "Tue, 14 Nov 2023 22:00:00 GMT")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PreconditionFailed, response.status().into());
+
+    let response = client
+        .put(&url)
+        .header("if-none-match", "*")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PreconditionFailed, response.status().into());
+
+    let response = client
+        .get(&url)
+        .header("if-none-match", "W/\"v2\"")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotModified, response.status().into());
+
+    let response = client
+        .get(&url)
+        .header("if-modified-since", "Tue, 14 Nov 2023 22:13:20 GMT")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotModified, response.status().into());
+
+    let response = client
+        .get(&url)
+        .header("if-modified-since", "Tue, 14 Nov 2023 22:00:00 GMT")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+
+    handle.abort();
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "problem-json")]
+
+use axol::{Error, Result, Router};
+use axol_http::StatusCode;
+use serde_json::{json, Value};
+
+mod common;
+use common::*;
+
+async fn conflict() -> Result<()> {
+    Err(Error::Conflict.with_detail("user already exists"))
+}
+
+async fn redirect() -> Result<()> {
+    Err(Error::found(axol_http::Uri::from_static("/elsewhere")))
+}
+
+#[tokio::test]
+async fn problem_json_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/conflict", conflict)
+            .get("/redirect", redirect),
+    )
+    .await;
+
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let response = client
+        .get(format!("http://{}/conflict", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Conflict, response.status().into());
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+    assert_eq!(
+        response.json::<Value>().await.unwrap(),
+        json!({
+            "type": "about:blank",
+            "title": "Conflict",
+            "status": 409,
+            "detail": "user already exists",
+        })
+    );
+
+    let response = client
+        .get(format!("http://{}/missing", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+    assert_eq!(
+        response.json::<Value>().await.unwrap(),
+        json!({ "type": "about:blank", "title": "Not Found", "status": 404 })
+    );
+
+    let response = client
+        .get(format!("http://{}/redirect", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Found, response.status().into());
+    assert!(response.headers().get("content-type").is_none());
+
+    handle.abort();
+}
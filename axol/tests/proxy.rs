@@ -0,0 +1,64 @@
+use axol::{Proxy, Router};
+use axol_http::{header::HeaderMap, Method, StatusCode, Uri};
+
+mod common;
+use common::*;
+
+async fn upstream(method: Method, uri: Uri, headers: HeaderMap, body: String) -> String {
+    format!(
+        "{method} {uri} {} {body}",
+        headers.get("x-custom").unwrap_or_default()
+    )
+}
+
+#[tokio::test]
+async fn proxy_tests() {
+    let upstream_uri: Uri = format!("http://{}/upstream", *TEST_ADDRESS)
+        .parse()
+        .unwrap();
+    let handle = spawn_router(
+        Router::new()
+            .post("/upstream/*rest", upstream)
+            .nest_service("/proxy", Proxy::new(upstream_uri))
+            .nest_service("/dead", Proxy::new("http://127.0.0.1:1".parse().unwrap())),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{}/proxy/a/b?x=1", *TEST_ADDRESS))
+        .header("x-custom", "value")
+        .header("connection", "keep-alive, x-custom")
+        .body("hello")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(
+        response.text().await.unwrap(),
+        "POST /upstream/a/b?x=1  hello"
+    );
+
+    let response = client
+        .post(format!("http://{}/proxy/a", *TEST_ADDRESS))
+        .header("x-custom", "value")
+        .body("hello")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(
+        response.text().await.unwrap(),
+        "POST /upstream/a value hello"
+    );
+
+    let response = client
+        .get(format!("http://{}/dead/a", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BadGateway, response.status().into());
+
+    handle.abort();
+}
@@ -0,0 +1,107 @@
+use axol::{FromRequestParts, Handler, IntoResponse, Query, QueryConfig, RawQuery, Result, Router};
+use axol_http::{request::RequestPartsRef, response::Response, Body, StatusCode};
+use serde::Deserialize;
+
+mod common;
+use common::*;
+
+#[derive(Deserialize)]
+struct Page {
+    #[serde(default)]
+    page: u32,
+    search: Option<String>,
+}
+
+async fn page(Query(query): Query<Page>) -> String {
+    format!("{} {:?}", query.page, query.search)
+}
+
+async fn optional(Query(query): Query<Option<Page>>) -> String {
+    match query {
+        Some(query) => format!("some {}", query.page),
+        None => "none".to_string(),
+    }
+}
+
+#[derive(Deserialize)]
+struct Borrowed<'a> {
+    name: &'a str,
+}
+
+// borrowed fields can't be handler arguments, so extract by hand
+struct BorrowedHandler;
+
+#[async_trait::async_trait]
+impl Handler for BorrowedHandler {
+    async fn call<'a>(&self, request: RequestPartsRef<'a>, _body: Body) -> Result<Response> {
+        let Query(query) = Query::<Borrowed<'a>>::from_request_parts(request).await?;
+        query.name.to_string().into_response()
+    }
+}
+
+async fn raw(query: Option<RawQuery>) -> String {
+    format!("{:?}", query.map(|x| x.0))
+}
+
+#[tokio::test]
+async fn query_extractor() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/page", page)
+            .get("/optional", optional)
+            .get("/raw", raw)
+            .nest_service("/borrowed", BorrowedHandler)
+            .get("/lenient/page", page)
+            .extension(
+                "/lenient",
+                QueryConfig {
+                    last_value_wins: true,
+                },
+            ),
+    )
+    .await;
+
+    let get = |path: &'static str| async move {
+        let response = reqwest::get(format!("http://{}{path}", *TEST_ADDRESS))
+            .await
+            .unwrap();
+        let status: StatusCode = response.status().into();
+        (status, response.text().await.unwrap())
+    };
+
+    assert_eq!(get("/page").await, (StatusCode::Ok, "0 None".to_string()));
+    assert_eq!(get("/page?").await, (StatusCode::Ok, "0 None".to_string()));
+    assert_eq!(
+        get("/page?page=2&search=a+b").await,
+        (StatusCode::Ok, "2 Some(\"a b\")".to_string())
+    );
+
+    assert_eq!(get("/optional").await, (StatusCode::Ok, "none".to_string()));
+    assert_eq!(
+        get("/optional?page=3").await,
+        (StatusCode::Ok, "some 3".to_string())
+    );
+
+    assert_eq!(get("/raw").await, (StatusCode::Ok, "None".to_string()));
+    assert_eq!(
+        get("/raw?search=a%20b&page=1").await,
+        (StatusCode::Ok, "Some(\"search=a%20b&page=1\")".to_string())
+    );
+
+    // values that need no decoding can be borrowed
+    assert_eq!(
+        get("/borrowed?name=plain").await,
+        (StatusCode::Ok, "plain".to_string())
+    );
+
+    // repeated keys for a single value are rejected, unless configured otherwise
+    let (status, body) = get("/page?page=1&page=2").await;
+    assert_eq!(status, StatusCode::BadRequest);
+    assert!(body.contains("`page`"), "{body}");
+    assert_eq!(
+        get("/lenient/page?page=1&page=2").await,
+        (StatusCode::Ok, "2 None".to_string())
+    );
+
+    handle.abort();
+}
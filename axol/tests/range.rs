@@ -0,0 +1,59 @@
+use axol::{Ranged, Result, Router};
+use axol_http::{response::Response, StatusCode};
+
+mod common;
+use common::*;
+
+fn payload() -> Vec<u8> {
+    (0..1000u32).map(|x| x as u8).collect()
+}
+
+async fn ranged(range: Ranged) -> Result<Response> {
+    range.respond(payload())
+}
+
+#[tokio::test]
+async fn range_tests() {
+    let handle = spawn_router(Router::new().get("/ranged", ranged)).await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/ranged", *TEST_ADDRESS))
+        .header("range", "bytes=0-99")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PartialContent, response.status().into());
+    assert_eq!(response.headers()["content-range"], "bytes 0-99/1000");
+    assert_eq!(response.headers()["accept-ranges"], "bytes");
+    assert_eq!(&response.bytes().await.unwrap()[..], &payload()[..100]);
+
+    let response = client
+        .get(format!("http://{}/ranged", *TEST_ADDRESS))
+        .header("range", "bytes=-10")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PartialContent, response.status().into());
+    assert_eq!(&response.bytes().await.unwrap()[..], &payload()[990..]);
+
+    let response = client
+        .get(format!("http://{}/ranged", *TEST_ADDRESS))
+        .header("range", "bytes=1000-")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::RangeNotSatisfiable, response.status().into());
+    assert_eq!(response.headers()["content-range"], "bytes */1000");
+
+    let response = client
+        .get(format!("http://{}/ranged", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.bytes().await.unwrap().len(), 1000);
+
+    handle.abort();
+}
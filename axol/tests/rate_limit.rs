@@ -0,0 +1,58 @@
+use axol::{RateLimit, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn rate_limit_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/ip", hello)
+            .get("/keyed", hello)
+            .request_hook_direct("/ip", RateLimit::new(0.4).burst(2))
+            .request_hook_direct(
+                "/keyed",
+                RateLimit::new(0.4).burst(1).key(|request| {
+                    request
+                        .headers
+                        .get("x-api-key")
+                        .unwrap_or_default()
+                        .to_string()
+                }),
+            ),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let get = |path: &'static str, key: &'static str| {
+        client
+            .get(format!("http://{}{path}", *TEST_ADDRESS))
+            .header("x-api-key", key)
+            .send()
+    };
+
+    // the burst is used up, then requests are rejected until a token is refilled
+    for _ in 0..2 {
+        let response = get("/ip", "a").await.unwrap();
+        assert_eq!(StatusCode::Ok, response.status().into());
+    }
+    let response = get("/ip", "a").await.unwrap();
+    assert_eq!(StatusCode::TooManyRequests, response.status().into());
+    // a token takes 2.5 seconds to refill, rounded up
+    assert_eq!(response.headers()["retry-after"], "3");
+
+    // the custom key gives each api key its own bucket, regardless of the client address
+    let response = get("/keyed", "a").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    let response = get("/keyed", "a").await.unwrap();
+    assert_eq!(StatusCode::TooManyRequests, response.status().into());
+    let response = get("/keyed", "b").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+
+    handle.abort();
+}
@@ -0,0 +1,107 @@
+use axol::{ConnectInfo, RealIp, Router};
+
+mod common;
+use common::*;
+
+async fn ip(ConnectInfo(address): ConnectInfo) -> String {
+    address.ip().to_string()
+}
+
+#[tokio::test]
+async fn real_ip() {
+    let loopback = "127.0.0.0/8".parse().unwrap();
+    let proxies = "10.0.0.0/8".parse().unwrap();
+    let handle = spawn_router(
+        Router::new()
+            .get("/trusted", ip)
+            .get("/untrusted", ip)
+            .get("/forwarded", ip)
+            .request_hook_direct(
+                "/trusted",
+                RealIp::new("x-forwarded-for")
+                    .trust_proxy(loopback)
+                    .trust_proxy(proxies),
+            )
+            .request_hook_direct(
+                "/untrusted",
+                RealIp::new("x-forwarded-for").trust_proxy(proxies),
+            )
+            .request_hook_direct(
+                "/forwarded",
+                RealIp::new("forwarded").trust_proxies([loopback, proxies]),
+            ),
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let get = |path: &'static str, header: &'static str, value: &'static str| {
+        let client = client.clone();
+        async move {
+            client
+                .get(format!("http://{}/{path}", *TEST_ADDRESS))
+                .header(header, value)
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap()
+        }
+    };
+
+    // the right-most untrusted hop is the client, whatever it prepended is ignored
+    assert_eq!(
+        get("trusted", "x-forwarded-for", "1.1.1.1, 2.2.2.2, 10.0.0.1").await,
+        "2.2.2.2"
+    );
+    // if all hops are trusted, the left-most one is used
+    assert_eq!(
+        get("trusted", "x-forwarded-for", "10.0.0.2, 10.0.0.1").await,
+        "10.0.0.2"
+    );
+    assert_eq!(
+        get("trusted", "x-forwarded-for", "unknown, 10.0.0.1").await,
+        "10.0.0.1"
+    );
+    // untrusted hops end the walk, even if trusted hops are left of them
+    assert_eq!(
+        get(
+            "trusted",
+            "x-forwarded-for",
+            "10.0.0.3, 2.2.2.2, 10.0.0.2, 10.0.0.1"
+        )
+        .await,
+        "2.2.2.2"
+    );
+    // repeated headers are walked as one list
+    let response = client
+        .get(format!("http://{}/trusted", *TEST_ADDRESS))
+        .header("x-forwarded-for", "1.1.1.1")
+        .header("x-forwarded-for", "2.2.2.2, 10.0.0.1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "2.2.2.2");
+    // a trusted peer without the header keeps its own address
+    let response = client
+        .get(format!("http://{}/trusted", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "127.0.0.1");
+    // the peer isn't a trusted proxy, so its header is ignored
+    assert_eq!(
+        get("untrusted", "x-forwarded-for", "2.2.2.2").await,
+        "127.0.0.1"
+    );
+    assert_eq!(
+        get(
+            "forwarded",
+            "forwarded",
+            "for=1.1.1.1, for=\"[2001:db8::1]:4711\";proto=https, for=10.0.0.1:80"
+        )
+        .await,
+        "2001:db8::1"
+    );
+
+    handle.abort();
+}
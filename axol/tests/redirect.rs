@@ -0,0 +1,66 @@
+use axol::{Form, Redirect, Router};
+use axol_http::{StatusCode, Uri};
+use serde::Deserialize;
+
+mod common;
+use common::*;
+
+#[derive(Deserialize)]
+struct NewItem {
+    name: String,
+}
+
+async fn create(Form(item): Form<NewItem>) -> Redirect {
+    Redirect::see_other(Uri::try_from(format!("/items/{}", item.name)).unwrap())
+}
+
+async fn moved() -> Redirect {
+    Redirect::permanent(Uri::from_static("/new"))
+}
+
+async fn temporary() -> Redirect {
+    Redirect::to(Uri::from_static("/elsewhere"))
+}
+
+#[tokio::test]
+async fn redirect_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .post("/items", create)
+            .get("/old", moved)
+            .get("/temporary", temporary),
+    )
+    .await;
+
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let response = client
+        .post(format!("http://{}/items", *TEST_ADDRESS))
+        .form(&[("name", "widget")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::SeeOther, response.status().into());
+    assert_eq!(response.headers()["location"], "/items/widget");
+
+    let response = client
+        .get(format!("http://{}/old", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PermanentRedirect, response.status().into());
+    assert_eq!(response.headers()["location"], "/new");
+
+    let response = client
+        .get(format!("http://{}/temporary", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::TemporaryRedirect, response.status().into());
+    assert_eq!(response.headers()["location"], "/elsewhere");
+
+    handle.abort();
+}
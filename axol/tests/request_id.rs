@@ -0,0 +1,46 @@
+use axol::{RequestId, RequestIdHook, Router};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn echo(request_id: RequestId) -> String {
+    request_id.0
+}
+
+#[tokio::test]
+async fn request_id_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/id", echo)
+            .plugin("/", RequestIdHook::default()),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/id", *TEST_ADDRESS))
+        .header("x-request-id", "abc-123")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.headers()["x-request-id"], "abc-123");
+    assert_eq!(response.text().await.unwrap(), "abc-123");
+
+    let response = client
+        .get(format!("http://{}/id", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    let header = response.headers()["x-request-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(header.len(), 36);
+    assert_eq!(response.text().await.unwrap(), header);
+
+    handle.abort();
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use axol::Error;
+use axol_http::StatusCode;
+
+#[test]
+fn retry_after_tests() {
+    let response =
+        Error::too_many_requests_after(Duration::from_secs(30), "slow down").into_response();
+    assert_eq!(response.status, StatusCode::TooManyRequests);
+    assert_eq!(response.headers.get("retry-after"), Some("30"));
+
+    let response =
+        Error::service_unavailable_after(Duration::from_millis(120_500), ()).into_response();
+    assert_eq!(response.status, StatusCode::ServiceUnavailable);
+    assert_eq!(response.headers.get("retry-after"), Some("121"));
+
+    // sub-second delays are rounded up, not down to zero
+    let response = Error::too_many_requests_after(Duration::from_millis(500), ()).into_response();
+    assert_eq!(response.headers.get("retry-after"), Some("1"));
+}
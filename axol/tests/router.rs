@@ -1,7 +1,8 @@
 use std::borrow::Cow;
 
-use axol::{Path, Query, Router};
-use axol_http::StatusCode;
+use axol::{Path, Query, Router, State};
+use axol_http::{Method, StatusCode};
+use hyper::service::Service;
 use serde::Deserialize;
 
 mod common;
@@ -13,10 +14,27 @@ async fn simple_get() -> &'static str {
 
 async fn empty_get() {}
 
+async fn cow_get() -> Cow<'static, str> {
+    Cow::Borrowed("cow")
+}
+
 async fn simple_path(Path(path): Path<Cow<'_, str>>) -> String {
     format!("success {path}")
 }
 
+async fn wildcard_path(Path(path): Path<String>) -> String {
+    format!("wildcard {path}")
+}
+
+#[derive(Clone)]
+struct AppConfig {
+    greeting: &'static str,
+}
+
+async fn simple_state(State(config): State<AppConfig>) -> &'static str {
+    config.greeting
+}
+
 #[derive(Deserialize)]
 struct SimpleQuery<'a> {
     name: Cow<'a, str>,
@@ -26,14 +44,44 @@ async fn simple_query(Query(SimpleQuery { name }): Query<SimpleQuery<'_>>) -> St
     format!("success {name}")
 }
 
+#[derive(Deserialize)]
+struct TagFilter {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TagQuery {
+    tag: Vec<String>,
+    note: Option<String>,
+    #[serde(rename = "sort key")]
+    sort_key: String,
+    filter: Option<TagFilter>,
+}
+
+async fn tag_query(Query(query): Query<TagQuery>) -> String {
+    format!(
+        "{:?} {:?} {} {:?}",
+        query.tag,
+        query.note,
+        query.sort_key,
+        query.filter.and_then(|x| x.name)
+    )
+}
+
 #[tokio::test]
 async fn router_tests() {
     let handle = spawn_router(
         Router::new()
             .get("/", simple_get)
             .get("/empty", empty_get)
+            .get("/cow", cow_get)
             .get("/var/:var", simple_path)
-            .get("/query", simple_query),
+            .get("/static/*path", wildcard_path)
+            .get("/static/literal", simple_get)
+            .get("/query", simple_query)
+            .get("/tags", tag_query)
+            .get("/state", simple_state)
+            .with_state(AppConfig { greeting: "hello" }),
     )
     .await;
 
@@ -41,13 +89,54 @@ async fn router_tests() {
         .await
         .unwrap();
     assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; charset=utf-8"
+    );
     assert_eq!(&response.bytes().await.unwrap(), &b"success"[..]);
 
+    let response = reqwest::get(format!("http://{}/cow", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(&response.bytes().await.unwrap(), &b"cow"[..]);
+
+    let response = reqwest::Client::new()
+        .head(format!("http://{}/", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(
+        response
+            .headers()
+            .get("content-length")
+            .unwrap()
+            .to_str()
+            .unwrap(),
+        "7"
+    );
+    assert!(response.bytes().await.unwrap().is_empty());
+
     let response = reqwest::get(format!("http://{}/empty", *TEST_ADDRESS))
         .await
         .unwrap();
     assert_eq!(StatusCode::Ok, response.status().into());
 
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/empty", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::MethodNotAllowed, response.status().into());
+    assert_eq!(
+        response.headers().get("allow").unwrap().to_str().unwrap(),
+        "GET, HEAD"
+    );
+
     let response = reqwest::get(format!("http://{}/fake", *TEST_ADDRESS))
         .await
         .unwrap();
@@ -57,8 +146,42 @@ async fn router_tests() {
         .await
         .unwrap();
     assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; charset=utf-8"
+    );
     assert_eq!(&response.bytes().await.unwrap(), &b"success test"[..]);
 
+    let response = reqwest::get(format!("http://{}/static/a/b%20c", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(&response.bytes().await.unwrap(), &b"wildcard a/b c"[..]);
+
+    let response = reqwest::get(format!("http://{}/static/a//b/", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(&response.bytes().await.unwrap(), &b"wildcard a//b/"[..]);
+
+    let response = reqwest::get(format!("http://{}/static/a/", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(&response.bytes().await.unwrap(), &b"wildcard a/"[..]);
+
+    let response = reqwest::get(format!("http://{}/static/literal", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(&response.bytes().await.unwrap(), &b"success"[..]);
+
+    let response = reqwest::get(format!("http://{}/state", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(&response.bytes().await.unwrap(), &b"hello"[..]);
+
     let response = reqwest::get(format!("http://{}/query?name=west", *TEST_ADDRESS))
         .await
         .unwrap();
@@ -71,5 +194,109 @@ async fn router_tests() {
     assert_eq!(StatusCode::Ok, response.status().into());
     assert_eq!(&response.bytes().await.unwrap(), &b"success east"[..]);
 
+    let response = reqwest::get(format!(
+        "http://{}/tags?tag=a&tag=b&note=&sort%20key=name&filter[name]=x",
+        *TEST_ADDRESS
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(
+        response.text().await.unwrap(),
+        r#"["a", "b"] None name Some("x")"#
+    );
+
+    let response = reqwest::get(format!("http://{}/tags?tag=a&sort+key=id", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), r#"["a"] None id None"#);
+
+    let response = reqwest::get(format!(
+        "http://{}/tags?tag=a&sort%20key=id&filter[name]=x&filter[name]=y",
+        *TEST_ADDRESS
+    ))
+    .await
+    .unwrap();
+    assert_eq!(StatusCode::BadRequest, response.status().into());
+    assert!(response.text().await.unwrap().contains("filter[name]"));
+
     handle.abort();
 }
+
+#[test]
+fn route_listing() {
+    let router = Router::new()
+        .get("/", simple_get)
+        .get("/api/:id/items", simple_path)
+        .post("/api/:id/items", simple_path)
+        .fallback("/api", empty_get)
+        .get("/static/*path", wildcard_path);
+
+    let routes = router.routes();
+    assert_eq!(routes.len(), 4);
+    assert_eq!(routes[0].path, "/");
+    assert_eq!(routes[1].path, "/api");
+    assert!(routes[1].has_fallback);
+    assert!(routes[1].methods.is_empty());
+    assert_eq!(routes[2].path, "/api/:id/items");
+    assert_eq!(routes[2].methods, vec![Method::Get, Method::Post]);
+    assert!(!routes[2].has_fallback);
+    assert_eq!(routes[3].path, "/static/*path");
+
+    assert_eq!(
+        router.to_string(),
+        "GET       /\n          /api (fallback)\nGET, POST /api/:id/items\nGET       /static/*path\n"
+    );
+}
+
+#[test]
+fn url_for() {
+    let router = Router::new()
+        .route_named("index", "/", Method::Get, simple_get)
+        .route_named("item", "/api/:id/items/:item", Method::Get, simple_path)
+        .nest(
+            "/static",
+            Router::new().route_named("file", "/*path", Method::Get, wildcard_path),
+        );
+
+    assert_eq!(router.url_for("index", &[]).unwrap(), "/");
+    assert_eq!(
+        router
+            .url_for("item", &[("item", "a b"), ("id", "7/8")])
+            .unwrap(),
+        "/api/7%2F8/items/a%20b"
+    );
+    assert_eq!(
+        router
+            .url_for("file", &[("path", "css/main file.css")])
+            .unwrap(),
+        "/static/css/main%20file.css"
+    );
+    assert!(router.url_for("item", &[("id", "7")]).is_err());
+    assert!(router.url_for("index", &[("id", "7")]).is_err());
+    assert!(router.url_for("missing", &[]).is_err());
+}
+
+#[tokio::test]
+async fn state_per_clone() {
+    let router = Router::new().get("/state", simple_state);
+    let mut english = router
+        .clone()
+        .with_state(AppConfig { greeting: "hello" })
+        .into_service();
+    let mut french = router
+        .with_state(AppConfig {
+            greeting: "bonjour",
+        })
+        .into_service();
+
+    for (service, greeting) in [(&mut english, "hello"), (&mut french, "bonjour")] {
+        let request = hyper::Request::get("/state")
+            .body(hyper::Body::empty())
+            .unwrap();
+        let response = service.call(request).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], greeting.as_bytes());
+    }
+}
@@ -0,0 +1,46 @@
+use axol::{Host, Router, Scheme};
+
+mod common;
+use common::*;
+
+async fn base_url(scheme: Scheme, host: Host) -> String {
+    format!("{scheme}://{host}/ {}", host.hostname())
+}
+
+#[tokio::test]
+async fn scheme_host() {
+    let handle = spawn_router(Router::new().get("/base", base_url)).await;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("http://{}/base", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.text().await.unwrap(),
+        format!("http://{}/ 127.0.0.1", *TEST_ADDRESS)
+    );
+
+    let response = client
+        .get(format!("http://{}/base", *TEST_ADDRESS))
+        .header("host", "example.com")
+        .header("x-forwarded-proto", "https")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.text().await.unwrap(),
+        "https://example.com/ example.com"
+    );
+
+    let response = client
+        .get(format!("http://{}/base", *TEST_ADDRESS))
+        .header("host", "bad host")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    handle.abort();
+}
@@ -0,0 +1,68 @@
+use axol::{Router, SecurityHeaders};
+use axol_http::{response::Response, Body};
+
+mod common;
+use common::*;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+async fn framable() -> Response {
+    let mut response = Response::new(Body::Bytes(b"framable".to_vec()));
+    response.headers.insert("x-frame-options", "ALLOWALL");
+    response
+}
+
+#[tokio::test]
+async fn security_headers() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/default/page", hello)
+            .get("/strict/page", hello)
+            .get("/strict/framable", framable)
+            .plugin("/default", SecurityHeaders::new())
+            .plugin("/strict", SecurityHeaders::strict()),
+    )
+    .await;
+
+    let response = reqwest::get(format!("http://{}/default/page", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    let headers = response.headers();
+    assert_eq!(headers["x-content-type-options"], "nosniff");
+    assert_eq!(headers["x-frame-options"], "SAMEORIGIN");
+    assert_eq!(
+        headers["referrer-policy"],
+        "strict-origin-when-cross-origin"
+    );
+    assert!(headers.get("strict-transport-security").is_none());
+    assert!(headers.get("content-security-policy").is_none());
+
+    let response = reqwest::get(format!("http://{}/strict/page", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    let headers = response.headers();
+    assert_eq!(
+        headers["strict-transport-security"],
+        "max-age=63072000; includeSubDomains"
+    );
+    assert_eq!(headers["x-frame-options"], "DENY");
+    assert_eq!(headers["referrer-policy"], "no-referrer");
+    assert!(headers["content-security-policy"]
+        .to_str()
+        .unwrap()
+        .starts_with("default-src 'self'"));
+
+    // headers set by the handler are kept
+    let response = reqwest::get(format!("http://{}/strict/framable", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers().get_all("x-frame-options").iter().count(),
+        1
+    );
+    assert_eq!(response.headers()["x-frame-options"], "ALLOWALL");
+
+    handle.abort();
+}
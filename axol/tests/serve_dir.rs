@@ -0,0 +1,67 @@
+#![cfg(feature = "fs")]
+
+use axol::{Router, ServeDir};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+#[tokio::test]
+async fn serve_dir_tests() {
+    let handle =
+        spawn_router(Router::new().get("/assets/*path", ServeDir::new(env!("CARGO_MANIFEST_DIR"))))
+            .await;
+
+    let client = reqwest::Client::new();
+    let expected = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap();
+
+    let response = client
+        .get(format!("http://{}/assets/Cargo.toml", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.headers()["content-type"], "text/x-toml");
+    let last_modified = response.headers()["last-modified"].clone();
+    assert_eq!(&response.bytes().await.unwrap()[..], &expected[..]);
+
+    let response = client
+        .get(format!("http://{}/assets/Cargo.toml", *TEST_ADDRESS))
+        .header("if-modified-since", last_modified)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotModified, response.status().into());
+
+    let response = client
+        .get(format!("http://{}/assets/Cargo.toml", *TEST_ADDRESS))
+        .header("range", "bytes=0-99")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::PartialContent, response.status().into());
+    assert_eq!(
+        response.headers()["content-range"],
+        format!("bytes 0-99/{}", expected.len())
+    );
+    assert_eq!(&response.bytes().await.unwrap()[..], &expected[..100]);
+
+    let response = client
+        .get(format!("http://{}/assets/missing.txt", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+
+    let response = client
+        .get(format!(
+            "http://{}/assets/src/..%2F..%2FCargo.toml",
+            *TEST_ADDRESS
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Forbidden, response.status().into());
+
+    handle.abort();
+}
@@ -0,0 +1,31 @@
+use std::net::SocketAddr;
+
+use axol::{ConnectInfo, Router};
+use hyper::service::Service;
+
+async fn peer(ConnectInfo(address): ConnectInfo) -> String {
+    address.to_string()
+}
+
+#[tokio::test]
+async fn service_tests() {
+    let address: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+    let mut service = Router::new()
+        .get("/peer", peer)
+        .into_service()
+        .connect_info(address);
+
+    let request = hyper::Request::get("/peer")
+        .body(hyper::Body::empty())
+        .unwrap();
+    let response = service.call(request).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(&body[..], b"10.0.0.1:1234");
+
+    let request = hyper::Request::get("/missing")
+        .body(hyper::Body::empty())
+        .unwrap();
+    let response = service.call(request).await.unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "session")]
+
+use axol::{Key, MemoryStore, Result, Router, Session, Sessions};
+
+mod common;
+use common::*;
+
+async fn login(session: Session) -> Result<&'static str> {
+    session.destroy().await?;
+    session.insert("user", "alice").await?;
+    Ok("logged in")
+}
+
+async fn whoami(session: Session) -> Result<String> {
+    Ok(session.get::<String>("user").await?.unwrap_or_default())
+}
+
+async fn remember(session: Session) -> Result<&'static str> {
+    session.insert("theme", "dark").await?;
+    Ok("remembered")
+}
+
+async fn logout(session: Session) -> Result<&'static str> {
+    session.destroy().await?;
+    Ok("logged out")
+}
+
+fn session_cookie(response: &reqwest::Response) -> Option<String> {
+    let header = response.headers().get("set-cookie")?.to_str().unwrap();
+    Some(header.split(';').next().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn session_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/login", login)
+            .get("/whoami", whoami)
+            .get("/remember", remember)
+            .get("/logout", logout)
+            .plugin(
+                "/",
+                Sessions::new(MemoryStore::new(), Key::generate()).secure(false),
+            ),
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let url = |path: &str| format!("http://{}{path}", *TEST_ADDRESS);
+
+    // reading an empty session doesn't create one
+    let response = client.get(url("/whoami")).send().await.unwrap();
+    assert!(session_cookie(&response).is_none());
+    assert_eq!(response.text().await.unwrap(), "");
+
+    let response = client.get(url("/login")).send().await.unwrap();
+    let cookie = session_cookie(&response).unwrap();
+    assert!(cookie.starts_with("session="));
+
+    let response = client
+        .get(url("/whoami"))
+        .header("cookie", &cookie)
+        .send()
+        .await
+        .unwrap();
+    assert!(session_cookie(&response).is_none());
+    assert_eq!(response.text().await.unwrap(), "alice");
+
+    // logging in again rotates the session id
+    let response = client
+        .get(url("/login"))
+        .header("cookie", &cookie)
+        .send()
+        .await
+        .unwrap();
+    let rotated = session_cookie(&response).unwrap();
+    assert_ne!(rotated, cookie);
+
+    let response = client
+        .get(url("/whoami"))
+        .header("cookie", &cookie)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "");
+
+    let response = client
+        .get(url("/logout"))
+        .header("cookie", &rotated)
+        .send()
+        .await
+        .unwrap();
+    let removal = response.headers()["set-cookie"].to_str().unwrap();
+    assert!(removal.starts_with("session=;"));
+    assert!(removal.contains("Max-Age=0"));
+
+    let response = client
+        .get(url("/whoami"))
+        .header("cookie", &rotated)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "");
+
+    // the destroyed id isn't reused, even though its cookie is still validly signed
+    let response = client
+        .get(url("/remember"))
+        .header("cookie", &rotated)
+        .send()
+        .await
+        .unwrap();
+    let fresh = session_cookie(&response).unwrap();
+    assert_ne!(fresh, rotated);
+
+    // unsigned ids are ignored
+    let response = client
+        .get(url("/remember"))
+        .header("cookie", "session=chosen-by-client")
+        .send()
+        .await
+        .unwrap();
+    let fresh = session_cookie(&response).unwrap();
+    assert!(!fresh.contains("chosen-by-client"));
+
+    handle.abort();
+}
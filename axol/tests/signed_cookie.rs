@@ -0,0 +1,98 @@
+#![cfg(all(feature = "cookie-signed", feature = "cookie-private"))]
+
+use axol::{Cookie, Key, PrivateCookieJar, Router, SignedCookieJar};
+use axol_http::StatusCode;
+
+mod common;
+use common::*;
+
+async fn set_signed(jar: SignedCookieJar) -> (SignedCookieJar, &'static str) {
+    (jar.add(Cookie::new("user", "alice")), "set")
+}
+
+async fn get_signed(jar: SignedCookieJar) -> String {
+    jar.get("user")
+        .map(|x| x.value().to_string())
+        .unwrap_or_default()
+}
+
+async fn set_private(jar: PrivateCookieJar) -> (PrivateCookieJar, &'static str) {
+    (jar.add(Cookie::new("secret", "hunter2")), "set")
+}
+
+async fn get_private(jar: PrivateCookieJar) -> String {
+    jar.get("secret")
+        .map(|x| x.value().to_string())
+        .unwrap_or_default()
+}
+
+fn set_cookie(response: &reqwest::Response) -> String {
+    let header = response.headers()["set-cookie"].to_str().unwrap();
+    header.split(';').next().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn signed_cookie_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/signed/set", set_signed)
+            .get("/signed/get", get_signed)
+            .get("/private/set", set_private)
+            .get("/private/get", get_private)
+            .get("/nokey", get_signed)
+            .extension("/signed", Key::generate())
+            .extension("/private", Key::generate()),
+    )
+    .await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/signed/set", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    let cookie = set_cookie(&response);
+    assert!(cookie.starts_with("user=") && cookie.ends_with("alice"));
+
+    let response = client
+        .get(format!("http://{}/signed/get", *TEST_ADDRESS))
+        .header("cookie", &cookie)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "alice");
+
+    // tampered values fail verification
+    let response = client
+        .get(format!("http://{}/signed/get", *TEST_ADDRESS))
+        .header("cookie", cookie.replace("alice", "mallory"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "");
+
+    let response = client
+        .get(format!("http://{}/private/set", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    let cookie = set_cookie(&response);
+    assert!(!cookie.contains("hunter2"));
+
+    let response = client
+        .get(format!("http://{}/private/get", *TEST_ADDRESS))
+        .header("cookie", &cookie)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "hunter2");
+
+    let response = client
+        .get(format!("http://{}/nokey", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::InternalServerError, response.status().into());
+
+    handle.abort();
+}
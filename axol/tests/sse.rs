@@ -0,0 +1,31 @@
+use axol::{Event, Result, Router, Sse};
+use axol_http::StatusCode;
+use futures::Stream;
+
+mod common;
+use common::*;
+
+async fn events() -> Sse<impl Stream<Item = Result<Event>>> {
+    Sse::new(futures::stream::iter([
+        Ok(Event::default().event("greeting").data("hello\nworld")),
+        Ok(Event::default().id("2").data("bye")),
+    ]))
+}
+
+#[tokio::test]
+async fn sse_tests() {
+    let handle = spawn_router(Router::new().get("/events", events)).await;
+
+    let response = reqwest::get(format!("http://{}/events", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    assert_eq!(response.headers()["cache-control"], "no-cache");
+    assert_eq!(
+        response.text().await.unwrap(),
+        "event: greeting\ndata: hello\ndata: world\n\nid: 2\ndata: bye\n\n"
+    );
+
+    handle.abort();
+}
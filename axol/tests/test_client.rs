@@ -0,0 +1,49 @@
+#![cfg(feature = "test-util")]
+
+use axol::{Json, Router};
+use axol_http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Greeting {
+    message: String,
+}
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+async fn echo(Json(greeting): Json<Greeting>) -> Json<Greeting> {
+    Json(greeting)
+}
+
+#[tokio::test]
+async fn test_client_tests() {
+    let client = Router::new()
+        .get("/hello", hello)
+        .post("/echo", echo)
+        .test_client();
+
+    let response = client.get("/hello").await;
+    assert_eq!(response.status, StatusCode::Ok);
+    assert_eq!(response.text(), "hello");
+
+    let response = client.get("/missing").await;
+    assert_eq!(response.status, StatusCode::NotFound);
+
+    let mut request = axol_http::Request {
+        method: axol_http::Method::Post,
+        uri: "/echo".parse().unwrap(),
+        body: r#"{"message":"hi"}"#.into(),
+        ..Default::default()
+    };
+    request.headers.insert("content-type", "application/json");
+    let response = client.request(request).await;
+    assert_eq!(response.status, StatusCode::Ok);
+    assert_eq!(
+        response.json::<Greeting>(),
+        Greeting {
+            message: "hi".to_string()
+        }
+    );
+}
@@ -0,0 +1,108 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use axol::{Cancellation, Router, Timeout, TimeoutWithBody};
+use axol_http::{body::BodyComponent, response::Response, Body, StatusCode};
+
+mod common;
+use common::*;
+
+async fn fast() -> &'static str {
+    "fast"
+}
+
+static CANCELLED: AtomicUsize = AtomicUsize::new(0);
+
+async fn slow(cancellation: Cancellation) -> &'static str {
+    let token = cancellation.0.clone();
+    tokio::spawn(async move {
+        token.cancelled().await;
+        CANCELLED.fetch_add(1, Ordering::SeqCst);
+    });
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    "slow"
+}
+
+/// Sends `hel` right away, and `lo` after `delay`.
+fn delayed_body(delay: Duration) -> Response {
+    Response {
+        body: Body::Stream {
+            size_hint: None,
+            stream: Box::pin(futures::stream::unfold(0, move |step| async move {
+                match step {
+                    0 => Some((Ok(BodyComponent::Data("hel".into())), 1)),
+                    1 => {
+                        tokio::time::sleep(delay).await;
+                        Some((Ok(BodyComponent::Data("lo".into())), 2))
+                    }
+                    _ => None,
+                }
+            })),
+        },
+        ..Default::default()
+    }
+}
+
+async fn quick_stream() -> Response {
+    delayed_body(Duration::from_millis(10))
+}
+
+async fn slow_stream() -> Response {
+    delayed_body(Duration::from_secs(5))
+}
+
+#[tokio::test]
+async fn timeout_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/timeout/fast", fast)
+            .get("/timeout/slow", slow)
+            .get("/timeout/stream", slow_stream)
+            .wrap("/timeout", Timeout(Duration::from_millis(200)))
+            .get("/body/slow", slow)
+            .get("/body/quick", quick_stream)
+            .get("/body/stream", slow_stream)
+            .wrap("/body", TimeoutWithBody(Duration::from_millis(200))),
+    )
+    .await;
+
+    let get = |path: &'static str| reqwest::get(format!("http://{}{path}", *TEST_ADDRESS));
+
+    let response = get("/timeout/fast").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "fast");
+
+    let response = get("/timeout/slow").await.unwrap();
+    assert_eq!(StatusCode::RequestTimeout, response.status().into());
+
+    let response = get("/body/slow").await.unwrap();
+    assert_eq!(StatusCode::RequestTimeout, response.status().into());
+    // both timeouts cancel the request's `Cancellation`
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(CANCELLED.load(Ordering::SeqCst), 2);
+
+    let response = get("/body/quick").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "hello");
+
+    // `TimeoutWithBody` cuts the body off at the deadline, after the headers were sent
+    let started = std::time::Instant::now();
+    let response = get("/body/stream").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert!(response.bytes().await.is_err());
+    assert!(started.elapsed() < Duration::from_secs(2));
+
+    // `Timeout` doesn't cover the body
+    let mut response = get("/timeout/stream").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(&response.chunk().await.unwrap().unwrap()[..], b"hel");
+    assert!(
+        tokio::time::timeout(Duration::from_millis(400), response.chunk())
+            .await
+            .is_err()
+    );
+
+    handle.abort();
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use axol::{Router, Server, TlsIncoming};
+use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore, ServerConfig, ServerName};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+
+mod common;
+use common::*;
+
+fn self_signed() -> (Certificate, PrivateKey) {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    (
+        Certificate(cert.serialize_der().unwrap()),
+        PrivateKey(cert.serialize_private_key_der()),
+    )
+}
+
+fn server_config((cert, key): (Certificate, PrivateKey)) -> ServerConfig {
+    ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(vec![cert], key)
+        .unwrap()
+}
+
+async fn peer_certificate(roots: &RootCertStore) -> Certificate {
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots.clone())
+        .with_no_client_auth();
+    let tcp = TcpStream::connect(*TEST_ADDRESS).await.unwrap();
+    let stream = TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), tcp)
+        .await
+        .unwrap();
+    stream.get_ref().1.peer_certificates().unwrap()[0].clone()
+}
+
+#[tokio::test]
+async fn tls_reload_tests() {
+    let first = self_signed();
+    let second = self_signed();
+    let mut roots = RootCertStore::empty();
+    roots.add(&first.0).unwrap();
+    roots.add(&second.0).unwrap();
+
+    let (incoming, reloader) = TlsIncoming::new_reloadable(
+        *TEST_ADDRESS,
+        true,
+        None,
+        Some(server_config(first.clone())),
+    )
+    .unwrap();
+    let handle = tokio::spawn(
+        Server::builder()
+            .incoming(incoming.start())
+            .router(Router::new())
+            .serve(),
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    assert_eq!(peer_certificate(&roots).await, first.0);
+
+    reloader.reload(server_config(second.clone()));
+    assert_eq!(peer_certificate(&roots).await, second.0);
+
+    handle.abort();
+}
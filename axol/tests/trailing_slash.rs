@@ -0,0 +1,59 @@
+use axol::{Router, TrailingSlash};
+use axol_http::StatusCode;
+use hyper::service::Service;
+
+mod common;
+use common::*;
+
+async fn page() -> &'static str {
+    "page"
+}
+
+#[tokio::test]
+async fn trailing_slash_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/", page)
+            .get("/docs/:name", page)
+            .trailing_slash(TrailingSlash::RedirectToNoSlash),
+    )
+    .await;
+
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+    let get = |path: &'static str| client.get(format!("http://{}{path}", *TEST_ADDRESS)).send();
+
+    let response = get("/docs/intro").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+
+    // the query string and percent-encoding are kept
+    let response = get("/docs/getting%20started/?lang=en&v=2").await.unwrap();
+    assert_eq!(StatusCode::PermanentRedirect, response.status().into());
+    assert_eq!(
+        response.headers()["location"],
+        "/docs/getting%20started?lang=en&v=2"
+    );
+
+    // the root is never redirected
+    let response = get("/").await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+
+    // paths that don't resolve aren't redirected
+    let response = get("/missing/").await.unwrap();
+    assert_eq!(StatusCode::NotFound, response.status().into());
+
+    handle.abort();
+
+    let mut service = Router::new()
+        .get("/docs/:name", page)
+        .trailing_slash(TrailingSlash::RedirectToSlash)
+        .into_service();
+    let request = hyper::Request::get("/docs/intro?lang=en")
+        .body(hyper::Body::empty())
+        .unwrap();
+    let response = service.call(request).await.unwrap();
+    assert_eq!(response.status(), 308);
+    assert_eq!(response.headers()["location"], "/docs/intro/?lang=en");
+}
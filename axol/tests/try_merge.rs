@@ -0,0 +1,77 @@
+use axol::{RouteConflict, Router};
+use axol_http::Method;
+
+mod common;
+use common::*;
+
+async fn a() -> &'static str {
+    "a"
+}
+
+async fn b() -> &'static str {
+    "b"
+}
+
+#[tokio::test]
+async fn try_merge() {
+    let conflict = Router::new()
+        .get("/users/:id", a)
+        .get("/items/:item", a)
+        .try_merge(
+            Router::new()
+                .get("/users/:id", b)
+                .post("/users/:id", b)
+                .get("/items/:name", b),
+        )
+        .unwrap_err();
+    assert_eq!(
+        conflict.conflicts,
+        vec![
+            RouteConflict::Method {
+                path: "/users/:id".to_string(),
+                method: Method::Get,
+            },
+            RouteConflict::SegmentName {
+                path: "/items/:name".to_string(),
+                existing: ":item".to_string(),
+            },
+        ]
+    );
+
+    let conflict = Router::new()
+        .get("/api/:id", a)
+        .try_nest("/api/:name", Router::new().get("/", b))
+        .unwrap_err();
+    assert_eq!(
+        conflict.conflicts,
+        vec![RouteConflict::SegmentName {
+            path: "/api/:name".to_string(),
+            existing: ":id".to_string(),
+        }]
+    );
+
+    let handle = spawn_router(
+        Router::new()
+            .get("/a", a)
+            .try_nest("/nested", Router::new().get("/b", b))
+            .unwrap()
+            .try_merge(Router::new().post("/a", b))
+            .unwrap(),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let get = |method: reqwest::Method, path: &'static str| {
+        client
+            .request(method, format!("http://{}{path}", *TEST_ADDRESS))
+            .send()
+    };
+    let response = get(reqwest::Method::GET, "/a").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "a");
+    let response = get(reqwest::Method::POST, "/a").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "b");
+    let response = get(reqwest::Method::GET, "/nested/b").await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "b");
+
+    handle.abort();
+}
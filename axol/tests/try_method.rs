@@ -0,0 +1,68 @@
+use axol::{RouteConflict, Router, RouterError};
+use axol_http::Method;
+
+mod common;
+use common::*;
+
+async fn a() -> &'static str {
+    "a"
+}
+
+async fn b() -> &'static str {
+    "b"
+}
+
+#[tokio::test]
+async fn try_method() {
+    let error = Router::new()
+        .get("/users/:id", a)
+        .try_get("/users/:user_id/posts", b)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        RouterError::Conflict(RouteConflict::SegmentName {
+            path: "/users/:user_id".to_string(),
+            existing: ":id".to_string(),
+        })
+    );
+
+    let error = Router::new()
+        .get("/users/:id", a)
+        .try_get("/users/:id", b)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        RouterError::Conflict(RouteConflict::Method {
+            path: "/users/:id".to_string(),
+            method: Method::Get,
+        })
+    );
+
+    let error = Router::new().try_get("/static/*path/more", a).unwrap_err();
+    assert!(matches!(error, RouterError::WildcardNotLast { .. }));
+
+    let handle = spawn_router(
+        Router::new()
+            .try_get("/users/:id", a)
+            .unwrap()
+            .try_post("/users/:id", b)
+            .unwrap(),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("http://{}/users/1", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "a");
+    let response = client
+        .post(format!("http://{}/users/1", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "b");
+
+    handle.abort();
+}
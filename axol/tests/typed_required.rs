@@ -0,0 +1,64 @@
+use axol::{Router, Typed, TypedRequired};
+use axol_http::{
+    typed_headers::{IfModifiedSince, UserAgent},
+    StatusCode,
+};
+
+mod common;
+use common::*;
+
+async fn required(TypedRequired(since): TypedRequired<IfModifiedSince>) -> String {
+    format!("{since:?}")
+}
+
+async fn optional(agent: Option<Typed<UserAgent>>) -> String {
+    agent.map(|x| x.0.to_string()).unwrap_or_default()
+}
+
+#[tokio::test]
+async fn typed_required_tests() {
+    let handle = spawn_router(
+        Router::new()
+            .get("/required", required)
+            .get("/optional", optional),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+    let url = |path: &str| format!("http://{}{path}", *TEST_ADDRESS);
+
+    let response = client
+        .get(url("/required"))
+        .header("if-modified-since", "Sun, 06 Nov 1994 08:49:37 GMT")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+
+    // missing and malformed headers are told apart
+    let response = client.get(url("/required")).send().await.unwrap();
+    assert_eq!(StatusCode::BadRequest, response.status().into());
+    assert_eq!(
+        response.text().await.unwrap(),
+        "missing header `if-modified-since`"
+    );
+
+    let response = client
+        .get(url("/required"))
+        .header("if-modified-since", "yesterday")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(StatusCode::BadRequest, response.status().into());
+    assert!(response
+        .text()
+        .await
+        .unwrap()
+        .starts_with("malformed header `if-modified-since`"));
+
+    let response = client.get(url("/optional")).send().await.unwrap();
+    assert_eq!(StatusCode::Ok, response.status().into());
+    assert_eq!(response.text().await.unwrap(), "");
+
+    handle.abort();
+}
@@ -0,0 +1,34 @@
+#![cfg(unix)]
+
+use axol::{Router, Server};
+use tokio::net::UnixStream;
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn unix_socket_tests() {
+    let path = std::env::temp_dir().join(format!("axol-test-{}.sock", std::process::id()));
+    std::fs::remove_file(&path).ok();
+
+    let server = Server::bind_unix(&path)
+        .unwrap()
+        .router(Router::new().get("/hello", hello));
+    let handle = tokio::spawn(server.serve());
+
+    let stream = UnixStream::connect(&path).await.unwrap();
+    let (mut sender, connection) = hyper::client::conn::handshake(stream).await.unwrap();
+    tokio::spawn(connection);
+    let request = hyper::Request::get("/hello")
+        .header("host", "localhost")
+        .body(hyper::Body::empty())
+        .unwrap();
+    let response = sender.send_request(request).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(&body[..], b"hello");
+
+    handle.abort();
+    std::fs::remove_file(&path).ok();
+}
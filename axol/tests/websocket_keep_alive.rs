@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use axol::{Message, Router, WebSocketUpgrade};
+use axol_http::response::Response;
+
+mod common;
+use common::*;
+use futures::StreamExt;
+use tokio_tungstenite::tungstenite::Message as TTMessage;
+
+async fn keep_alive_ws(ws: WebSocketUpgrade) -> Response {
+    ws.keep_alive(Duration::from_millis(100), Duration::from_millis(100))
+        .on_upgrade(|mut c| async move {
+            let error = c.recv().await.unwrap().unwrap_err();
+            assert!(c.recv().await.is_none());
+            c.send(Message::Text(error.to_string())).await.unwrap();
+        })
+}
+
+#[tokio::test]
+async fn websocket_keep_alive_tests() {
+    let handle = spawn_router(Router::new().get("/ws", keep_alive_ws)).await;
+
+    let (mut stream, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", *TEST_ADDRESS))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        stream.next().await.unwrap().unwrap(),
+        TTMessage::Ping(_)
+    ));
+    // the pong is only sent on the next read, so the server times out meanwhile
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    match stream.next().await.unwrap().unwrap() {
+        TTMessage::Text(text) => assert!(text.contains("timed out")),
+        message => panic!("unexpected message at client: {message:?}"),
+    }
+
+    handle.abort();
+}
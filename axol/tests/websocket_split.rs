@@ -0,0 +1,78 @@
+use axol::{Message, Router, State, WebSocketUpgrade};
+use axol_http::response::Response;
+use tokio::{net::TcpStream, sync::broadcast};
+
+mod common;
+use common::*;
+use futures::{SinkExt, StreamExt};
+use tokio_tungstenite::{tungstenite::Message as TTMessage, MaybeTlsStream, WebSocketStream};
+
+#[derive(Clone)]
+struct Chat(broadcast::Sender<String>);
+
+async fn chat_ws(ws: WebSocketUpgrade, State(chat): State<Chat>) -> Response {
+    ws.on_upgrade(|socket| async move {
+        let (mut sender, mut receiver) = socket.split();
+        let mut messages = chat.0.subscribe();
+        let forward = tokio::spawn(async move {
+            while let Ok(text) = messages.recv().await {
+                if sender.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+        });
+        while let Some(Ok(message)) = receiver.recv().await {
+            if let Message::Text(text) = message {
+                chat.0.send(text).ok();
+            }
+        }
+        forward.abort();
+    })
+}
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn expect_text(stream: &mut Client) -> String {
+    match stream.next().await.unwrap().unwrap() {
+        TTMessage::Text(text) => text,
+        message => panic!("unexpected message at client: {message:?}"),
+    }
+}
+
+#[tokio::test]
+async fn websocket_split_tests() {
+    let (chat, _) = broadcast::channel(16);
+    let handle = spawn_router(
+        Router::new()
+            .get("/ws", chat_ws)
+            .with_state(Chat(chat.clone())),
+    )
+    .await;
+
+    let (mut first, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    let (mut second, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", *TEST_ADDRESS))
+        .await
+        .unwrap();
+    // wait for both server sockets to subscribe
+    while chat.receiver_count() < 2 {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    first
+        .send(TTMessage::Text("hello".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(expect_text(&mut first).await, "hello");
+    assert_eq!(expect_text(&mut second).await, "hello");
+
+    second
+        .send(TTMessage::Text("world".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(expect_text(&mut first).await, "world");
+    assert_eq!(expect_text(&mut second).await, "world");
+
+    handle.abort();
+}
@@ -0,0 +1,54 @@
+use axol::{Result, Router, When, Wrap, WrapState};
+use axol_http::{request::RequestPartsRef, response::Response};
+
+mod common;
+use common::*;
+
+struct Stamp;
+
+#[async_trait::async_trait]
+impl Wrap for Stamp {
+    async fn wrap(&self, state: WrapState<'_>) -> Result<Response> {
+        let mut response = state.next().await?;
+        response.headers.insert("x-stamp", "1");
+        Ok(response)
+    }
+}
+
+async fn hello() -> &'static str {
+    "hello"
+}
+
+#[tokio::test]
+async fn when() {
+    let handle = spawn_router(Router::new().get("/", hello).wrap(
+        "/",
+        When(
+            |request: RequestPartsRef<'_>| {
+                request.headers.get("accept") == Some("application/json")
+            },
+            Stamp,
+        ),
+    ))
+    .await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://{}/", *TEST_ADDRESS))
+        .header("accept", "application/json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["x-stamp"], "1");
+    assert_eq!(response.text().await.unwrap(), "hello");
+
+    let response = client
+        .get(format!("http://{}/", *TEST_ADDRESS))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.headers().get("x-stamp").is_none());
+    assert_eq!(response.text().await.unwrap(), "hello");
+
+    handle.abort();
+}